@@ -0,0 +1,48 @@
+//! Compares `bits` and `set_bits` with hand-written shifts and masks.
+//!
+//! Run with `cargo bench`.
+
+use intbits::Bits;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const N: usize = 1 << 16;
+const ROUNDS: u32 = 200;
+
+fn bench(name: &str, mut f: impl FnMut(&[u32]) -> u32) {
+	let data: Vec<u32> = (0..N as u32).map(|i| i.wrapping_mul(0x9E37_79B9)).collect();
+	let mut best = Duration::MAX;
+	for _ in 0..ROUNDS {
+		let start = Instant::now();
+		black_box(f(black_box(&data)));
+		best = best.min(start.elapsed());
+	}
+	println!(
+		"{:<24} {:>8.3} ns/iter",
+		name,
+		best.as_secs_f64() * 1e9 / N as f64
+	);
+}
+
+fn main() {
+	bench("bits, constant", |d| {
+		d.iter().fold(0, |acc, &x| acc ^ x.bits(5..17))
+	});
+	bench("shift, constant", |d| {
+		d.iter().fold(0, |acc, &x| acc ^ (x >> 5 & 0xFFF))
+	});
+	bench("bits, variable", |d| {
+		d.iter().fold(0, |acc, &x| acc ^ x.bits(x % 16..x % 16 + 8))
+	});
+	bench("shift, variable", |d| {
+		d.iter().fold(0, |acc, &x| acc ^ (x >> (x % 16) & 0xFF))
+	});
+	bench("set_bits, constant", |d| {
+		d.iter()
+			.fold(0, |acc, &x| acc.with_bits(8..16, (acc ^ x) & 0xFF))
+	});
+	bench("shift set, constant", |d| {
+		d.iter()
+			.fold(0, |acc, &x| acc & !0xFF00 | ((acc ^ x) & 0xFF) << 8)
+	});
+}
@@ -0,0 +1,58 @@
+//! Functions for inspecting the generated code of constant bit ranges.
+//!
+//! Each of these should compile to a few shift and mask instructions, without
+//! branches or panics. This is checked by `tests/codegen.rs`. To look at the
+//! code yourself, run:
+//!
+//! ```text
+//! cargo rustc --release --example codegen -- --emit asm
+//! ```
+
+use intbits::{Bits, ConstBits};
+
+#[no_mangle]
+pub fn get_bit(x: u32) -> bool {
+	x.bit(7)
+}
+
+#[no_mangle]
+pub fn get_bits(x: u32) -> u32 {
+	x.bits(4..12)
+}
+
+#[no_mangle]
+pub fn get_bits_inclusive(x: u64) -> u64 {
+	x.bits(40..=47)
+}
+
+#[no_mangle]
+pub fn get_bits_signed(x: i16) -> u16 {
+	x.bits(3..)
+}
+
+#[no_mangle]
+pub fn set_bit(x: &mut u32, bit: bool) {
+	x.set_bit(7, bit)
+}
+
+#[no_mangle]
+pub fn set_bits(x: &mut u32, v: u32) {
+	x.set_bits(4..12, v & 0xFF)
+}
+
+#[no_mangle]
+pub fn with_bits(x: u8, v: u8) -> u8 {
+	x.with_bits(..4, v >> 4)
+}
+
+#[no_mangle]
+pub fn const_bits(x: u32) -> u32 {
+	x.const_bits::<12, 8>()
+}
+
+#[no_mangle]
+pub fn set_const_bits(x: &mut u64, v: u64) {
+	x.set_const_bits::<32, 16>(v & 0xFFFF)
+}
+
+fn main() {}
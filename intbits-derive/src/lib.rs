@@ -0,0 +1,254 @@
+//! Derive macros for [`intbits`](https://docs.rs/intbits).
+//!
+//! Don't use this crate directly. Enable the `derive` feature of `intbits`
+//! and use `intbits::FromBits`, `intbits::IntoBits` and `intbits::BitsIndex`
+//! instead.
+
+extern crate proc_macro;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Expr, Fields, RangeLimits, Type};
+
+/// Derive `intbits::FromBits` for a struct with `#[bits(..)]` fields.
+#[proc_macro_derive(FromBits, attributes(bits))]
+pub fn derive_from_bits(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input = syn::parse_macro_input!(input as DeriveInput);
+	expand_from_bits(&input)
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
+
+/// Derive `intbits::IntoBits` for a struct with `#[bits(..)]` fields.
+#[proc_macro_derive(IntoBits, attributes(bits))]
+pub fn derive_into_bits(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input = syn::parse_macro_input!(input as DeriveInput);
+	expand_into_bits(&input)
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
+
+/// Derive `intbits::BitsIndex` for a fieldless enum, using the discriminants
+/// as bit indices.
+#[proc_macro_derive(BitsIndex)]
+pub fn derive_bits_index(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input = syn::parse_macro_input!(input as DeriveInput);
+	expand_bits_index(&input)
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
+
+struct Layout {
+	repr: Type,
+	fields: Vec<Field>,
+}
+
+struct Field {
+	ident: syn::Ident,
+	ty: Type,
+	start: TokenStream,
+	end: TokenStream,
+	single: bool,
+	span: Span,
+}
+
+impl Field {
+	fn is_bool(&self) -> bool {
+		matches!(&self.ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident("bool"))
+	}
+}
+
+fn bits_attr(attrs: &[syn::Attribute]) -> Option<&syn::Attribute> {
+	attrs.iter().find(|a| a.path().is_ident("bits"))
+}
+
+fn parse_layout(input: &DeriveInput) -> syn::Result<Layout> {
+	let repr = match bits_attr(&input.attrs) {
+		Some(attr) => attr.parse_args::<Type>()?,
+		None => {
+			return Err(Error::new(
+				Span::call_site(),
+				"missing #[bits(T)] attribute specifying the underlying integer type",
+			))
+		}
+	};
+	let named = match &input.data {
+		Data::Struct(s) => match &s.fields {
+			Fields::Named(named) => named,
+			_ => {
+				return Err(Error::new(
+					Span::call_site(),
+					"only structs with named fields are supported",
+				))
+			}
+		},
+		_ => return Err(Error::new(Span::call_site(), "only structs are supported")),
+	};
+	let mut fields = Vec::new();
+	for field in &named.named {
+		let ident = field.ident.clone().unwrap();
+		let attr = bits_attr(&field.attrs)
+			.ok_or_else(|| Error::new(field.span(), "missing #[bits(..)] attribute on field"))?;
+		let expr = attr.parse_args::<Expr>()?;
+		let (start, end, single) = match &expr {
+			Expr::Range(r) => {
+				let start = match &r.start {
+					Some(s) => quote!((#s) as u32),
+					None => quote!(0u32),
+				};
+				let end = match (&r.end, &r.limits) {
+					(Some(e), RangeLimits::HalfOpen(_)) => quote!((#e) as u32),
+					(Some(e), RangeLimits::Closed(_)) => quote!((#e) as u32 + 1),
+					(None, _) => quote!(<#repr as ::intbits::Bits>::N_BITS),
+				};
+				(start, end, false)
+			}
+			e => (quote!((#e) as u32), quote!((#e) as u32 + 1), true),
+		};
+		let field = Field {
+			ident,
+			ty: field.ty.clone(),
+			start,
+			end,
+			single,
+			span: expr.span(),
+		};
+		if field.is_bool() && !field.single {
+			return Err(Error::new(
+				field.span,
+				"bool fields must use a single bit index, like #[bits(3)]",
+			));
+		}
+		fields.push(field);
+	}
+	Ok(Layout { repr, fields })
+}
+
+/// Compile-time checks for the layout, evaluated in a `const` item.
+fn layout_checks(layout: &Layout) -> TokenStream {
+	let repr = &layout.repr;
+	let ranges = layout.fields.iter().map(|f| {
+		let (start, end) = (&f.start, &f.end);
+		quote_spanned!(f.span=> (#start, #end))
+	});
+	let widths = layout.fields.iter().filter(|f| !f.is_bool()).map(|f| {
+		let (ty, start, end) = (&f.ty, &f.start, &f.end);
+		quote_spanned! {f.span=>
+			const _: () = ::intbits::__private::check_field_width(
+				#end - #start,
+				<#ty as ::intbits::Bits>::N_BITS,
+			);
+			const _: () = ::intbits::__private::check_field_type(
+				<#ty as ::intbits::Bits>::N_BITS,
+				<#repr as ::intbits::Bits>::N_BITS,
+			);
+		}
+	});
+	quote! {
+		const _: () = ::intbits::__private::check_layout(
+			<#repr as ::intbits::Bits>::N_BITS,
+			&[#(#ranges),*],
+		);
+		#(#widths)*
+	}
+}
+
+fn expand_from_bits(input: &DeriveInput) -> syn::Result<TokenStream> {
+	let layout = parse_layout(input)?;
+	let repr = &layout.repr;
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let checks = layout_checks(&layout);
+	let fields = layout.fields.iter().map(|f| {
+		let (ident, ty, start, end) = (&f.ident, &f.ty, &f.start, &f.end);
+		if f.is_bool() {
+			quote_spanned!(f.span=> #ident: ::intbits::Bits::bit(bits, #start))
+		} else {
+			quote_spanned!(f.span=> #ident: ::intbits::Bits::bits(bits, #start..#end) as #ty)
+		}
+	});
+	Ok(quote! {
+		#checks
+		impl #impl_generics ::intbits::FromBits<#repr> for #name #ty_generics #where_clause {
+			#[inline]
+			fn from_bits(bits: #repr) -> Self {
+				Self {
+					#(#fields,)*
+				}
+			}
+		}
+	})
+}
+
+fn expand_into_bits(input: &DeriveInput) -> syn::Result<TokenStream> {
+	let layout = parse_layout(input)?;
+	let repr = &layout.repr;
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let checks = layout_checks(&layout);
+	let fields = layout.fields.iter().map(|f| {
+		let (ident, start, end) = (&f.ident, &f.start, &f.end);
+		if f.is_bool() {
+			quote_spanned!(f.span=> ::intbits::Bits::set_bit(&mut bits, #start, self.#ident);)
+		} else {
+			quote_spanned! {f.span=>
+				::intbits::Bits::set_bits(
+					&mut bits,
+					#start..#end,
+					::intbits::__private::field_bits(self.#ident),
+				);
+			}
+		}
+	});
+	Ok(quote! {
+		#checks
+		impl #impl_generics ::intbits::IntoBits<#repr> for #name #ty_generics #where_clause {
+			#[inline]
+			fn into_bits(self) -> #repr {
+				let mut bits: #repr = 0;
+				#(#fields)*
+				bits
+			}
+		}
+	})
+}
+
+fn expand_bits_index(input: &DeriveInput) -> syn::Result<TokenStream> {
+	let variants = match &input.data {
+		Data::Enum(e) => &e.variants,
+		_ => return Err(Error::new(Span::call_site(), "only enums are supported")),
+	};
+	if !input.generics.params.is_empty() {
+		return Err(Error::new(
+			input.generics.span(),
+			"generic enums are not supported",
+		));
+	}
+	let name = &input.ident;
+	let mut arms = Vec::new();
+	for v in variants {
+		if !matches!(v.fields, Fields::Unit) {
+			return Err(Error::new(
+				v.span(),
+				"only enums without fields are supported",
+			));
+		}
+		let ident = &v.ident;
+		arms.push(quote!(#name::#ident => #name::#ident as u32));
+	}
+	// Which integer types implement `Bits` depends on the features of
+	// intbits, so the impls are generated by a macro of intbits itself.
+	Ok(quote! {
+		impl #name {
+			#[inline]
+			fn __intbits_index(&self) -> u32 {
+				match self {
+					#(#arms,)*
+				}
+			}
+		}
+
+		::intbits::__impl_enum_index!(#name);
+	})
+}
@@ -0,0 +1,171 @@
+use super::range::bounds;
+use core::ops::RangeBounds;
+
+/// Access to individual bits of arrays of unsigned integers.
+///
+/// The array is treated as a single large integer, with the first element
+/// holding the least significant bits. Bit `i` is bit `i % W` of element
+/// `i / W`, where `W` is the number of bits of the element type.
+///
+/// Ranges can span element boundaries. Since results are returned as a
+/// [`u128`], a range can be at most 128 bits wide.
+///
+/// # Example
+///
+/// ```
+/// use intbits::ArrayBits;
+///
+/// let mut mask = [0u64; 4];
+/// mask.set_bits(60..68, 0xA5);
+/// assert_eq!(mask, [0x5 << 60, 0xA, 0, 0]);
+/// assert_eq!(mask.bits(60..68), 0xA5);
+/// assert!(mask.bit(65));
+/// ```
+pub trait ArrayBits {
+	/// The total number of bits in the array.
+	const N_BITS: u32;
+
+	/// Get a specific bit.
+	///
+	/// Panics if the index is out of range.
+	fn bit(&self, i: u32) -> bool;
+
+	/// Get a range of bits.
+	///
+	/// The bits are returned in the least significant bits of the return
+	/// value. The other bits, if any, will be 0.
+	///
+	/// Panics when the range bounds are out of range, or when the range is
+	/// wider than 128 bits.
+	fn bits<R: RangeBounds<u32>>(&self, range: R) -> u128;
+
+	/// Set a specific bit.
+	///
+	/// Panics if the index is out of range.
+	fn set_bit(&mut self, i: u32, bit: bool);
+
+	/// Set a range of bits.
+	///
+	/// Panics when the range bounds are out of range, when the range is wider
+	/// than 128 bits, or when the irrelevant bits of the second argument are
+	/// not 0.
+	fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: u128);
+
+	/// Get a new array with one bit set to a specific value.
+	///
+	/// Panics if the index is out of range.
+	fn with_bit(self, i: u32, bit: bool) -> Self
+	where
+		Self: Sized;
+
+	/// Get a new array with a range of bits set to specific values.
+	///
+	/// Panics when the range bounds are out of range, when the range is wider
+	/// than 128 bits, or when the irrelevant bits of the second argument are
+	/// not 0.
+	fn with_bits<R: RangeBounds<u32>>(self, range: R, bits: u128) -> Self
+	where
+		Self: Sized;
+}
+
+// The implementation on slices, shared by the arrays and the growable sets.
+pub(crate) trait WordsBits {
+	fn words_bit(&self, i: u32) -> bool;
+	fn words_bits<R: RangeBounds<u32>>(&self, range: R) -> u128;
+	fn words_set_bit(&mut self, i: u32, bit: bool);
+	fn words_set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: u128);
+}
+
+macro_rules! array_bits {
+	($($t:ident),*) => {$(
+		impl WordsBits for [$t] {
+			#[inline]
+			fn words_bit(&self, i: u32) -> bool {
+				check!(i < self.len() as u32 * $t::BITS, invalid_bit_index);
+				self[(i / $t::BITS) as usize] >> (i % $t::BITS) & 1 != 0
+			}
+
+			#[inline]
+			fn words_bits<R: RangeBounds<u32>>(&self, range: R) -> u128 {
+				let (start, end) = bounds(&range, self.len() as u32 * $t::BITS);
+				check!(end - start <= 128, bit_range_too_wide);
+				let mut result = 0u128;
+				let mut i = start;
+				while i < end {
+					let word = (i / $t::BITS) as usize;
+					let shift = i % $t::BITS;
+					let n = ($t::BITS - shift).min(end - i);
+					let bits = (self[word] >> shift) as u128 & (!0u128 >> (128 - n));
+					result |= bits << (i - start);
+					i += n;
+				}
+				result
+			}
+
+			#[inline]
+			fn words_set_bit(&mut self, i: u32, bit: bool) {
+				check!(i < self.len() as u32 * $t::BITS, invalid_bit_index);
+				let word = &mut self[(i / $t::BITS) as usize];
+				let shift = i % $t::BITS;
+				*word = *word & !(1 << shift) | (bit as $t) << shift;
+			}
+
+			#[inline]
+			fn words_set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: u128) {
+				let (start, end) = bounds(&range, self.len() as u32 * $t::BITS);
+				check!(end - start <= 128, bit_range_too_wide);
+				if bits.checked_shr(end - start).unwrap_or(0) != 0 {
+					crate::panic::bits_outside_range();
+				}
+				let mut i = start;
+				while i < end {
+					let word = &mut self[(i / $t::BITS) as usize];
+					let shift = i % $t::BITS;
+					let n = ($t::BITS - shift).min(end - i);
+					let mask = (!0 as $t >> ($t::BITS - n)) << shift;
+					let value = ((bits >> (i - start)) as $t) << shift;
+					*word = *word & !mask | value & mask;
+					i += n;
+				}
+			}
+		}
+
+		impl<const N: usize> ArrayBits for [$t; N] {
+			const N_BITS: u32 = N as u32 * $t::BITS;
+
+			#[inline]
+			fn bit(&self, i: u32) -> bool {
+				self[..].words_bit(i)
+			}
+
+			#[inline]
+			fn bits<R: RangeBounds<u32>>(&self, range: R) -> u128 {
+				self[..].words_bits(range)
+			}
+
+			#[inline]
+			fn set_bit(&mut self, i: u32, bit: bool) {
+				self[..].words_set_bit(i, bit)
+			}
+
+			#[inline]
+			fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: u128) {
+				self[..].words_set_bits(range, bits)
+			}
+
+			#[inline]
+			fn with_bit(mut self, i: u32, bit: bool) -> Self {
+				self.set_bit(i, bit);
+				self
+			}
+
+			#[inline]
+			fn with_bits<R: RangeBounds<u32>>(mut self, range: R, bits: u128) -> Self {
+				self.set_bits(range, bits);
+				self
+			}
+		}
+	)*};
+}
+
+array_bits!(u8, u16, u32, u64, u128, usize);
@@ -0,0 +1,123 @@
+use super::{BitOrder, ReadError};
+use crate::refill::RefillBuffer;
+use embedded_io_async::Read;
+
+/// Reads values of any number of bits sequentially from an
+/// `embedded_io_async::Read` source.
+///
+/// This is like [`BitReader`][crate::BitReader], but the data is read from the
+/// source into an internal buffer of `N` bytes whenever more bits are needed,
+/// so fields can cross the boundaries of the chunks returned by the source.
+///
+/// This is only available with the `embedded-io-async` feature.
+///
+/// # Example
+///
+/// ```
+/// use embedded_io_async::Read;
+/// use intbits::{AsyncBitReader, ReadError};
+///
+/// async fn read_header<R: Read>(r: &mut AsyncBitReader<R>) -> Result<(u8, u16), ReadError<R::Error>> {
+///     let version = r.read_bits(4).await? as u8;
+///     let length = r.read_bits(12).await? as u16;
+///     Ok((version, length))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncBitReader<R, const N: usize = 64> {
+	source: R,
+	buf: RefillBuffer<N>,
+}
+
+impl<R: Read, const N: usize> AsyncBitReader<R, N> {
+	/// Create a reader that starts at the next byte of `source`.
+	///
+	/// Panics if `N` is less than 9, since a value of 64 bits that doesn't
+	/// start at the start of a byte can span 9 bytes.
+	pub fn new(source: R, order: BitOrder) -> Self {
+		Self {
+			source,
+			buf: RefillBuffer::new(order),
+		}
+	}
+
+	/// The bit order of this reader.
+	pub fn order(&self) -> BitOrder {
+		self.buf.order()
+	}
+
+	/// The number of bits read (or skipped) so far.
+	pub fn position(&self) -> usize {
+		self.buf.position()
+	}
+
+	/// Whether the reader is at the start of a byte.
+	pub fn is_aligned(&self) -> bool {
+		self.buf.is_aligned()
+	}
+
+	/// Return the source.
+	///
+	/// Any data that was read from the source but not yet from this reader is
+	/// lost.
+	pub fn into_inner(self) -> R {
+		self.source
+	}
+
+	/// Make sure the next `n` bits are in the buffer, reading from the source
+	/// as needed.
+	async fn fill(&mut self, n: u32) -> Result<(), ReadError<R::Error>> {
+		if self.buf.has(n) {
+			return Ok(());
+		}
+		self.buf.compact();
+		while !self.buf.has(n) {
+			match self.source.read(self.buf.spare()).await {
+				Ok(0) => return Err(ReadError::OutOfBits),
+				Ok(k) => self.buf.filled(k),
+				Err(e) => return Err(ReadError::Io(e)),
+			}
+		}
+		Ok(())
+	}
+
+	/// Get the next `n` bits without advancing the reader.
+	///
+	/// Panics if `n` is more than 64.
+	pub async fn peek_bits(&mut self, n: u32) -> Result<u64, ReadError<R::Error>> {
+		check!(n <= 64, bit_range_too_wide);
+		self.fill(n).await?;
+		Ok(self.buf.peek(n))
+	}
+
+	/// Read the next `n` bits.
+	///
+	/// Panics if `n` is more than 64.
+	pub async fn read_bits(&mut self, n: u32) -> Result<u64, ReadError<R::Error>> {
+		let bits = self.peek_bits(n).await?;
+		self.buf.advance(n as usize);
+		Ok(bits)
+	}
+
+	/// Read a single bit.
+	pub async fn read_bit(&mut self) -> Result<bool, ReadError<R::Error>> {
+		self.read_bits(1).await.map(|b| b != 0)
+	}
+
+	/// Skip the next `n` bits.
+	pub async fn skip(&mut self, mut n: usize) -> Result<(), ReadError<R::Error>> {
+		while n > 0 {
+			let k = n.min(64);
+			self.fill(k as u32).await?;
+			self.buf.advance(k);
+			n -= k;
+		}
+		Ok(())
+	}
+
+	/// Skip to the start of the next byte, unless already at the start of a
+	/// byte.
+	pub async fn align_to_byte(&mut self) -> Result<(), ReadError<R::Error>> {
+		self.skip(self.buf.bits_to_byte()).await
+	}
+}
@@ -0,0 +1,219 @@
+//! Operations applied to the same bits of every word in a slice.
+//!
+//! These work on whole slices at once, with the mask and shift calculated
+//! only once, so the compiler can vectorize the loops. This is much faster
+//! than calling the [`Bits`][crate::Bits] methods on every element.
+//!
+//! # Example
+//!
+//! ```
+//! use intbits::batch;
+//!
+//! let mut records = [0x12u64, 0x34, 0x56, 0x78];
+//! batch::set_bit_all(&mut records, 63, true);
+//!
+//! let mut fields = [0; 4];
+//! batch::extract_bits_all(&records, 4..8, &mut fields);
+//! assert_eq!(fields, [1, 3, 5, 7]);
+//! assert_eq!(batch::count_ones_all(&records, 60..), 4);
+//! ```
+
+use super::range::bounds;
+use core::ops::RangeBounds;
+
+/// The shift and mask (before shifting) of a range of bits of a `u64`.
+#[inline]
+fn shift_mask<R: RangeBounds<u32>>(range: &R) -> (u32, u64) {
+	let (start, end) = bounds(range, 64);
+	let mask = if start == end {
+		0
+	} else {
+		!0 >> (64 - (end - start))
+	};
+	(start.min(63), mask)
+}
+
+/// Set bit `i` of every word to the same value.
+///
+/// Panics if the index is out of range.
+pub fn set_bit_all(words: &mut [u64], i: u32, bit: bool) {
+	check!(i < 64, invalid_bit_index);
+	let (and, or) = (!(1 << i), (bit as u64) << i);
+	for w in words {
+		*w = *w & and | or;
+	}
+}
+
+/// Set a range of bits of every word to the same value.
+///
+/// Panics when the range bounds are out of range or when the irrelevant bits
+/// of `bits` are not 0.
+pub fn set_bits_all<R: RangeBounds<u32>>(words: &mut [u64], range: R, bits: u64) {
+	let (shift, mask) = shift_mask(&range);
+	if bits & !mask != 0 {
+		crate::panic::bits_outside_range();
+	}
+	let (and, or) = (!(mask << shift), bits << shift);
+	for w in words {
+		*w = *w & and | or;
+	}
+}
+
+/// Count the bits that are set in a range of bits of every word, in total.
+///
+/// Panics when the range bounds are out of range.
+pub fn count_ones_all<R: RangeBounds<u32>>(words: &[u64], range: R) -> u64 {
+	let (shift, mask) = shift_mask(&range);
+	let mask = mask << shift;
+	words.iter().map(|w| (w & mask).count_ones() as u64).sum()
+}
+
+/// Extract a range of bits from every word.
+///
+/// The bits of `words[i]` are written to the least significant bits of
+/// `out[i]`.
+///
+/// Panics when the range bounds are out of range, or when `out` is shorter
+/// than `words`.
+pub fn extract_bits_all<R: RangeBounds<u32>>(words: &[u64], range: R, out: &mut [u64]) {
+	check!(out.len() >= words.len(), buffer_too_small);
+	let (shift, mask) = shift_mask(&range);
+	for (o, w) in out.iter_mut().zip(words) {
+		*o = w >> shift & mask;
+	}
+}
+
+/// Insert a range of bits into every word.
+///
+/// The least significant bits of `values[i]` are written to `words[i]`. This
+/// is the counterpart of [`extract_bits_all`].
+///
+/// Panics when the range bounds are out of range, when `values` is shorter
+/// than `words`, or when the irrelevant bits of any value are not 0.
+pub fn insert_bits_all<R: RangeBounds<u32>>(words: &mut [u64], range: R, values: &[u64]) {
+	check!(values.len() >= words.len(), buffer_too_small);
+	let (shift, mask) = shift_mask(&range);
+	let mut outside = 0;
+	for (w, &v) in words.iter_mut().zip(values) {
+		outside |= v & !mask;
+		*w = *w & !(mask << shift) | (v & mask) << shift;
+	}
+	if outside != 0 {
+		crate::panic::bits_outside_range();
+	}
+}
+
+/// Count, for every bit position, how many words have that bit set.
+///
+/// `bit_histogram(words)[i]` is the number of words with bit `i` set. Instead
+/// of checking every bit of every word, this adds whole words to a set of
+/// bit-sliced counters, and only splits those into separate counts once every
+/// 255 words.
+///
+/// # Example
+///
+/// ```
+/// use intbits::batch;
+///
+/// let h = batch::bit_histogram(&[0b011, 0b110, 0b010]);
+/// assert_eq!(h[..4], [1, 3, 1, 0]);
+/// ```
+pub fn bit_histogram(words: &[u64]) -> [u32; 64] {
+	let mut histogram = [0; 64];
+	for chunk in words.chunks(255) {
+		// Bit `i` of `counters[j]` is bit `j` of the count for bit `i`.
+		let mut counters = [0u64; 8];
+		for &w in chunk {
+			let mut carry = w;
+			for c in &mut counters {
+				let next = *c & carry;
+				*c ^= carry;
+				carry = next;
+				if carry == 0 {
+					break;
+				}
+			}
+		}
+		for (i, h) in histogram.iter_mut().enumerate() {
+			let count = counters
+				.iter()
+				.rev()
+				.fold(0, |n, c| n << 1 | (c >> i & 1) as u32);
+			*h += count;
+		}
+	}
+	histogram
+}
+
+/// Transpose a 64×64 matrix of bits in place.
+///
+/// Bit `j` of `block[i]` is swapped with bit `i` of `block[j]`.
+///
+/// # Example
+///
+/// ```
+/// use intbits::batch;
+///
+/// let mut block = [0; 64];
+/// block[3] = 1 << 40;
+/// batch::transpose_64(&mut block);
+/// assert_eq!(block[40], 1 << 3);
+/// assert_eq!(block.iter().filter(|&&w| w != 0).count(), 1);
+/// ```
+pub fn transpose_64(block: &mut [u64; 64]) {
+	// Swap the off-diagonal halves, then quarters within each half, and so on.
+	let mut j = 32;
+	let mut m = 0x0000_0000_FFFF_FFFFu64;
+	while j != 0 {
+		let mut k = 0;
+		while k < 64 {
+			let t = (block[k] >> j ^ block[k + j]) & m;
+			block[k] ^= t << j;
+			block[k + j] ^= t;
+			k = (k + j + 1) & !j;
+		}
+		j >>= 1;
+		m ^= m << j;
+	}
+}
+
+/// Convert up to 64 words to the bit-sliced layout.
+///
+/// Bit `i` of `sliced[j]` is set to bit `j` of `words[i]`. If there are fewer
+/// than 64 words, the missing words are taken to be 0.
+///
+/// Panics if there are more than 64 words.
+///
+/// # Example
+///
+/// ```
+/// use intbits::batch;
+///
+/// let mut sliced = [0; 64];
+/// batch::to_bit_sliced(&[0b01, 0b11, 0b10], &mut sliced);
+/// assert_eq!(sliced[..3], [0b011, 0b110, 0]);
+///
+/// let mut words = [0; 3];
+/// batch::from_bit_sliced(&sliced, &mut words);
+/// assert_eq!(words, [0b01, 0b11, 0b10]);
+/// ```
+pub fn to_bit_sliced(words: &[u64], sliced: &mut [u64; 64]) {
+	check!(words.len() <= 64, buffer_too_small);
+	sliced[..words.len()].copy_from_slice(words);
+	sliced[words.len()..].fill(0);
+	transpose_64(sliced);
+}
+
+/// Convert from the bit-sliced layout back to up to 64 words.
+///
+/// Bit `j` of `words[i]` is set to bit `i` of `sliced[j]`. This is the
+/// inverse of [`to_bit_sliced`]. Only the first `words.len()` words are
+/// written.
+///
+/// Panics if there are more than 64 words.
+pub fn from_bit_sliced(sliced: &[u64; 64], words: &mut [u64]) {
+	check!(words.len() <= 64, buffer_too_small);
+	let mut block = *sliced;
+	transpose_64(&mut block);
+	words.copy_from_slice(&block[..words.len()]);
+}
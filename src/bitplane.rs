@@ -0,0 +1,81 @@
+/// Load a group of 8 packed pixels of `bpp` bits, first pixel in the most
+/// significant bits.
+fn load_group(group: &[u8]) -> u64 {
+	group.iter().fold(0, |w, &b| w << 8 | b as u64)
+}
+
+/// Split packed pixels into separate bitplanes.
+///
+/// `pixels` holds `planes.len()` bits per pixel, packed with the leftmost
+/// pixel in the most significant bits, as in most 2 and 4 bits per pixel
+/// formats. Bit `p` of every pixel is written to `planes[p]`, 8 pixels per
+/// byte, with the leftmost pixel in the most significant bit.
+///
+/// Every group of 8 pixels is `planes.len()` bytes of `pixels` and one byte of
+/// each plane. As many complete groups as fit in `pixels` and all planes are
+/// converted. Returns the number of bytes written to each plane.
+///
+/// Panics if there are no planes or more than 8.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{merge_bitplanes, split_bitplanes};
+///
+/// // One row of an NES tile: colors 0, 1, 2, 3, 3, 2, 1, 0.
+/// let mut plane0 = [0];
+/// let mut plane1 = [0];
+/// assert_eq!(split_bitplanes(&[0b00_01_10_11, 0b11_10_01_00], &mut [&mut plane0, &mut plane1]), 1);
+/// assert_eq!((plane0, plane1), ([0b0101_1010], [0b0011_1100]));
+///
+/// let mut pixels = [0; 2];
+/// assert_eq!(merge_bitplanes(&[&plane0, &plane1], &mut pixels), 2);
+/// assert_eq!(pixels, [0b00_01_10_11, 0b11_10_01_00]);
+/// ```
+pub fn split_bitplanes(pixels: &[u8], planes: &mut [&mut [u8]]) -> usize {
+	let bpp = planes.len();
+	check!((1..=8).contains(&bpp), invalid_bitplanes);
+	let n = planes
+		.iter()
+		.map(|p| p.len())
+		.fold(pixels.len() / bpp, usize::min);
+	for (i, group) in pixels.chunks_exact(bpp).take(n).enumerate() {
+		let word = load_group(group);
+		for (p, plane) in planes.iter_mut().enumerate() {
+			let mut byte = 0;
+			for k in 0..8 {
+				byte |= ((word >> (k * bpp + p) & 1) as u8) << k;
+			}
+			plane[i] = byte;
+		}
+	}
+	n
+}
+
+/// Merge separate bitplanes into packed pixels.
+///
+/// This is the counterpart of [`split_bitplanes`]. As many complete groups as
+/// fit in all planes and `pixels` are converted. Returns the number of bytes
+/// written to `pixels`.
+///
+/// Panics if there are no planes or more than 8.
+pub fn merge_bitplanes(planes: &[&[u8]], pixels: &mut [u8]) -> usize {
+	let bpp = planes.len();
+	check!((1..=8).contains(&bpp), invalid_bitplanes);
+	let n = planes
+		.iter()
+		.map(|p| p.len())
+		.fold(pixels.len() / bpp, usize::min);
+	for (i, group) in pixels.chunks_exact_mut(bpp).take(n).enumerate() {
+		let mut word = 0u64;
+		for (p, plane) in planes.iter().enumerate() {
+			for k in 0..8 {
+				word |= ((plane[i] >> k & 1) as u64) << (k * bpp + p);
+			}
+		}
+		for (j, b) in group.iter_mut().enumerate() {
+			*b = (word >> (8 * (bpp - 1 - j))) as u8;
+		}
+	}
+	n * bpp
+}
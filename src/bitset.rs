@@ -0,0 +1,379 @@
+use super::ArrayBits;
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, RangeBounds};
+use core::ops::{Sub, SubAssign};
+
+/// A set of numbers below `64 * WORDS`, stored in an array.
+///
+/// This is like [`IntSet`][crate::IntSet], but for sets that don't fit in a
+/// single integer. It doesn't allocate.
+///
+/// Besides the set operations, it has the same bit access functions as
+/// [`ArrayBits`], with the same range semantics.
+///
+/// # Example
+///
+/// ```
+/// use intbits::FixedBitSet;
+///
+/// let mut claimed = FixedBitSet::<4>::new();
+/// claimed.insert(3);
+/// claimed.insert(200);
+/// claimed.set_bits(64..72, 0xFF);
+/// assert!(claimed.contains(200));
+/// assert_eq!(claimed.len(), 10);
+/// assert_eq!(claimed.iter().last(), Some(200));
+/// assert_eq!(claimed.bits(60..68), 0xF0);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBitSet<const WORDS: usize> {
+	words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> FixedBitSet<WORDS> {
+	/// The number of bits in the set, which is one more than the largest
+	/// number that can be stored.
+	pub const N_BITS: u32 = WORDS as u32 * 64;
+
+	/// Create an empty set.
+	#[inline]
+	pub const fn new() -> Self {
+		Self { words: [0; WORDS] }
+	}
+
+	/// Create a set from its words, least significant word first.
+	#[inline]
+	pub const fn from_words(words: [u64; WORDS]) -> Self {
+		Self { words }
+	}
+
+	/// Get the words of the set, least significant word first.
+	#[inline]
+	pub const fn words(&self) -> &[u64; WORDS] {
+		&self.words
+	}
+
+	/// Get a specific bit.
+	///
+	/// See [`ArrayBits::bit`].
+	#[inline]
+	pub fn bit(&self, i: u32) -> bool {
+		self.words.bit(i)
+	}
+
+	/// Get a range of bits.
+	///
+	/// See [`ArrayBits::bits`].
+	#[inline]
+	pub fn bits<R: RangeBounds<u32>>(&self, range: R) -> u128 {
+		self.words.bits(range)
+	}
+
+	/// Set a specific bit.
+	///
+	/// See [`ArrayBits::set_bit`].
+	#[inline]
+	pub fn set_bit(&mut self, i: u32, bit: bool) {
+		self.words.set_bit(i, bit)
+	}
+
+	/// Set a range of bits.
+	///
+	/// See [`ArrayBits::set_bits`].
+	#[inline]
+	pub fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: u128) {
+		self.words.set_bits(range, bits)
+	}
+
+	/// Get a new set with one bit set to a specific value.
+	///
+	/// See [`ArrayBits::with_bit`].
+	#[inline]
+	pub fn with_bit(mut self, i: u32, bit: bool) -> Self {
+		self.set_bit(i, bit);
+		self
+	}
+
+	/// Get a new set with a range of bits set to specific values.
+	///
+	/// See [`ArrayBits::with_bits`].
+	#[inline]
+	pub fn with_bits<R: RangeBounds<u32>>(mut self, range: R, bits: u128) -> Self {
+		self.set_bits(range, bits);
+		self
+	}
+
+	/// Add a number to the set.
+	///
+	/// Returns whether it was newly added.
+	///
+	/// Panics if the number doesn't fit.
+	#[inline]
+	pub fn insert(&mut self, i: u32) -> bool {
+		let new = !self.bit(i);
+		self.set_bit(i, true);
+		new
+	}
+
+	/// Remove a number from the set.
+	///
+	/// Returns whether it was in the set.
+	///
+	/// Panics if the number doesn't fit.
+	#[inline]
+	pub fn remove(&mut self, i: u32) -> bool {
+		let present = self.bit(i);
+		self.set_bit(i, false);
+		present
+	}
+
+	/// Check whether a number is in the set.
+	///
+	/// Numbers that don't fit are never in the set.
+	#[inline]
+	pub fn contains(&self, i: u32) -> bool {
+		i < Self::N_BITS && self.bit(i)
+	}
+
+	/// Remove all numbers from the set.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.words = [0; WORDS];
+	}
+
+	/// The number of numbers in the set.
+	#[inline]
+	pub fn len(&self) -> u32 {
+		self.words.iter().map(|w| w.count_ones()).sum()
+	}
+
+	/// Check whether the set is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.words.iter().all(|&w| w == 0)
+	}
+
+	/// The smallest number in the set.
+	#[inline]
+	pub fn first(&self) -> Option<u32> {
+		self.iter().next()
+	}
+
+	/// The largest number in the set.
+	#[inline]
+	pub fn last(&self) -> Option<u32> {
+		self.iter().next_back()
+	}
+
+	/// The numbers in either set.
+	#[inline]
+	pub fn union(mut self, other: &Self) -> Self {
+		self.zip(other, |a, b| a | b);
+		self
+	}
+
+	/// The numbers in both sets.
+	#[inline]
+	pub fn intersection(mut self, other: &Self) -> Self {
+		self.zip(other, |a, b| a & b);
+		self
+	}
+
+	/// The numbers in `self` but not in `other`.
+	#[inline]
+	pub fn difference(mut self, other: &Self) -> Self {
+		self.zip(other, |a, b| a & !b);
+		self
+	}
+
+	/// The numbers in exactly one of the sets.
+	#[inline]
+	pub fn symmetric_difference(mut self, other: &Self) -> Self {
+		self.zip(other, |a, b| a ^ b);
+		self
+	}
+
+	/// Check whether all numbers of `self` are in `other`.
+	#[inline]
+	pub fn is_subset(&self, other: &Self) -> bool {
+		self.words
+			.iter()
+			.zip(&other.words)
+			.all(|(a, b)| a & !b == 0)
+	}
+
+	/// Check whether the sets have no numbers in common.
+	#[inline]
+	pub fn is_disjoint(&self, other: &Self) -> bool {
+		self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+	}
+
+	/// Iterate over the numbers in the set, in increasing order.
+	#[inline]
+	pub fn iter(&self) -> BitSetIter<'_> {
+		BitSetIter::new(&self.words)
+	}
+
+	#[inline]
+	fn zip(&mut self, other: &Self, f: impl Fn(u64, u64) -> u64) {
+		for (a, &b) in self.words.iter_mut().zip(&other.words) {
+			*a = f(*a, b);
+		}
+	}
+}
+
+impl<const WORDS: usize> Default for FixedBitSet<WORDS> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const WORDS: usize> fmt::Debug for FixedBitSet<WORDS> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_set().entries(self.iter()).finish()
+	}
+}
+
+/// Formatted as its words in hexadecimal, least significant word first,
+/// rather than as a list of elements.
+#[cfg(feature = "defmt")]
+impl<const WORDS: usize> defmt::Format for FixedBitSet<WORDS> {
+	fn format(&self, f: defmt::Formatter) {
+		defmt::write!(f, "FixedBitSet({=[?]:#x})", &self.words[..]);
+	}
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a FixedBitSet<WORDS> {
+	type Item = u32;
+	type IntoIter = BitSetIter<'a>;
+
+	#[inline]
+	fn into_iter(self) -> BitSetIter<'a> {
+		self.iter()
+	}
+}
+
+impl<const WORDS: usize> FromIterator<u32> for FixedBitSet<WORDS> {
+	fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+		let mut set = Self::new();
+		set.extend(iter);
+		set
+	}
+}
+
+impl<const WORDS: usize> Extend<u32> for FixedBitSet<WORDS> {
+	fn extend<I: IntoIterator<Item = u32>>(&mut self, iter: I) {
+		for i in iter {
+			self.insert(i);
+		}
+	}
+}
+
+macro_rules! set_op {
+	($($op:ident $f:ident $op_assign:ident $f_assign:ident $method:ident),*) => {$(
+		impl<const WORDS: usize> $op for FixedBitSet<WORDS> {
+			type Output = Self;
+
+			#[inline]
+			fn $f(self, other: Self) -> Self {
+				self.$method(&other)
+			}
+		}
+
+		impl<const WORDS: usize> $op_assign for FixedBitSet<WORDS> {
+			#[inline]
+			fn $f_assign(&mut self, other: Self) {
+				*self = self.$method(&other);
+			}
+		}
+	)*};
+}
+
+set_op!(
+	BitOr bitor BitOrAssign bitor_assign union,
+	BitAnd bitand BitAndAssign bitand_assign intersection,
+	Sub sub SubAssign sub_assign difference,
+	BitXor bitxor BitXorAssign bitxor_assign symmetric_difference
+);
+
+/// An iterator over the numbers in a bit set, in increasing order.
+///
+/// Created by [`FixedBitSet::iter`].
+#[derive(Clone, Debug)]
+pub struct BitSetIter<'a> {
+	words: &'a [u64],
+	// The remaining bits of the first and last word of `words`.
+	front: u64,
+	back: u64,
+	// The index of the first word in the original slice.
+	offset: u32,
+}
+
+impl<'a> BitSetIter<'a> {
+	pub(crate) fn new(words: &'a [u64]) -> Self {
+		Self {
+			words,
+			front: words.first().copied().unwrap_or(0),
+			back: words.last().copied().unwrap_or(0),
+			offset: 0,
+		}
+	}
+}
+
+impl Iterator for BitSetIter<'_> {
+	type Item = u32;
+
+	fn next(&mut self) -> Option<u32> {
+		loop {
+			if self.words.len() == 1 {
+				// The front and back share a word.
+				self.front &= self.back;
+				self.back = self.front;
+			}
+			if self.front != 0 {
+				let i = self.front.trailing_zeros();
+				self.front &= self.front - 1;
+				return Some(self.offset * 64 + i);
+			}
+			if self.words.len() <= 1 {
+				self.words = &[];
+				return None;
+			}
+			self.words = &self.words[1..];
+			self.offset += 1;
+			self.front = if self.words.len() == 1 {
+				self.back
+			} else {
+				self.words[0]
+			};
+		}
+	}
+}
+
+impl DoubleEndedIterator for BitSetIter<'_> {
+	fn next_back(&mut self) -> Option<u32> {
+		loop {
+			if self.words.len() == 1 {
+				self.back &= self.front;
+				self.front = self.back;
+			}
+			if self.back != 0 {
+				let i = 63 - self.back.leading_zeros();
+				self.back &= !(1 << i);
+				return Some((self.offset + self.words.len() as u32 - 1) * 64 + i);
+			}
+			if self.words.len() <= 1 {
+				self.words = &[];
+				return None;
+			}
+			self.words = &self.words[..self.words.len() - 1];
+			self.back = if self.words.len() == 1 {
+				self.front
+			} else {
+				self.words[self.words.len() - 1]
+			};
+		}
+	}
+}
@@ -0,0 +1,69 @@
+use super::{Bits, BitsIndex};
+use bitvec::order::BitOrder;
+use bitvec::slice::BitSlice;
+use bitvec::store::BitStore;
+
+/// Load an integer from a `bitvec` [`BitSlice`].
+///
+/// Bit `i` of the slice becomes bit `i` of the result, regardless of the
+/// [`BitOrder`] of the slice. Use `bitvec`'s indexing to select a range:
+/// `load_bitslice(&slice[4..12])`.
+///
+/// Panics when the slice is longer than the integer.
+///
+/// This is only available with the `bitvec` feature.
+///
+/// # Example
+///
+/// ```
+/// use bitvec::prelude::*;
+/// use intbits::{load_bitslice, store_bitslice};
+///
+/// let data = bits![u8, Msb0; 0, 0, 0, 1, 0, 1, 1, 0];
+/// let x: u8 = load_bitslice(&data[2..6]);
+/// assert_eq!(x, 0b1010);
+///
+/// let mut out = bitarr![u16, Lsb0; 0; 16];
+/// store_bitslice(0x5u8, &mut out[4..8]);
+/// assert_eq!(out.load_le::<u16>(), 0x50);
+/// ```
+pub fn load_bitslice<B, S, O>(slice: &BitSlice<S, O>) -> B
+where
+	B: Bits<Bits = B> + Default,
+	u32: BitsIndex<B>,
+	S: BitStore,
+	O: BitOrder,
+{
+	check!(slice.len() <= B::N_BITS as usize, bit_range_too_wide);
+	let mut value = B::default();
+	for i in slice.iter_ones() {
+		value.set_bit(i as u32, true);
+	}
+	value
+}
+
+/// Store an integer into a `bitvec` [`BitSlice`].
+///
+/// Bit `i` of `value` is written to bit `i` of the slice, regardless of the
+/// [`BitOrder`] of the slice. This is the counterpart of [`load_bitslice`].
+///
+/// Panics when the slice is longer than the integer, or when `value` has bits
+/// set that don't fit in the slice.
+///
+/// This is only available with the `bitvec` feature.
+pub fn store_bitslice<T, S, O>(value: T, slice: &mut BitSlice<S, O>)
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+	S: BitStore,
+	O: BitOrder,
+{
+	let n = slice.len() as u32;
+	check!(n <= T::N_BITS, bit_range_too_wide);
+	if value.bits(n..) != T::Bits::default() {
+		crate::panic::bits_outside_range();
+	}
+	for i in 0..n {
+		slice.set(i as usize, value.bit(i));
+	}
+}
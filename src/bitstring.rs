@@ -0,0 +1,137 @@
+use super::parse::parse_bin;
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// A wrapper that serializes an integer as a binary string.
+///
+/// In human-readable formats, like JSON or TOML, the value is written as a
+/// string like `"0b1010_1100"`, with all bits of the type in groups of four.
+/// When deserializing, the `0b` prefix is optional, underscores are ignored,
+/// and plain integers are accepted as well.
+///
+/// In other formats, the integer itself is serialized.
+///
+/// This is only available with the `serde` feature.
+///
+/// # Example
+///
+/// ```
+/// use intbits::BitString;
+///
+/// let json = serde_json::to_string(&BitString(0xACu8)).unwrap();
+/// assert_eq!(json, r#""0b1010_1100""#);
+///
+/// let mask: BitString<u16> = serde_json::from_str(r#""0b1_0000_0001""#).unwrap();
+/// assert_eq!(mask.0, 0x101);
+///
+/// let mask: BitString<u16> = serde_json::from_str("257").unwrap();
+/// assert_eq!(mask.0, 0x101);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitString<T>(pub T);
+
+impl<T> From<T> for BitString<T> {
+	fn from(value: T) -> Self {
+		Self(value)
+	}
+}
+
+/// Write `n_bits` bits of `value` as a binary string into `buf`.
+fn format_bin(value: u128, n_bits: u32, buf: &mut [u8; 162]) -> &str {
+	buf[0] = b'0';
+	buf[1] = b'b';
+	let mut len = 2;
+	for i in (0..n_bits).rev() {
+		buf[len] = b'0' + (value >> i & 1) as u8;
+		len += 1;
+		if i != 0 && i % 4 == 0 {
+			buf[len] = b'_';
+			len += 1;
+		}
+	}
+	core::str::from_utf8(&buf[..len]).unwrap()
+}
+
+struct BitStringVisitor<T>(PhantomData<T>);
+
+macro_rules! bit_string {
+	($t:ty, $u:ty) => {
+		impl Serialize for BitString<$t> {
+			fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				if serializer.is_human_readable() {
+					let mut buf = [0; 162];
+					let n_bits = core::mem::size_of::<$t>() as u32 * 8;
+					serializer.serialize_str(format_bin(self.0 as $u as u128, n_bits, &mut buf))
+				} else {
+					self.0.serialize(serializer)
+				}
+			}
+		}
+
+		impl<'de> Deserialize<'de> for BitString<$t> {
+			fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				if deserializer.is_human_readable() {
+					deserializer.deserialize_any(BitStringVisitor::<$t>(PhantomData))
+				} else {
+					<$t>::deserialize(deserializer).map(BitString)
+				}
+			}
+		}
+
+		impl<'de> Visitor<'de> for BitStringVisitor<$t> {
+			type Value = BitString<$t>;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				write!(f, "a binary string or an integer")
+			}
+
+			fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+				let n_bits = core::mem::size_of::<$t>() as u32 * 8;
+				match parse_bin(s, n_bits) {
+					Ok(v) => Ok(BitString(v as $u as $t)),
+					Err(_) => Err(E::invalid_value(Unexpected::Str(s), &self)),
+				}
+			}
+
+			fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+				<$t>::try_from(v)
+					.map(BitString)
+					.map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+			}
+
+			fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+				<$t>::try_from(v)
+					.map(BitString)
+					.map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+			}
+
+			fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+				<$t>::try_from(v)
+					.map(BitString)
+					.map_err(|_| E::invalid_value(Unexpected::Other("u128"), &self))
+			}
+
+			fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+				<$t>::try_from(v)
+					.map(BitString)
+					.map_err(|_| E::invalid_value(Unexpected::Other("i128"), &self))
+			}
+		}
+	};
+}
+
+bit_string!(u8, u8);
+bit_string!(u16, u16);
+bit_string!(u32, u32);
+bit_string!(u64, u64);
+bit_string!(u128, u128);
+bit_string!(usize, usize);
+bit_string!(i8, u8);
+bit_string!(i16, u16);
+bit_string!(i32, u32);
+bit_string!(i64, u64);
+bit_string!(i128, u128);
+bit_string!(isize, usize);
@@ -0,0 +1,135 @@
+use super::{Bits, BitsIndex};
+use core::fmt;
+use core::ops::RangeBounds;
+
+/// The error returned by [`BitsBuilder::build`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BuildError {
+	/// A bit was written more than once.
+	Overlap,
+	/// A value didn't fit in its range of bits.
+	ValueTooWide,
+}
+
+impl fmt::Display for BuildError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			BuildError::Overlap => write!(f, "bits written more than once"),
+			BuildError::ValueTooWide => write!(f, "bits outside range"),
+		}
+	}
+}
+
+/// Assembles an integer field by field.
+///
+/// Unlike a chain of [`with_bits`][Bits::with_bits] calls, this keeps track of
+/// which bits were already written, such that writing the same bit twice is
+/// reported as an error.
+///
+/// Created by [`Bits::bits_builder`]. All bits that are not written are 0.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{Bits, BuildError};
+///
+/// let v = u16::bits_builder().field(0..4, 0xA).bit(7, true).build();
+/// assert_eq!(v, Ok(0x8A));
+///
+/// let v = u16::bits_builder().field(0..4, 0xA).bit(3, true).build();
+/// assert_eq!(v, Err(BuildError::Overlap));
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct BitsBuilder<T: Bits> {
+	value: T,
+	written: T::Bits,
+	error: Option<BuildError>,
+}
+
+macro_rules! builder {
+	($($t:tt $ut:tt),*) => {$(
+		impl BitsBuilder<$t> {
+			/// Start building a new value, with all bits 0.
+			#[inline]
+			pub fn new() -> Self {
+				Self {
+					value: 0,
+					written: 0,
+					error: None,
+				}
+			}
+
+			/// Set a range of bits.
+			///
+			/// Panics when the range bounds are out of range, like
+			/// [`Bits::set_bits`]. Overlapping or too wide values are reported by
+			/// [`build`][Self::build].
+			#[inline]
+			pub fn field<I, R>(mut self, range: R, bits: $ut) -> Self
+			where
+				I: BitsIndex<$t>,
+				R: RangeBounds<I> + Clone,
+			{
+				let ones = (!0 as $t).bits(range.clone());
+				let mask = (0 as $t).with_bits(range.clone(), ones) as $ut;
+				if self.error.is_none() {
+					if bits & !ones != 0 {
+						self.error = Some(BuildError::ValueTooWide);
+					} else if self.written & mask != 0 {
+						self.error = Some(BuildError::Overlap);
+					} else {
+						self.written |= mask;
+						self.value.set_bits(range, bits);
+					}
+				}
+				self
+			}
+
+			/// Set a specific bit.
+			///
+			/// Panics if the index is out of range.
+			#[inline]
+			pub fn bit<I>(mut self, i: I, bit: bool) -> Self
+			where
+				I: BitsIndex<$t> + Copy,
+			{
+				let mask = (0 as $t).with_bit(i, true) as $ut;
+				if self.error.is_none() {
+					if self.written & mask != 0 {
+						self.error = Some(BuildError::Overlap);
+					} else {
+						self.written |= mask;
+						self.value.set_bit(i, bit);
+					}
+				}
+				self
+			}
+
+			/// Get the assembled value, or the first error that occurred.
+			#[inline]
+			pub fn build(self) -> Result<$t, BuildError> {
+				match self.error {
+					Some(e) => Err(e),
+					None => Ok(self.value),
+				}
+			}
+		}
+
+		impl Default for BitsBuilder<$t> {
+			#[inline]
+			fn default() -> Self {
+				Self::new()
+			}
+		}
+	)*};
+}
+
+builder!(u8 u8, u16 u16, u32 u32, u64 u64, usize usize);
+#[cfg(feature = "impl-signed")]
+builder!(i8 u8, i16 u16, i32 u32, i64 u64, isize usize);
+#[cfg(feature = "impl-128")]
+builder!(u128 u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+builder!(i128 u128);
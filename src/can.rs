@@ -0,0 +1,112 @@
+//! Signal extraction from CAN frames, following DBC conventions.
+//!
+//! # Example
+//!
+//! ```
+//! use intbits::can::{extract_signal, insert_signal, ByteOrder, Signedness};
+//!
+//! let mut frame = [0u8; 8];
+//! insert_signal(&mut frame, 7, 12, ByteOrder::Motorola, Signedness::Signed, -2);
+//! assert_eq!(frame[..2], [0xFF, 0xE0]);
+//! assert_eq!(extract_signal(&frame, 7, 12, ByteOrder::Motorola, Signedness::Signed), -2);
+//! assert_eq!(extract_signal(&frame, 4, 4, ByteOrder::Intel, Signedness::Unsigned), 0xF);
+//! ```
+
+use super::{BitOrder, SliceBits};
+
+/// The byte order of a signal, as specified in a DBC file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ByteOrder {
+	/// Little endian (`@1` in DBC files).
+	///
+	/// The start bit is the least significant bit of the signal, and the
+	/// signal continues towards more significant bits and higher bytes.
+	Intel,
+	/// Big endian (`@0` in DBC files).
+	///
+	/// The start bit is the most significant bit of the signal, and the
+	/// signal continues towards less significant bits, wrapping from bit 0 of
+	/// one byte to bit 7 of the next byte (the "sawtooth" numbering).
+	Motorola,
+}
+
+/// Whether a signal is an unsigned or a two's complement signed value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Signedness {
+	/// Unsigned (`+` in DBC files).
+	Unsigned,
+	/// Two's complement signed (`-` in DBC files).
+	Signed,
+}
+
+/// The range of bits of the signal, in the numbering of the [`BitOrder`]
+/// that matches the byte order.
+fn signal_range(start_bit: u32, length: u32, order: ByteOrder) -> (usize, usize, BitOrder) {
+	check!((1..=64).contains(&length), invalid_bit_range);
+	let start = match order {
+		ByteOrder::Intel => start_bit,
+		ByteOrder::Motorola => start_bit / 8 * 8 + 7 - start_bit % 8,
+	} as usize;
+	let bit_order = match order {
+		ByteOrder::Intel => BitOrder::LsbFirst,
+		ByteOrder::Motorola => BitOrder::MsbFirst,
+	};
+	(start, start + length as usize, bit_order)
+}
+
+/// Extract a signal from a frame.
+///
+/// The frame can be of any length, to support CAN FD. Unsigned 64-bit signals
+/// are returned as their bit pattern reinterpreted as `i64`.
+///
+/// Panics when `length` is not in `1..=64` or when the signal does not fit in
+/// the frame.
+pub fn extract_signal(
+	frame: &[u8],
+	start_bit: u32,
+	length: u32,
+	order: ByteOrder,
+	signedness: Signedness,
+) -> i64 {
+	let (start, end, bit_order) = signal_range(start_bit, length, order);
+	let raw = frame.bits(start..end, bit_order) as u64;
+	match signedness {
+		Signedness::Unsigned => raw as i64,
+		Signedness::Signed => (raw << (64 - length)) as i64 >> (64 - length),
+	}
+}
+
+/// Insert a signal into a frame.
+///
+/// This is the counterpart of [`extract_signal`]. Unsigned 64-bit signals are
+/// given as their bit pattern reinterpreted as `i64`.
+///
+/// Panics when `length` is not in `1..=64`, when the signal does not fit in
+/// the frame, or when `value` does not fit in the signal.
+pub fn insert_signal(
+	frame: &mut [u8],
+	start_bit: u32,
+	length: u32,
+	order: ByteOrder,
+	signedness: Signedness,
+	value: i64,
+) {
+	let (start, end, bit_order) = signal_range(start_bit, length, order);
+	let fits = match signedness {
+		_ if length == 64 => true,
+		Signedness::Unsigned => value >= 0 && value >> length == 0,
+		Signedness::Signed => {
+			let sign = value >> (length - 1);
+			sign == 0 || sign == -1
+		}
+	};
+	if !fits {
+		crate::panic::bits_outside_range();
+	}
+	let raw = value as u64 & !0 >> (64 - length);
+	frame.set_bits(start..end, raw as u128, bit_order);
+}
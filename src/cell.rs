@@ -0,0 +1,108 @@
+use super::{Bits, BitsIndex};
+use core::cell::Cell;
+use core::ops::RangeBounds;
+
+/// Extension trait to modify individual bits of integers in a [`Cell`].
+///
+/// Each method gets the value from the cell, modifies it, and puts it back.
+///
+/// # Example
+///
+/// ```
+/// use core::cell::Cell;
+/// use intbits::CellBits;
+///
+/// let flags = Cell::new(0u8);
+/// flags.set_bit(3, true);
+/// flags.set_bits(4..8, 0xA);
+/// flags.toggle_bit(0);
+/// assert_eq!(flags.get(), 0xA9);
+/// ```
+pub trait CellBits {
+	/// The type of integer in the cell.
+	type Value: Bits;
+
+	/// Get a specific bit.
+	///
+	/// See [`Bits::bit`].
+	fn bit<I>(&self, i: I) -> bool
+	where
+		I: BitsIndex<Self::Value>;
+
+	/// Get a range of bits.
+	///
+	/// See [`Bits::bits`].
+	fn bits<I, R>(&self, range: R) -> <Self::Value as Bits>::Bits
+	where
+		I: BitsIndex<Self::Value>,
+		R: RangeBounds<I>;
+
+	/// Set a specific bit.
+	///
+	/// See [`Bits::set_bit`].
+	fn set_bit<I>(&self, i: I, bit: bool)
+	where
+		I: BitsIndex<Self::Value>;
+
+	/// Set a range of bits.
+	///
+	/// See [`Bits::set_bits`].
+	fn set_bits<I, R>(&self, range: R, bits: <Self::Value as Bits>::Bits)
+	where
+		I: BitsIndex<Self::Value>,
+		R: RangeBounds<I>;
+
+	/// Invert a specific bit.
+	///
+	/// Panics if the index is out of range.
+	fn toggle_bit<I>(&self, i: I)
+	where
+		I: BitsIndex<Self::Value> + Copy;
+}
+
+impl<T: Bits> CellBits for Cell<T> {
+	type Value = T;
+
+	#[inline]
+	fn bit<I>(&self, i: I) -> bool
+	where
+		I: BitsIndex<T>,
+	{
+		self.get().bit(i)
+	}
+
+	#[inline]
+	fn bits<I, R>(&self, range: R) -> T::Bits
+	where
+		I: BitsIndex<T>,
+		R: RangeBounds<I>,
+	{
+		self.get().bits(range)
+	}
+
+	#[inline]
+	fn set_bit<I>(&self, i: I, bit: bool)
+	where
+		I: BitsIndex<T>,
+	{
+		self.set(self.get().with_bit(i, bit))
+	}
+
+	#[inline]
+	fn set_bits<I, R>(&self, range: R, bits: T::Bits)
+	where
+		I: BitsIndex<T>,
+		R: RangeBounds<I>,
+	{
+		self.set(self.get().with_bits(range, bits))
+	}
+
+	#[inline]
+	fn toggle_bit<I>(&self, i: I)
+	where
+		I: BitsIndex<T> + Copy,
+	{
+		let v = self.get();
+		self.set(v.with_bit(i, !v.bit(i)))
+	}
+}
@@ -0,0 +1,170 @@
+//! Packed pixel formats, with typed access to their color channels.
+//!
+//! Conversion from 8-bit channels rounds to the nearest value, and conversion
+//! to 8-bit channels scales the full range of the channel to `0..=255`, also
+//! rounding to the nearest value. This way, converting a channel of fewer than
+//! 8 bits to 8 bits and back gives the original value.
+//!
+//! # Example
+//!
+//! ```
+//! use intbits::color::Rgb565;
+//!
+//! let mut p = Rgb565::from_rgb8([255, 128, 0]);
+//! assert_eq!(p, Rgb565(0xFC00));
+//! assert_eq!((p.r(), p.g(), p.b()), (31, 32, 0));
+//! p.set_b(31);
+//! assert_eq!(p.to_rgb8(), [255, 130, 255]);
+//! ```
+
+use super::Bits;
+
+/// Scale an 8-bit channel to `n` bits, rounding to the nearest value.
+#[inline]
+const fn from_8(v: u8, n: u32) -> u32 {
+	let max = (1 << n) - 1;
+	(v as u32 * max + 127) / 255
+}
+
+/// Scale an `n`-bit channel to 8 bits, rounding to the nearest value.
+#[inline]
+const fn to_8(v: u32, n: u32) -> u8 {
+	let max = (1 << n) - 1;
+	((v * 255 + max / 2) / max) as u8
+}
+
+macro_rules! pixel {
+	(
+		$(#[$doc:meta])*
+		$name:ident($t:ident) {
+			$($(#[$cdoc:meta])* $c:ident $set_c:ident: $start:literal..$end:literal,)*
+		}
+	) => {
+		$(#[$doc])*
+		#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+		#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+		pub struct $name(pub $t);
+
+		impl $name {
+			$(
+				$(#[$cdoc])*
+				#[inline]
+				pub fn $c(self) -> $t {
+					self.0.bits($start..$end)
+				}
+
+				/// Set the channel.
+				///
+				/// Panics if the value doesn't fit in the channel.
+				#[inline]
+				pub fn $set_c(&mut self, value: $t) {
+					self.0.set_bits($start..$end, value)
+				}
+			)*
+		}
+	};
+}
+
+pixel! {
+	/// A 16-bit RGB pixel with 5 bits of red, 6 bits of green and 5 bits of
+	/// blue: `RRRRRGGGGGGBBBBB`.
+	///
+	/// This is the native format of many small TFT displays.
+	Rgb565(u16) {
+		/// The red channel, in bits 11 to 15.
+		r set_r: 11..16,
+		/// The green channel, in bits 5 to 10.
+		g set_g: 5..11,
+		/// The blue channel, in bits 0 to 4.
+		b set_b: 0..5,
+	}
+}
+
+pixel! {
+	/// A 16-bit RGBA pixel with 5 bits per color and 1 bit of alpha:
+	/// `RRRRRGGGGGBBBBBA`.
+	Rgba5551(u16) {
+		/// The red channel, in bits 11 to 15.
+		r set_r: 11..16,
+		/// The green channel, in bits 6 to 10.
+		g set_g: 6..11,
+		/// The blue channel, in bits 1 to 5.
+		b set_b: 1..6,
+		/// The alpha channel, in bit 0.
+		a set_a: 0..1,
+	}
+}
+
+pixel! {
+	/// A 32-bit RGBA pixel with 10 bits per color and 2 bits of alpha, with
+	/// red in the least significant bits:
+	/// `AABBBBBBBBBBGGGGGGGGGGRRRRRRRRRR`.
+	///
+	/// This is the layout of `DXGI_FORMAT_R10G10B10A2_UNORM`.
+	Rgb10a2(u32) {
+		/// The red channel, in bits 0 to 9.
+		r set_r: 0..10,
+		/// The green channel, in bits 10 to 19.
+		g set_g: 10..20,
+		/// The blue channel, in bits 20 to 29.
+		b set_b: 20..30,
+		/// The alpha channel, in bits 30 and 31.
+		a set_a: 30..32,
+	}
+}
+
+impl Rgb565 {
+	/// Convert from 8-bit red, green and blue channels.
+	#[inline]
+	pub const fn from_rgb8([r, g, b]: [u8; 3]) -> Self {
+		Self((from_8(r, 5) << 11 | from_8(g, 6) << 5 | from_8(b, 5)) as u16)
+	}
+
+	/// Convert to 8-bit red, green and blue channels.
+	#[inline]
+	pub fn to_rgb8(self) -> [u8; 3] {
+		let c = |v: u16, n| to_8(v as u32, n);
+		[c(self.r(), 5), c(self.g(), 6), c(self.b(), 5)]
+	}
+}
+
+impl Rgba5551 {
+	/// Convert from 8-bit red, green, blue and alpha channels.
+	///
+	/// An alpha of 128 or more is opaque.
+	#[inline]
+	pub const fn from_rgba8([r, g, b, a]: [u8; 4]) -> Self {
+		Self((from_8(r, 5) << 11 | from_8(g, 5) << 6 | from_8(b, 5) << 1 | from_8(a, 1)) as u16)
+	}
+
+	/// Convert to 8-bit red, green, blue and alpha channels.
+	#[inline]
+	pub fn to_rgba8(self) -> [u8; 4] {
+		let c = |v: u16, n| to_8(v as u32, n);
+		[
+			c(self.r(), 5),
+			c(self.g(), 5),
+			c(self.b(), 5),
+			c(self.a(), 1),
+		]
+	}
+}
+
+impl Rgb10a2 {
+	/// Convert from 8-bit red, green, blue and alpha channels.
+	#[inline]
+	pub const fn from_rgba8([r, g, b, a]: [u8; 4]) -> Self {
+		Self(from_8(r, 10) | from_8(g, 10) << 10 | from_8(b, 10) << 20 | from_8(a, 2) << 30)
+	}
+
+	/// Convert to 8-bit red, green, blue and alpha channels.
+	#[inline]
+	pub fn to_rgba8(self) -> [u8; 4] {
+		[
+			to_8(self.r(), 10),
+			to_8(self.g(), 10),
+			to_8(self.b(), 10),
+			to_8(self.a(), 2),
+		]
+	}
+}
@@ -0,0 +1,15 @@
+use core::fmt;
+
+/// The error returned by [`Bits::bits_as`][crate::Bits::bits_as] when the bits
+/// don't represent a valid value of the requested type.
+///
+/// Contains the raw bits that failed to convert.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidValue<B>(pub B);
+
+impl<B: fmt::Display> fmt::Display for InvalidValue<B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid value {} for field", self.0)
+	}
+}
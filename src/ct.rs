@@ -0,0 +1,99 @@
+//! Branchless bit operations for code that handles secrets, like
+//! cryptographic code.
+//!
+//! The regular methods of [`Bits`] return a `bool` for a single bit, which
+//! invites code that branches on it. The methods of [`CtBits`] instead
+//! return masks, with all bits set for `true` and no bits set for `false`,
+//! which can be combined using [`CtBits::ct_select`] without branching.
+//!
+//! # Guarantees
+//!
+//! The operations are implemented using only bitwise operations, shifts,
+//! additions and subtractions on the values, without branches, table lookups
+//! or early returns that depend on them. Indices and ranges are treated as
+//! public: they are checked like in [`Bits`], and the time taken may depend
+//! on them.
+//!
+//! Every mask that is returned or taken is passed through
+//! [`core::hint::black_box`], to stop the optimizer from recognizing that a
+//! mask can only be 0 or all ones and reintroducing a branch. Rust itself
+//! does not promise constant-time execution, and `black_box` is only a best
+//! effort, so check the generated code for the targets that matter.
+//!
+//! # Example
+//!
+//! ```
+//! use intbits::ct::CtBits;
+//!
+//! let secret = 0b1010u8;
+//! let mask = secret.ct_bit(1);
+//! assert_eq!(mask, 0xFF);
+//! assert_eq!(u8::ct_select(mask, 10, 20), 10);
+//! assert_eq!(secret.ct_eq_bits(0..2, 0b0110), 0xFF);
+//! assert_eq!(secret.ct_eq_bits(0..4, 0b0110), 0x00);
+//! ```
+
+use super::range::bounds;
+use super::Bits;
+use core::hint::black_box;
+use core::ops::RangeBounds;
+
+/// Branchless bit operations on integers.
+///
+/// See the [module documentation][self] for the guarantees.
+pub trait CtBits: Bits {
+	/// Get a mask with all bits set if bit `i` is set, or 0 otherwise.
+	///
+	/// Panics if `i` is out of range.
+	fn ct_bit(self, i: u32) -> Self::Bits;
+
+	/// Get a mask with all bits set if the given range of bits of `self` and
+	/// `other` is equal, or 0 otherwise.
+	///
+	/// Panics if the range is out of range.
+	fn ct_eq_bits<R: RangeBounds<u32>>(self, range: R, other: Self) -> Self::Bits;
+
+	/// Take the bits of `a` where `mask` is set, and the bits of `b`
+	/// elsewhere.
+	///
+	/// With a mask from [`ct_bit`][Self::ct_bit] or
+	/// [`ct_eq_bits`][Self::ct_eq_bits], this selects either `a` or `b`.
+	fn ct_select(mask: Self::Bits, a: Self, b: Self) -> Self;
+}
+
+macro_rules! ct_bits {
+	($($t:ident $ut:ident),*) => {$(
+		impl CtBits for $t {
+			#[inline]
+			fn ct_bit(self, i: u32) -> $ut {
+				check!(i < $ut::BITS, invalid_bit_index);
+				black_box((self as $ut >> i & 1).wrapping_neg())
+			}
+
+			#[inline]
+			fn ct_eq_bits<R: RangeBounds<u32>>(self, range: R, other: Self) -> $ut {
+				let (start, end) = bounds(&range, $ut::BITS);
+				let ones = (!0 as $ut).checked_shr($ut::BITS - (end - start)).unwrap_or(0);
+				let ones = ones.checked_shl(start).unwrap_or(0);
+				let diff = (self ^ other) as $ut & ones;
+				// The top bit of `diff | -diff` is set if and only if `diff` is not zero.
+				let nonzero = (diff | diff.wrapping_neg()) >> ($ut::BITS - 1);
+				black_box(nonzero.wrapping_sub(1))
+			}
+
+			#[inline]
+			fn ct_select(mask: $ut, a: Self, b: Self) -> Self {
+				let mask = black_box(mask);
+				(b as $ut ^ mask & (a ^ b) as $ut) as $t
+			}
+		}
+	)*};
+}
+
+ct_bits!(u8 u8, u16 u16, u32 u32, u64 u64, usize usize);
+#[cfg(feature = "impl-signed")]
+ct_bits!(i8 u8, i16 u16, i32 u32, i64 u64, isize usize);
+#[cfg(feature = "impl-128")]
+ct_bits!(u128 u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+ct_bits!(i128 u128);
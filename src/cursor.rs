@@ -0,0 +1,142 @@
+use super::{BitOrder, OutOfBits, SliceBits};
+
+/// A bit position within a byte buffer, for random access reads and writes.
+///
+/// Like [`std::io::Cursor`], this wraps any buffer: `&[u8]` for reading,
+/// or `&mut [u8]` (or an owned buffer) for reading and writing.
+///
+/// Fields can be read and written at the current position, which advances
+/// the cursor, or at absolute bit offsets, which leaves the cursor where it
+/// is.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitCursor, BitOrder};
+///
+/// let mut c = BitCursor::new([0u8; 4], BitOrder::MsbFirst);
+/// c.seek_bits(12).unwrap();
+/// c.write_bits(0xABC, 12).unwrap();
+/// assert_eq!(c.position_bits(), 24);
+/// c.write_bits_at(0, 0x1, 4).unwrap();
+/// assert_eq!(c.read_bits_at(8, 8), Ok(0x0A));
+/// assert_eq!(c.into_inner(), [0x10, 0x0A, 0xBC, 0x00]);
+/// ```
+///
+/// [`std::io::Cursor`]: https://doc.rust-lang.org/std/io/struct.Cursor.html
+#[derive(Clone, Debug)]
+pub struct BitCursor<T> {
+	inner: T,
+	pos: usize,
+	order: BitOrder,
+}
+
+impl<T> BitCursor<T> {
+	/// Create a cursor at the first bit of `inner`.
+	pub fn new(inner: T, order: BitOrder) -> Self {
+		Self {
+			inner,
+			pos: 0,
+			order,
+		}
+	}
+
+	/// Get the underlying buffer back.
+	pub fn into_inner(self) -> T {
+		self.inner
+	}
+
+	/// Get a reference to the underlying buffer.
+	pub fn get_ref(&self) -> &T {
+		&self.inner
+	}
+
+	/// Get a mutable reference to the underlying buffer.
+	pub fn get_mut(&mut self) -> &mut T {
+		&mut self.inner
+	}
+
+	/// The bit order of this cursor.
+	pub fn order(&self) -> BitOrder {
+		self.order
+	}
+
+	/// The current position, in bits from the start of the buffer.
+	pub fn position_bits(&self) -> usize {
+		self.pos
+	}
+}
+
+impl<T: AsRef<[u8]>> BitCursor<T> {
+	fn len_bits(&self) -> usize {
+		self.inner.as_ref().len() * 8
+	}
+
+	fn check(&self, offset: usize, n: u32) -> Result<usize, OutOfBits> {
+		check!(n <= 64, bit_range_too_wide);
+		match offset.checked_add(n as usize) {
+			Some(end) if end <= self.len_bits() => Ok(end),
+			_ => Err(OutOfBits),
+		}
+	}
+
+	/// Move the cursor to an absolute bit position.
+	///
+	/// The position may be at most the length of the buffer in bits.
+	pub fn seek_bits(&mut self, pos: usize) -> Result<(), OutOfBits> {
+		if pos > self.len_bits() {
+			return Err(OutOfBits);
+		}
+		self.pos = pos;
+		Ok(())
+	}
+
+	/// The number of bits after the current position.
+	pub fn remaining_bits(&self) -> usize {
+		self.len_bits().saturating_sub(self.pos)
+	}
+
+	/// Read `n` bits at an absolute bit offset, without moving the cursor.
+	///
+	/// Panics if `n` is more than 64.
+	pub fn read_bits_at(&self, offset: usize, n: u32) -> Result<u64, OutOfBits> {
+		let end = self.check(offset, n)?;
+		Ok(self.inner.as_ref().bits(offset..end, self.order) as u64)
+	}
+
+	/// Read `n` bits at the current position, and advance past them.
+	///
+	/// Panics if `n` is more than 64.
+	pub fn read_bits(&mut self, n: u32) -> Result<u64, OutOfBits> {
+		let bits = self.read_bits_at(self.pos, n)?;
+		self.pos += n as usize;
+		Ok(bits)
+	}
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> BitCursor<T> {
+	/// Write the lowest `n` bits of `value` at an absolute bit offset,
+	/// without moving the cursor.
+	///
+	/// Panics if `n` is more than 64, or when the other bits of `value` are
+	/// not 0.
+	pub fn write_bits_at(&mut self, offset: usize, value: u64, n: u32) -> Result<(), OutOfBits> {
+		let end = self.check(offset, n)?;
+		let order = self.order;
+		self.inner
+			.as_mut()
+			.set_bits(offset..end, value as u128, order);
+		Ok(())
+	}
+
+	/// Write the lowest `n` bits of `value` at the current position, and
+	/// advance past them.
+	///
+	/// Panics if `n` is more than 64, or when the other bits of `value` are
+	/// not 0.
+	pub fn write_bits(&mut self, value: u64, n: u32) -> Result<(), OutOfBits> {
+		self.write_bits_at(self.pos, value, n)?;
+		self.pos += n as usize;
+		Ok(())
+	}
+}
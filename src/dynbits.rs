@@ -0,0 +1,91 @@
+use super::{Bits, BitsIndex};
+use core::convert::TryFrom;
+use core::ops::Range;
+
+/// A version of [`Bits`] that can be used as a trait object.
+///
+/// All methods take `u32` indices and [`Range<u32>`], and use `u128` for
+/// values, so integers of different types can be handled through the same
+/// `dyn DynBits`.
+///
+/// This is implemented for all types that implement [`Bits`].
+///
+/// # Example
+///
+/// ```
+/// use intbits::DynBits;
+///
+/// let mut regs: [&mut dyn DynBits; 3] = [&mut 0u8, &mut 0x1234u16, &mut u32::MAX];
+/// assert_eq!(regs.iter().map(|r| r.n_bits()).sum::<u32>(), 56);
+///
+/// for r in &mut regs {
+///     r.set_bits(0..4, 0xA);
+/// }
+/// assert_eq!(regs[1].bits(0..16), 0x123A);
+/// assert_eq!(regs[2].bits(0..8), 0xFA);
+/// ```
+pub trait DynBits {
+	/// The number of bits of the integer.
+	///
+	/// See [`Bits::N_BITS`].
+	fn n_bits(&self) -> u32;
+
+	/// Get a specific bit.
+	///
+	/// See [`Bits::bit`].
+	fn bit(&self, i: u32) -> bool;
+
+	/// Get a range of bits.
+	///
+	/// See [`Bits::bits`].
+	fn bits(&self, range: Range<u32>) -> u128;
+
+	/// Set a specific bit.
+	///
+	/// See [`Bits::set_bit`].
+	fn set_bit(&mut self, i: u32, bit: bool);
+
+	/// Set a range of bits.
+	///
+	/// See [`Bits::set_bits`].
+	fn set_bits(&mut self, range: Range<u32>, bits: u128);
+}
+
+impl<T> DynBits for T
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+	u128: TryFrom<T::Bits>,
+	T::Bits: TryFrom<u128>,
+{
+	#[inline]
+	fn n_bits(&self) -> u32 {
+		T::N_BITS
+	}
+
+	#[inline]
+	fn bit(&self, i: u32) -> bool {
+		Bits::bit(*self, i)
+	}
+
+	#[inline]
+	fn bits(&self, range: Range<u32>) -> u128 {
+		match u128::try_from(Bits::bits(*self, range)) {
+			Ok(bits) => bits,
+			Err(_) => unreachable!(),
+		}
+	}
+
+	#[inline]
+	fn set_bit(&mut self, i: u32, bit: bool) {
+		Bits::set_bit(self, i, bit)
+	}
+
+	#[inline]
+	fn set_bits(&mut self, range: Range<u32>, bits: u128) {
+		match T::Bits::try_from(bits) {
+			Ok(bits) => Bits::set_bits(self, range, bits),
+			Err(_) => crate::panic::bits_outside_range(),
+		}
+	}
+}
@@ -0,0 +1,405 @@
+use super::array::WordsBits;
+use super::range::bounds;
+use super::BitSetIter;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::ops::Bound::Unbounded;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, RangeBounds};
+use core::ops::{Sub, SubAssign};
+
+/// A growable set of numbers, stored in a [`Vec`].
+///
+/// This is like [`FixedBitSet`][crate::FixedBitSet], but grows as numbers
+/// are added, and sets of different lengths can be combined. Bits beyond the
+/// end of the set read as 0, and setting them grows the set.
+///
+/// Besides the set operations, it has the same bit access functions as
+/// [`ArrayBits`][crate::ArrayBits], with the same range semantics. A range
+/// without an end goes up to [`DynBitSet::n_bits`].
+///
+/// # Example
+///
+/// ```
+/// use intbits::DynBitSet;
+///
+/// let mut claimed = DynBitSet::new();
+/// claimed.insert(3);
+/// claimed.insert(200);
+/// claimed.set_bits(64..72, 0xFF);
+/// assert!(claimed.contains(200));
+/// assert_eq!(claimed.len(), 10);
+/// assert_eq!(claimed.iter().last(), Some(200));
+/// assert_eq!(claimed.bits(60..68), 0xF0);
+/// assert_eq!(claimed.bits(1000..1010), 0);
+/// ```
+#[derive(Clone, Default)]
+pub struct DynBitSet {
+	words: Vec<u64>,
+}
+
+impl DynBitSet {
+	/// Create an empty set.
+	#[inline]
+	pub const fn new() -> Self {
+		Self { words: Vec::new() }
+	}
+
+	/// Create an empty set with room for the numbers below `n_bits` without
+	/// growing.
+	#[inline]
+	pub fn with_capacity(n_bits: u32) -> Self {
+		Self {
+			words: Vec::with_capacity(words_for(n_bits)),
+		}
+	}
+
+	/// Create a set from its words, least significant word first.
+	#[inline]
+	pub fn from_words(words: Vec<u64>) -> Self {
+		Self { words }
+	}
+
+	/// Get the words of the set, least significant word first.
+	#[inline]
+	pub fn words(&self) -> &[u64] {
+		&self.words
+	}
+
+	/// Get the words of the set, least significant word first.
+	#[inline]
+	pub fn into_words(self) -> Vec<u64> {
+		self.words
+	}
+
+	/// The number of bits currently stored, which is a multiple of 64.
+	///
+	/// All numbers in the set are below this.
+	#[inline]
+	pub fn n_bits(&self) -> u32 {
+		(self.words.len() as u32).saturating_mul(64)
+	}
+
+	/// Get a specific bit.
+	///
+	/// See [`ArrayBits::bit`][crate::ArrayBits::bit].
+	#[inline]
+	pub fn bit(&self, i: u32) -> bool {
+		i < self.n_bits() && self.words.words_bit(i)
+	}
+
+	/// Get a range of bits.
+	///
+	/// See [`ArrayBits::bits`][crate::ArrayBits::bits].
+	#[inline]
+	pub fn bits<R: RangeBounds<u32>>(&self, range: R) -> u128 {
+		let (start, end) = self.bounds(&range);
+		check!(end - start <= 128, bit_range_too_wide);
+		let n_bits = self.n_bits();
+		if start >= n_bits {
+			return 0;
+		}
+		self.words.words_bits(start..end.min(n_bits))
+	}
+
+	/// Set a specific bit.
+	///
+	/// See [`ArrayBits::set_bit`][crate::ArrayBits::set_bit].
+	#[inline]
+	pub fn set_bit(&mut self, i: u32, bit: bool) {
+		if bit {
+			self.grow(i.saturating_add(1));
+		} else if i >= self.n_bits() {
+			return;
+		}
+		self.words.words_set_bit(i, bit)
+	}
+
+	/// Set a range of bits.
+	///
+	/// See [`ArrayBits::set_bits`][crate::ArrayBits::set_bits].
+	#[inline]
+	pub fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: u128) {
+		let (start, end) = self.bounds(&range);
+		check!(end - start <= 128, bit_range_too_wide);
+		self.grow(end);
+		self.words.words_set_bits(start..end, bits)
+	}
+
+	/// Get a new set with one bit set to a specific value.
+	///
+	/// See [`ArrayBits::with_bit`][crate::ArrayBits::with_bit].
+	#[inline]
+	pub fn with_bit(mut self, i: u32, bit: bool) -> Self {
+		self.set_bit(i, bit);
+		self
+	}
+
+	/// Get a new set with a range of bits set to specific values.
+	///
+	/// See [`ArrayBits::with_bits`][crate::ArrayBits::with_bits].
+	#[inline]
+	pub fn with_bits<R: RangeBounds<u32>>(mut self, range: R, bits: u128) -> Self {
+		self.set_bits(range, bits);
+		self
+	}
+
+	/// Add a number to the set.
+	///
+	/// Returns whether it was newly added.
+	#[inline]
+	pub fn insert(&mut self, i: u32) -> bool {
+		let new = !self.bit(i);
+		self.set_bit(i, true);
+		new
+	}
+
+	/// Remove a number from the set.
+	///
+	/// Returns whether it was in the set.
+	#[inline]
+	pub fn remove(&mut self, i: u32) -> bool {
+		let present = self.bit(i);
+		self.set_bit(i, false);
+		present
+	}
+
+	/// Check whether a number is in the set.
+	#[inline]
+	pub fn contains(&self, i: u32) -> bool {
+		self.bit(i)
+	}
+
+	/// Remove all numbers from the set.
+	///
+	/// This keeps the allocated memory.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.words.clear();
+	}
+
+	/// Free the memory of the words above the largest number in the set.
+	#[inline]
+	pub fn shrink_to_fit(&mut self) {
+		self.words.truncate(self.used_words());
+		self.words.shrink_to_fit();
+	}
+
+	/// The number of numbers in the set.
+	#[inline]
+	pub fn len(&self) -> u32 {
+		self.words.iter().map(|w| w.count_ones()).sum()
+	}
+
+	/// Check whether the set is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.words.iter().all(|&w| w == 0)
+	}
+
+	/// The smallest number in the set.
+	#[inline]
+	pub fn first(&self) -> Option<u32> {
+		self.iter().next()
+	}
+
+	/// The largest number in the set.
+	#[inline]
+	pub fn last(&self) -> Option<u32> {
+		self.iter().next_back()
+	}
+
+	/// The numbers in either set.
+	#[inline]
+	pub fn union(mut self, other: &Self) -> Self {
+		self.grow_words(other.used_words());
+		self.zip(other, |a, b| a | b);
+		self
+	}
+
+	/// The numbers in both sets.
+	#[inline]
+	pub fn intersection(mut self, other: &Self) -> Self {
+		self.words.truncate(other.words.len());
+		self.zip(other, |a, b| a & b);
+		self
+	}
+
+	/// The numbers in `self` but not in `other`.
+	#[inline]
+	pub fn difference(mut self, other: &Self) -> Self {
+		self.zip(other, |a, b| a & !b);
+		self
+	}
+
+	/// The numbers in exactly one of the sets.
+	#[inline]
+	pub fn symmetric_difference(mut self, other: &Self) -> Self {
+		self.grow_words(other.used_words());
+		self.zip(other, |a, b| a ^ b);
+		self
+	}
+
+	/// Check whether all numbers of `self` are in `other`.
+	#[inline]
+	pub fn is_subset(&self, other: &Self) -> bool {
+		let n = self.words.len().min(other.words.len());
+		self.words[..n]
+			.iter()
+			.zip(&other.words)
+			.all(|(a, b)| a & !b == 0)
+			&& self.words[n..].iter().all(|&w| w == 0)
+	}
+
+	/// Check whether the sets have no numbers in common.
+	#[inline]
+	pub fn is_disjoint(&self, other: &Self) -> bool {
+		self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+	}
+
+	/// Iterate over the numbers in the set, in increasing order.
+	#[inline]
+	pub fn iter(&self) -> BitSetIter<'_> {
+		BitSetIter::new(&self.words)
+	}
+
+	// Like `bounds`, but with the end only limited by the type, since the set
+	// can grow.
+	#[inline]
+	fn bounds<R: RangeBounds<u32>>(&self, range: &R) -> (u32, u32) {
+		match range.end_bound() {
+			Unbounded => bounds(range, self.n_bits()),
+			_ => bounds(range, u32::MAX),
+		}
+	}
+
+	#[inline]
+	fn grow(&mut self, n_bits: u32) {
+		self.grow_words(words_for(n_bits));
+	}
+
+	#[inline]
+	fn grow_words(&mut self, n: usize) {
+		if self.words.len() < n {
+			self.words.resize(n, 0);
+		}
+	}
+
+	// The number of words without the trailing zero words.
+	#[inline]
+	fn used_words(&self) -> usize {
+		self.words
+			.iter()
+			.rposition(|&w| w != 0)
+			.map_or(0, |i| i + 1)
+	}
+
+	// Combines the words both sets have. The other words of `self` are
+	// combined with zero words.
+	#[inline]
+	fn zip(&mut self, other: &Self, f: impl Fn(u64, u64) -> u64) {
+		let zeros = core::iter::repeat(&0);
+		for (a, &b) in self.words.iter_mut().zip(other.words.iter().chain(zeros)) {
+			*a = f(*a, b);
+		}
+	}
+}
+
+#[inline]
+fn words_for(n_bits: u32) -> usize {
+	(n_bits as usize).div_ceil(64)
+}
+
+/// Sets are equal when they have the same numbers, regardless of
+/// [`DynBitSet::n_bits`].
+impl PartialEq for DynBitSet {
+	#[inline]
+	fn eq(&self, other: &Self) -> bool {
+		self.words[..self.used_words()] == other.words[..other.used_words()]
+	}
+}
+
+impl Eq for DynBitSet {}
+
+impl Hash for DynBitSet {
+	#[inline]
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.words[..self.used_words()].hash(state)
+	}
+}
+
+impl fmt::Debug for DynBitSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_set().entries(self.iter()).finish()
+	}
+}
+
+impl<'a> IntoIterator for &'a DynBitSet {
+	type Item = u32;
+	type IntoIter = BitSetIter<'a>;
+
+	#[inline]
+	fn into_iter(self) -> BitSetIter<'a> {
+		self.iter()
+	}
+}
+
+impl FromIterator<u32> for DynBitSet {
+	fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+		let mut set = Self::new();
+		set.extend(iter);
+		set
+	}
+}
+
+impl Extend<u32> for DynBitSet {
+	fn extend<I: IntoIterator<Item = u32>>(&mut self, iter: I) {
+		for i in iter {
+			self.insert(i);
+		}
+	}
+}
+
+macro_rules! set_op {
+	($($op:ident $f:ident $op_assign:ident $f_assign:ident $method:ident),*) => {$(
+		impl $op for DynBitSet {
+			type Output = Self;
+
+			#[inline]
+			fn $f(self, other: Self) -> Self {
+				self.$method(&other)
+			}
+		}
+
+		impl $op<&DynBitSet> for DynBitSet {
+			type Output = Self;
+
+			#[inline]
+			fn $f(self, other: &Self) -> Self {
+				self.$method(other)
+			}
+		}
+
+		impl $op_assign for DynBitSet {
+			#[inline]
+			fn $f_assign(&mut self, other: Self) {
+				*self = core::mem::take(self).$method(&other);
+			}
+		}
+
+		impl $op_assign<&DynBitSet> for DynBitSet {
+			#[inline]
+			fn $f_assign(&mut self, other: &Self) {
+				*self = core::mem::take(self).$method(other);
+			}
+		}
+	)*};
+}
+
+set_op!(
+	BitOr bitor BitOrAssign bitor_assign union,
+	BitAnd bitand BitAndAssign bitand_assign intersection,
+	Sub sub SubAssign sub_assign difference,
+	BitXor bitxor BitXorAssign bitxor_assign symmetric_difference
+);
@@ -0,0 +1,239 @@
+//! 8b/10b line coding, as used by Fibre Channel, PCI Express, SATA, USB 3 and
+//! many other serial links.
+//!
+//! Every byte is encoded as a 10-bit code with at most one more or one less
+//! `1` than `0`. The [`Encoder`] keeps track of the running disparity to keep
+//! the link DC balanced, and the [`Decoder`] checks it.
+//!
+//! Codes are written as `abcdei fghj`, with `a` (the first bit on the line) in
+//! bit 9 and `j` in bit 0.
+//!
+//! # Example
+//!
+//! ```
+//! use intbits::enc8b10b::{CodeError, Decoder, Encoder, Symbol, K28_5};
+//!
+//! let mut encoder = Encoder::new();
+//! assert_eq!(encoder.encode(K28_5), 0b001111_1010);
+//! assert_eq!(encoder.running_disparity(), 1);
+//! assert_eq!(encoder.encode(Symbol::Data(0x00)), 0b011000_1011);
+//!
+//! let mut decoder = Decoder::new();
+//! assert_eq!(decoder.decode(0b001111_1010), Ok(K28_5));
+//! assert_eq!(decoder.decode(0b011000_1011), Ok(Symbol::Data(0x00)));
+//!
+//! // D.0.0 can't be encoded like this with a negative running disparity.
+//! let mut decoder = Decoder::new();
+//! assert_eq!(decoder.decode(0b011000_1011), Err(CodeError::WrongDisparity));
+//! ```
+
+use core::fmt;
+
+/// A byte to be encoded, or a decoded byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Symbol {
+	/// A data byte, D.x.y.
+	Data(u8),
+	/// A control byte, K.x.y.
+	///
+	/// Only K.28.0 to K.28.7, K.23.7, K.27.7, K.29.7 and K.30.7 exist.
+	Control(u8),
+}
+
+/// K.28.5, the comma symbol used for alignment.
+pub const K28_5: Symbol = Symbol::Control(0xBC);
+
+impl Symbol {
+	/// Check if this is a data byte, or a control byte that exists.
+	pub const fn is_valid(self) -> bool {
+		match self {
+			Symbol::Data(_) => true,
+			Symbol::Control(b) => b & 31 == 28 || matches!(b, 0xF7 | 0xFB | 0xFD | 0xFE),
+		}
+	}
+}
+
+/// The error returned by [`Decoder::decode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CodeError {
+	/// The code is not a valid 8b/10b code.
+	InvalidCode,
+	/// The code is valid, but not with the current running disparity.
+	WrongDisparity,
+}
+
+impl fmt::Display for CodeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CodeError::InvalidCode => write!(f, "invalid 8b/10b code"),
+			CodeError::WrongDisparity => write!(f, "8b/10b code with wrong disparity"),
+		}
+	}
+}
+
+/// The `abcdei` codes of the 5b/6b sub-block for D.x, for a negative running
+/// disparity.
+const D6: [u16; 32] = [
+	0b100111, 0b011101, 0b101101, 0b110001, 0b110101, 0b101001, 0b011001, 0b111000, //
+	0b111001, 0b100101, 0b010101, 0b110100, 0b001101, 0b101100, 0b011100, 0b010111, //
+	0b011011, 0b100011, 0b010011, 0b110010, 0b001011, 0b101010, 0b011010, 0b111010, //
+	0b110011, 0b100110, 0b010110, 0b110110, 0b001110, 0b101110, 0b011110, 0b101011, //
+];
+
+/// The `abcdei` code of K.28, for a negative running disparity.
+const K28_6: u16 = 0b001111;
+
+/// The `fghj` codes of the 3b/4b sub-block for D.x.y, for a negative running
+/// disparity. For y = 7, this is the primary code, D.x.P7.
+const D4: [u16; 8] = [
+	0b1011, 0b1001, 0b0101, 0b1100, 0b1101, 0b1010, 0b0110, 0b1110,
+];
+
+/// The `fghj` code of the alternate D.x.A7, for a negative running disparity.
+const A7: u16 = 0b0111;
+
+/// The `fghj` codes of the 3b/4b sub-block for K.x.y, for a negative running
+/// disparity.
+const K4: [u16; 8] = [
+	0b1011, 0b0110, 0b1010, 0b1100, 0b1101, 0b0101, 0b1001, 0b0111,
+];
+
+/// Encode a byte, and return the code and the new running disparity (`true`
+/// for positive).
+const fn encode(byte: u8, control: bool, mut rd: bool) -> (u16, bool) {
+	let (x, y) = ((byte & 31) as usize, (byte >> 5) as usize);
+	let mut six = if control && x == 28 { K28_6 } else { D6[x] };
+	let balanced = six.count_ones() == 3;
+	if rd && (!balanced || six == 0b111000) {
+		six ^= 0b111111;
+	}
+	if !balanced {
+		rd = !rd;
+	}
+	let mut four = if control {
+		K4[y]
+	} else if y == 7
+		&& (!rd && (x == 17 || x == 18 || x == 20) || rd && (x == 11 || x == 13 || x == 14))
+	{
+		A7
+	} else {
+		D4[y]
+	};
+	let balanced = four.count_ones() == 2;
+	if rd && (!balanced || four == 0b1100 || control) {
+		four ^= 0b1111;
+	}
+	if !balanced {
+		rd = !rd;
+	}
+	(six << 4 | four, rd)
+}
+
+/// For every 10-bit code: the decoded byte in the lowest 8 bits, bit 8 set
+/// for a control byte, and bits 9 and 10 set if the code is valid for a
+/// negative or positive running disparity.
+static DECODE: [u16; 1024] = {
+	let mut table = [0; 1024];
+	let mut i = 0;
+	while i < 1024 {
+		let (byte, control, rd) = (i as u8, i & 256 != 0, i & 512 != 0);
+		if !control || Symbol::Control(byte).is_valid() {
+			let (code, _) = encode(byte, control, rd);
+			table[code as usize] |= byte as u16 | (control as u16) << 8 | 1 << (9 + rd as u16);
+		}
+		i += 1;
+	}
+	table
+};
+
+/// Encodes bytes into 8b/10b codes, keeping track of the running disparity.
+#[derive(Clone, Debug, Default)]
+pub struct Encoder {
+	rd: bool,
+}
+
+impl Encoder {
+	/// Create an encoder, starting with a negative running disparity.
+	pub const fn new() -> Self {
+		Self { rd: false }
+	}
+
+	/// The running disparity: -1 or 1.
+	pub const fn running_disparity(&self) -> i32 {
+		if self.rd {
+			1
+		} else {
+			-1
+		}
+	}
+
+	/// Encode a byte into a 10-bit code.
+	///
+	/// Panics if `symbol` is a control byte that doesn't exist.
+	#[inline]
+	pub fn encode(&mut self, symbol: Symbol) -> u16 {
+		check!(symbol.is_valid(), invalid_control_symbol);
+		let (byte, control) = match symbol {
+			Symbol::Data(b) => (b, false),
+			Symbol::Control(b) => (b, true),
+		};
+		let (code, rd) = encode(byte, control, self.rd);
+		self.rd = rd;
+		code
+	}
+}
+
+/// Decodes 8b/10b codes into bytes, checking the running disparity.
+#[derive(Clone, Debug, Default)]
+pub struct Decoder {
+	rd: bool,
+}
+
+impl Decoder {
+	/// Create a decoder, starting with a negative running disparity.
+	pub const fn new() -> Self {
+		Self { rd: false }
+	}
+
+	/// The running disparity: -1 or 1.
+	pub const fn running_disparity(&self) -> i32 {
+		if self.rd {
+			1
+		} else {
+			-1
+		}
+	}
+
+	/// Decode a 10-bit code.
+	///
+	/// The running disparity is updated according to the code, even if it
+	/// was received with the wrong disparity, such that a single error isn't
+	/// reported again for every following code.
+	///
+	/// Panics if `code` has more than 10 bits.
+	#[inline]
+	pub fn decode(&mut self, code: u16) -> Result<Symbol, CodeError> {
+		check!(code < 1024, bits_outside_range);
+		let entry = DECODE[code as usize];
+		if entry == 0 {
+			return Err(CodeError::InvalidCode);
+		}
+		let valid = entry & 1 << (9 + self.rd as u16) != 0;
+		match code.count_ones() {
+			6 => self.rd = true,
+			4 => self.rd = false,
+			_ => {}
+		}
+		if !valid {
+			return Err(CodeError::WrongDisparity);
+		}
+		let byte = entry as u8;
+		Ok(if entry & 1 << 8 != 0 {
+			Symbol::Control(byte)
+		} else {
+			Symbol::Data(byte)
+		})
+	}
+}
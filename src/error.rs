@@ -0,0 +1,186 @@
+use super::enc8b10b::CodeError;
+use super::{
+	BuildError, DecodeError, InvalidValue, OutOfBits, OverlayError, ParseBinError, PartitionError,
+	ReservedBitsSet, SchemaError,
+};
+use core::fmt;
+
+/// An error from any fallible operation of this crate.
+///
+/// Every error type of this crate converts into this type, such that
+/// different errors can be propagated with `?` from a single function. Like
+/// the other error types, this implements [`core::error::Error`], so it can
+/// also be propagated into error types of other crates.
+///
+/// This only keeps the kind of error. Match on the specific error types for
+/// details like the index of the field that caused it.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, BitReader, Error, FromBinStr};
+///
+/// fn parse(s: &str) -> Result<u16, Error> {
+///     let data = [u8::from_bin_str(s)?];
+///     let mut r = BitReader::new(&data, BitOrder::MsbFirst);
+///     Ok(r.read_bits(4)? as u16 * 100)
+/// }
+///
+/// assert_eq!(parse("0b0011_0000"), Ok(300));
+/// assert_eq!(parse("0b2"), Err(Error::InvalidDigit));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error {
+	/// A bit index is out of range.
+	InvalidIndex,
+	/// A range of bits is inverted or out of range.
+	InvalidRange,
+	/// A value doesn't fit in its range of bits.
+	ValueTooWide,
+	/// Ranges of bits overlap, or a bit was written more than once.
+	Overlap,
+	/// Some bits are not part of any field.
+	Gap,
+	/// Reserved bits are not zero.
+	ReservedBitsSet,
+	/// Two fields have the same name.
+	DuplicateName,
+	/// Two variants of an overlay have the same tag.
+	DuplicateTag,
+	/// There is no field with the given name.
+	UnknownField,
+	/// A bit stream ended before enough bits were read.
+	OutOfBits,
+	/// A bit stream contains an invalid code.
+	InvalidCode,
+	/// Bits don't represent a valid value of the requested type.
+	InvalidValue,
+	/// A string contains no digits or an invalid digit.
+	InvalidDigit,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match self {
+			Error::InvalidIndex => "invalid bit index",
+			Error::InvalidRange => "invalid bit range",
+			Error::ValueTooWide => "bits outside range",
+			Error::Overlap => "overlapping bit ranges",
+			Error::Gap => "bits not used by any field",
+			Error::ReservedBitsSet => "reserved bits are not zero",
+			Error::DuplicateName => "fields with the same name",
+			Error::DuplicateTag => "variants with the same tag",
+			Error::UnknownField => "unknown field",
+			Error::OutOfBits => "not enough bits left in the stream",
+			Error::InvalidCode => "invalid code in the stream",
+			Error::InvalidValue => "invalid value for field",
+			Error::InvalidDigit => "invalid digit",
+		})
+	}
+}
+
+impl core::error::Error for Error {}
+
+impl From<OutOfBits> for Error {
+	fn from(_: OutOfBits) -> Self {
+		Error::OutOfBits
+	}
+}
+
+impl From<DecodeError> for Error {
+	fn from(e: DecodeError) -> Self {
+		match e {
+			DecodeError::OutOfBits => Error::OutOfBits,
+			DecodeError::InvalidCode => Error::InvalidCode,
+		}
+	}
+}
+
+impl From<CodeError> for Error {
+	fn from(_: CodeError) -> Self {
+		Error::InvalidCode
+	}
+}
+
+impl From<BuildError> for Error {
+	fn from(e: BuildError) -> Self {
+		match e {
+			BuildError::Overlap => Error::Overlap,
+			BuildError::ValueTooWide => Error::ValueTooWide,
+		}
+	}
+}
+
+impl From<SchemaError> for Error {
+	fn from(e: SchemaError) -> Self {
+		match e {
+			SchemaError::OutOfRange { .. } => Error::InvalidRange,
+			SchemaError::Overlap { .. } => Error::Overlap,
+			SchemaError::DuplicateName { .. } => Error::DuplicateName,
+			SchemaError::UnknownField => Error::UnknownField,
+		}
+	}
+}
+
+impl From<OverlayError> for Error {
+	fn from(e: OverlayError) -> Self {
+		match e {
+			OverlayError::TagOutOfRange => Error::InvalidRange,
+			OverlayError::TagTooWide { .. } => Error::ValueTooWide,
+			OverlayError::DuplicateTag { .. } => Error::DuplicateTag,
+		}
+	}
+}
+
+impl From<PartitionError> for Error {
+	fn from(e: PartitionError) -> Self {
+		match e {
+			PartitionError::OutOfRange { .. } => Error::InvalidRange,
+			PartitionError::Overlap { .. } => Error::Overlap,
+			PartitionError::Gap { .. } => Error::Gap,
+		}
+	}
+}
+
+impl From<ReservedBitsSet> for Error {
+	fn from(_: ReservedBitsSet) -> Self {
+		Error::ReservedBitsSet
+	}
+}
+
+impl From<ParseBinError> for Error {
+	fn from(e: ParseBinError) -> Self {
+		match e {
+			ParseBinError::Empty | ParseBinError::InvalidDigit { .. } => Error::InvalidDigit,
+			ParseBinError::TooLong { .. } => Error::ValueTooWide,
+		}
+	}
+}
+
+impl<B> From<InvalidValue<B>> for Error {
+	fn from(_: InvalidValue<B>) -> Self {
+		Error::InvalidValue
+	}
+}
+
+impl core::error::Error for OutOfBits {}
+impl core::error::Error for DecodeError {}
+impl core::error::Error for CodeError {}
+impl core::error::Error for BuildError {}
+impl core::error::Error for SchemaError {}
+impl core::error::Error for OverlayError {}
+impl core::error::Error for PartitionError {}
+impl core::error::Error for ReservedBitsSet {}
+impl core::error::Error for ParseBinError {}
+impl<B: fmt::Debug + fmt::Display> core::error::Error for InvalidValue<B> {}
+
+impl<E: core::error::Error + 'static> core::error::Error for crate::ReadError<E> {
+	fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+		match self {
+			crate::ReadError::OutOfBits => None,
+			crate::ReadError::Io(e) => Some(e),
+		}
+	}
+}
@@ -0,0 +1,364 @@
+use super::range::bounds;
+use super::Bits;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use core::ops::Range;
+use core::ops::RangeBounds;
+
+/// A compile-time descriptor of a field of `LEN` bits starting at bit `START`
+/// of an integer of type `T`.
+///
+/// This is a zero-sized type that is only used through its associated
+/// constants and functions. It's meant to be used with a type alias, such that
+/// register fields can be defined once instead of repeating ranges everywhere.
+///
+/// The field is checked at compile time to fit within `T`. Using a field that
+/// doesn't fit results in a compilation error.
+///
+/// With the `serde` feature, this is serialized as its range of bits.
+/// Deserializing it checks that the range matches, so a layout loaded at
+/// runtime can be checked against the one in the code.
+///
+/// # Example
+///
+/// ```
+/// use intbits::BitField;
+///
+/// type Mode = BitField<u32, 4, 3>;
+///
+/// assert_eq!(Mode::MASK, 0x70);
+/// assert_eq!(Mode::SHIFT, 4);
+/// assert_eq!(Mode::get(0x1234_5678), 7);
+///
+/// let mut reg = 0u32;
+/// Mode::set(&mut reg, 5);
+/// assert_eq!(reg, 0x50);
+/// ```
+pub struct BitField<T, const START: u32, const LEN: u32>(PhantomData<T>);
+
+/// Access to ranges of bits that are known at compile time.
+///
+/// The range is given as a start and a length, like for [`BitField`], and is
+/// checked at compile time. The mask and shift are constants, so no bounds
+/// are checked at runtime, even in debug builds. Only setting bits checks
+/// whether the new value fits.
+///
+/// # Example
+///
+/// ```
+/// use intbits::ConstBits;
+///
+/// assert_eq!(0x1234_5678u32.const_bits::<4, 8>(), 0x67);
+/// assert_eq!(0u16.with_const_bits::<12, 4>(0xA), 0xA000);
+///
+/// let mut reg = 0xFFu8;
+/// reg.set_const_bits::<0, 4>(0);
+/// assert_eq!(reg, 0xF0);
+/// ```
+pub trait ConstBits: Bits {
+	/// Get the `LEN` bits starting at bit `START`.
+	///
+	/// See [`Bits::bits`].
+	fn const_bits<const START: u32, const LEN: u32>(self) -> Self::Bits;
+
+	/// Set the `LEN` bits starting at bit `START`.
+	///
+	/// Panics when the bits of `bits` that don't fit in the range are not 0.
+	///
+	/// See [`Bits::set_bits`].
+	fn set_const_bits<const START: u32, const LEN: u32>(&mut self, bits: Self::Bits);
+
+	/// Get a new integer with the `LEN` bits starting at bit `START` set to
+	/// specific values.
+	///
+	/// Panics when the bits of `bits` that don't fit in the range are not 0.
+	///
+	/// See [`Bits::with_bits`].
+	fn with_const_bits<const START: u32, const LEN: u32>(self, bits: Self::Bits) -> Self;
+}
+
+/// An integer that is compared and hashed by only one of its fields.
+///
+/// `Eq`, `Ord` and `Hash` only consider the `LEN` bits starting at bit
+/// `START`, as selected by the [`BitField`] with the same parameters. This
+/// makes it possible to sort, deduplicate or look up packed records by one of
+/// their fields, without extracting the keys first.
+///
+/// # Example
+///
+/// ```
+/// use intbits::FieldKey;
+///
+/// // Sort by the middle byte, and remove records with the same middle byte.
+/// let mut records = [0x01_30_FFu32, 0x02_10_00, 0x03_20_00, 0x04_10_AA];
+/// records.sort_by_key(|&r| FieldKey::<u32, 8, 8>(r));
+/// assert_eq!(records, [0x02_10_00, 0x04_10_AA, 0x03_20_00, 0x01_30_FF]);
+///
+/// let mut keys: Vec<_> = records.iter().map(|&r| FieldKey::<u32, 8, 8>(r)).collect();
+/// keys.dedup();
+/// assert_eq!(keys.len(), 3);
+/// assert_eq!(keys[0].key(), 0x10);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FieldKey<T, const START: u32, const LEN: u32>(pub T);
+
+/// A field of an integer of type `T`, with a range that is only known at
+/// runtime.
+///
+/// The range is checked and turned into a mask and shift once, when the
+/// `FieldSpec` is created. Using the same field many times is then as cheap as
+/// using a [`BitField`], and doesn't check any bounds.
+///
+/// With the `serde` feature, this is serialized as its range of bits, which
+/// is checked when deserializing.
+///
+/// # Example
+///
+/// ```
+/// use intbits::FieldSpec;
+///
+/// let channel = FieldSpec::<u16>::new(4..12);
+/// assert_eq!(channel.mask(), 0x0FF0);
+///
+/// let mut records = [0x1234u16, 0xABCD];
+/// for r in &mut records {
+///     let old = channel.replace(r, 0x5A);
+///     assert!(old == 0x23 || old == 0xBC);
+/// }
+/// assert_eq!(records, [0x15A4, 0xA5AD]);
+/// assert_eq!(channel.get(records[0]), 0x5A);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldSpec<T: Bits> {
+	mask: T::Bits,
+	shift: u32,
+}
+
+macro_rules! bitfield {
+	($($t:tt $ut:tt),*) => {$(
+		impl<const START: u32, const LEN: u32> BitField<$t, START, LEN> {
+			/// The index of the lowest bit of this field.
+			pub const SHIFT: u32 = START;
+
+			/// The number of bits in this field.
+			pub const LEN: u32 = LEN;
+
+			/// The bits of this field, at their position in the integer.
+			pub const MASK: $ut = {
+				assert!(
+					START <= $t::BITS && LEN <= $t::BITS - START,
+					"bit field out of range"
+				);
+				if LEN == 0 {
+					0
+				} else {
+					!0 >> ($t::BITS - LEN) << START
+				}
+			};
+
+			/// Get the value of this field.
+			///
+			/// The bits are returned in the least significant bits of the return
+			/// value.
+			#[inline]
+			pub const fn get(value: $t) -> $ut {
+				(value as $ut & Self::MASK).wrapping_shr(START)
+			}
+
+			/// Set the value of this field.
+			///
+			/// Panics when the bits of `bits` that don't fit in the field are not 0.
+			#[inline]
+			pub fn set(value: &mut $t, bits: $ut) {
+				*value = Self::with(*value, bits);
+			}
+
+			/// Get a new integer with this field set to a specific value.
+			///
+			/// Panics when the bits of `bits` that don't fit in the field are not 0.
+			#[inline]
+			pub const fn with(value: $t, bits: $ut) -> $t {
+				if bits & !Self::MASK.wrapping_shr(START) != 0 {
+					panic!("bits outside range");
+				}
+				(value as $ut & !Self::MASK | bits.wrapping_shl(START)) as $t
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl<const START: u32, const LEN: u32> serde::Serialize for BitField<$t, START, LEN> {
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serde::Serialize::serialize(&(START..START + LEN), serializer)
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl<'de, const START: u32, const LEN: u32> serde::Deserialize<'de>
+			for BitField<$t, START, LEN>
+		{
+			fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				let range: Range<u32> = serde::Deserialize::deserialize(deserializer)?;
+				if range != (START..START + LEN) {
+					return Err(serde::de::Error::custom("bit range doesn't match the field"));
+				}
+				Ok(Self(PhantomData))
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl serde::Serialize for FieldSpec<$t> {
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serde::Serialize::serialize(&(self.shift..self.shift + self.len()), serializer)
+			}
+		}
+
+		#[cfg(feature = "serde")]
+		impl<'de> serde::Deserialize<'de> for FieldSpec<$t> {
+			fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				let range: Range<u32> = serde::Deserialize::deserialize(deserializer)?;
+				if range.start > range.end || range.end > $t::BITS {
+					return Err(serde::de::Error::custom("invalid bit range"));
+				}
+				Ok(Self::new(range))
+			}
+		}
+
+		impl FieldSpec<$t> {
+			/// Create a field from a range of bits.
+			///
+			/// Panics when the range bounds are out of range.
+			#[inline]
+			pub fn new<R: RangeBounds<u32>>(range: R) -> Self {
+				let (start, end) = bounds(&range, $t::BITS);
+				let len = end - start;
+				let mask = if len == 0 { 0 } else { !0 >> ($t::BITS - len) << start };
+				Self { mask, shift: start }
+			}
+
+			/// The bits of this field, at their position in the integer.
+			#[inline]
+			pub const fn mask(self) -> $ut {
+				self.mask
+			}
+
+			/// The index of the lowest bit of this field.
+			#[inline]
+			pub const fn shift(self) -> u32 {
+				self.shift
+			}
+
+			/// The number of bits in this field.
+			#[inline]
+			pub const fn len(self) -> u32 {
+				self.mask.count_ones()
+			}
+
+			/// Check if this field has no bits.
+			#[inline]
+			pub const fn is_empty(self) -> bool {
+				self.mask == 0
+			}
+
+			/// Get the value of this field.
+			///
+			/// The bits are returned in the least significant bits of the return
+			/// value.
+			#[inline]
+			pub const fn get(self, value: $t) -> $ut {
+				(value as $ut & self.mask).wrapping_shr(self.shift)
+			}
+
+			/// Set the value of this field.
+			///
+			/// Panics when the bits of `bits` that don't fit in the field are not 0.
+			#[inline]
+			pub fn set(self, value: &mut $t, bits: $ut) {
+				*value = self.with(*value, bits);
+			}
+
+			/// Get a new integer with this field set to a specific value.
+			///
+			/// Panics when the bits of `bits` that don't fit in the field are not 0.
+			#[inline]
+			pub fn with(self, value: $t, bits: $ut) -> $t {
+				check!(bits & !self.mask.wrapping_shr(self.shift) == 0, bits_outside_range);
+				(value as $ut & !self.mask | bits.wrapping_shl(self.shift)) as $t
+			}
+
+			/// Set the value of this field, and return its previous value.
+			///
+			/// Panics when the bits of `bits` that don't fit in the field are not 0.
+			#[inline]
+			pub fn replace(self, value: &mut $t, bits: $ut) -> $ut {
+				let old = self.get(*value);
+				self.set(value, bits);
+				old
+			}
+		}
+
+		impl<const START: u32, const LEN: u32> FieldKey<$t, START, LEN> {
+			/// Get the value of the field that is used as the key.
+			#[inline]
+			pub const fn key(self) -> $ut {
+				BitField::<$t, START, LEN>::get(self.0)
+			}
+		}
+
+		impl<const START: u32, const LEN: u32> PartialEq for FieldKey<$t, START, LEN> {
+			#[inline]
+			fn eq(&self, other: &Self) -> bool {
+				self.key() == other.key()
+			}
+		}
+
+		impl<const START: u32, const LEN: u32> Eq for FieldKey<$t, START, LEN> {}
+
+		impl<const START: u32, const LEN: u32> PartialOrd for FieldKey<$t, START, LEN> {
+			#[inline]
+			fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+				Some(self.cmp(other))
+			}
+		}
+
+		impl<const START: u32, const LEN: u32> Ord for FieldKey<$t, START, LEN> {
+			#[inline]
+			fn cmp(&self, other: &Self) -> Ordering {
+				self.key().cmp(&other.key())
+			}
+		}
+
+		impl<const START: u32, const LEN: u32> Hash for FieldKey<$t, START, LEN> {
+			#[inline]
+			fn hash<H: Hasher>(&self, state: &mut H) {
+				self.key().hash(state)
+			}
+		}
+
+		impl ConstBits for $t {
+			#[inline]
+			fn const_bits<const START: u32, const LEN: u32>(self) -> $ut {
+				BitField::<$t, START, LEN>::get(self)
+			}
+
+			#[inline]
+			fn set_const_bits<const START: u32, const LEN: u32>(&mut self, bits: $ut) {
+				BitField::<$t, START, LEN>::set(self, bits)
+			}
+
+			#[inline]
+			fn with_const_bits<const START: u32, const LEN: u32>(self, bits: $ut) -> $t {
+				BitField::<$t, START, LEN>::with(self, bits)
+			}
+		}
+	)*};
+}
+
+bitfield!(u8 u8, u16 u16, u32 u32, u64 u64, usize usize);
+#[cfg(feature = "impl-signed")]
+bitfield!(i8 u8, i16 u16, i32 u32, i64 u64, isize usize);
+#[cfg(feature = "impl-128")]
+bitfield!(u128 u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+bitfield!(i128 u128);
@@ -0,0 +1,146 @@
+use super::{Bits, BitsIndex};
+use core::ops::{Range, RangeBounds};
+#[cfg(feature = "impl-128")]
+use fixed::types::extra::LeEqU128;
+use fixed::types::extra::{LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+use fixed::FixedI128;
+#[cfg(feature = "impl-128")]
+use fixed::FixedU128;
+#[cfg(feature = "impl-signed")]
+use fixed::{FixedI16, FixedI32, FixedI64, FixedI8};
+use fixed::{FixedU16, FixedU32, FixedU64, FixedU8};
+
+/// Access to the integer and fractional parts of a fixed-point number from
+/// the `fixed` crate.
+///
+/// The fixed-point types implement [`Bits`] by accessing their underlying
+/// bits, as returned by `to_bits()`. This trait names the ranges of bits that
+/// form the integer and fractional parts. For signed types, the integer part
+/// includes the sign bit.
+///
+/// This is only available with the `fixed` feature.
+///
+/// # Example
+///
+/// ```
+/// use fixed::types::U4F4;
+/// use intbits::{Bits, FixedBits};
+///
+/// let mut x = U4F4::from_num(5.25);
+/// assert_eq!(U4F4::frac_range(), 0..4);
+/// assert_eq!(x.int_bits(), 5);
+/// assert_eq!(x.frac_bits(), 0b0100);
+/// x.set_int_bits(9);
+/// assert_eq!(x, 9.25);
+/// assert_eq!(x.bits(2..6), 0b0101);
+/// ```
+pub trait FixedBits: Bits {
+	/// The range of bits of the integer part.
+	fn int_range() -> Range<u32>;
+
+	/// The range of bits of the fractional part.
+	fn frac_range() -> Range<u32>;
+
+	/// Get the bits of the integer part.
+	fn int_bits(self) -> Self::Bits;
+
+	/// Get the bits of the fractional part.
+	fn frac_bits(self) -> Self::Bits;
+
+	/// Set the bits of the integer part.
+	///
+	/// Panics if `bits` doesn't fit in the integer part.
+	fn set_int_bits(&mut self, bits: Self::Bits);
+
+	/// Set the bits of the fractional part.
+	///
+	/// Panics if `bits` doesn't fit in the fractional part.
+	fn set_frac_bits(&mut self, bits: Self::Bits);
+}
+
+macro_rules! fixed_bits {
+	($($f:ident $le:ident $t:ident $ut:ident),*) => {$(
+		forward_bits!(
+			[Frac: $le] $f<Frac>,
+			$ut,
+			<$t as Bits>::N_BITS,
+			|v| v.swap_bytes(),
+			$f::from_bits(!0)
+		);
+
+		impl<Frac: $le, I: BitsIndex<$t>> BitsIndex<$f<Frac>> for I {
+			#[inline]
+			fn bit(v: $f<Frac>, i: Self) -> bool {
+				<I as BitsIndex<$t>>::bit(v.to_bits(), i)
+			}
+
+			#[inline]
+			fn bits<R>(v: $f<Frac>, range: R) -> <$f<Frac> as Bits>::Bits
+			where
+				R: RangeBounds<Self>,
+			{
+				<I as BitsIndex<$t>>::bits(v.to_bits(), range)
+			}
+
+			#[inline]
+			fn set_bit(v: &mut $f<Frac>, i: Self, bit: bool) {
+				*v = $f::from_bits(v.to_bits().with_bit(i, bit));
+			}
+
+			#[inline]
+			fn set_bits<R>(v: &mut $f<Frac>, range: R, bits: <$f<Frac> as Bits>::Bits)
+			where
+				R: RangeBounds<Self>,
+			{
+				*v = $f::from_bits(v.to_bits().with_bits(range, bits));
+			}
+		}
+
+		impl<Frac: $le> FixedBits for $f<Frac> {
+			#[inline]
+			fn int_range() -> Range<u32> {
+				Self::FRAC_NBITS..<$t as Bits>::N_BITS
+			}
+
+			#[inline]
+			fn frac_range() -> Range<u32> {
+				0..Self::FRAC_NBITS
+			}
+
+			#[inline]
+			fn int_bits(self) -> $ut {
+				self.bits(Self::int_range())
+			}
+
+			#[inline]
+			fn frac_bits(self) -> $ut {
+				self.bits(Self::frac_range())
+			}
+
+			#[inline]
+			fn set_int_bits(&mut self, bits: $ut) {
+				self.set_bits(Self::int_range(), bits)
+			}
+
+			#[inline]
+			fn set_frac_bits(&mut self, bits: $ut) {
+				self.set_bits(Self::frac_range(), bits)
+			}
+		}
+	)*};
+}
+
+fixed_bits!(
+	FixedU8 LeEqU8 u8 u8, FixedU16 LeEqU16 u16 u16,
+	FixedU32 LeEqU32 u32 u32, FixedU64 LeEqU64 u64 u64
+);
+#[cfg(feature = "impl-signed")]
+fixed_bits!(
+	FixedI8 LeEqU8 i8 u8, FixedI16 LeEqU16 i16 u16,
+	FixedI32 LeEqU32 i32 u32, FixedI64 LeEqU64 i64 u64
+);
+#[cfg(feature = "impl-128")]
+fixed_bits!(FixedU128 LeEqU128 u128 u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fixed_bits!(FixedI128 LeEqU128 i128 u128);
@@ -0,0 +1,14 @@
+/// What [`Bits::bits_as_flags`][crate::Bits::bits_as_flags] does with bits
+/// that don't correspond to a defined flag.
+///
+/// This is only available with the `bitflags` feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UnknownBits {
+	/// Return an error if any unknown bit is set.
+	Reject,
+	/// Ignore unknown bits, like `Flags::from_bits_truncate`.
+	Truncate,
+	/// Keep unknown bits, like `Flags::from_bits_retain`.
+	Retain,
+}
@@ -0,0 +1,205 @@
+use super::range::bounds;
+use super::{Bits, BitsIndex};
+use core::fmt::{self, Write};
+use core::ops::RangeBounds;
+
+/// Formats a value in binary, with all of its bits and a separator between
+/// groups of bits.
+///
+/// Unlike `{:b}`, this always prints all [`N_BITS`][Bits::N_BITS] bits of the
+/// type, including leading zeros. By default, bits are grouped per four and
+/// separated by `_`. Use [`group`][Bin::group] to change that. The alternate
+/// flag (`{:#}`) adds a `0b` prefix.
+///
+/// # Example
+///
+/// ```
+/// use intbits::Bin;
+///
+/// assert_eq!(format!("{}", Bin::new(0xACu8)), "1010_1100");
+/// assert_eq!(format!("{:#}", Bin::new(0x0Cu8)), "0b0000_1100");
+/// assert_eq!(format!("{}", Bin::new(0xAC10u16).group(8, ' ')), "10101100 00010000");
+/// assert_eq!(format!("{}", Bin::new(0xFEu8).group(0, ' ')), "11111110");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Bin<T> {
+	value: T,
+	group: u32,
+	separator: char,
+}
+
+impl<T> Bin<T> {
+	/// Wrap a value, to format it in groups of four bits separated by `_`.
+	#[inline]
+	pub const fn new(value: T) -> Self {
+		Self {
+			value,
+			group: 4,
+			separator: '_',
+		}
+	}
+
+	/// Use groups of `size` bits, separated by `separator`.
+	///
+	/// A size of 0 disables grouping.
+	#[inline]
+	pub fn group(mut self, size: u32, separator: char) -> Self {
+		self.group = size;
+		self.separator = separator;
+		self
+	}
+
+	/// Get the wrapped value.
+	#[inline]
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<T> From<T> for Bin<T> {
+	#[inline]
+	fn from(value: T) -> Self {
+		Self::new(value)
+	}
+}
+
+impl<T> fmt::Display for Bin<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if f.alternate() {
+			f.write_str("0b")?;
+		}
+		for i in T::BIT_INDICES.rev() {
+			f.write_char(if self.value.bit(i) { '1' } else { '0' })?;
+			if i != 0 && self.group != 0 && i % self.group == 0 {
+				f.write_char(self.separator)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<T> fmt::Debug for Bin<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format> defmt::Format for Bin<T> {
+	fn format(&self, f: defmt::Formatter) {
+		defmt::write!(f, "{=?:#b}", self.value);
+	}
+}
+
+/// Formats a value as the set of indices of its bits that are 1.
+///
+/// This is useful for flag words that have only a few bits set.
+///
+/// # Example
+///
+/// ```
+/// use intbits::SetBits;
+///
+/// assert_eq!(format!("{:?}", SetBits(0x2_0009u64)), "{0, 3, 17}");
+/// assert_eq!(format!("{:?}", SetBits(0u8)), "{}");
+/// ```
+#[derive(Clone, Copy)]
+pub struct SetBits<T>(pub T);
+
+impl<T> fmt::Debug for SetBits<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_set()
+			.entries(T::BIT_INDICES.filter(|&i| self.0.bit(i)))
+			.finish()
+	}
+}
+
+/// Formats a value in hexadecimal and binary, with a range of bits bracketed,
+/// followed by the value of that range.
+///
+/// # Example
+///
+/// ```
+/// use intbits::FieldDump;
+///
+/// assert_eq!(
+///     FieldDump::new(0x4A3Fu16, 5..10).to_string(),
+///     "0x4A3F = 0100 10[10 001]1 1111 (bits 5..10 = 0b10001 = 17)"
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct FieldDump<T> {
+	value: T,
+	start: u32,
+	end: u32,
+}
+
+impl<T: Bits> FieldDump<T> {
+	/// Create a dump of `value`, highlighting the bits in `range`.
+	///
+	/// Panics if the range is out of bounds.
+	pub fn new<R: RangeBounds<u32>>(value: T, range: R) -> Self {
+		let (start, end) = bounds(&range, T::N_BITS);
+		Self { value, start, end }
+	}
+}
+
+impl<T> fmt::Display for FieldDump<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let n = T::N_BITS;
+		f.write_str("0x")?;
+		for i in (0..n.div_ceil(4)).rev() {
+			let nibble = self.value.bits(i * 4..(i * 4 + 4).min(n));
+			write!(f, "{:X}", nibble)?;
+		}
+		f.write_str(" = ")?;
+		for i in (0..n).rev() {
+			if i + 1 == self.end && self.start != self.end {
+				f.write_char('[')?;
+			}
+			f.write_char(if self.value.bit(i) { '1' } else { '0' })?;
+			if i == self.start && self.start != self.end {
+				f.write_char(']')?;
+			}
+			if i != 0 && i % 4 == 0 {
+				f.write_char(' ')?;
+			}
+		}
+		let field = self.value.bits(self.start..self.end);
+		write!(
+			f,
+			" (bits {}..{} = {:#0w$b} = {})",
+			self.start,
+			self.end,
+			field,
+			field,
+			w = (self.end - self.start) as usize + 2
+		)
+	}
+}
+
+impl<T> fmt::Debug for FieldDump<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
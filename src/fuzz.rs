@@ -0,0 +1,118 @@
+#[cfg(feature = "impl-128")]
+use super::WideInt;
+use super::{FixedBitSet, IntSet, U};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use core::ops::Range;
+
+/// Generate an arbitrary bit index below `n_bits`.
+///
+/// Panics if `n_bits` is 0.
+///
+/// This is only available with the `arbitrary` feature.
+///
+/// # Example
+///
+/// ```
+/// use arbitrary::Unstructured;
+/// use intbits::arbitrary_bit_index;
+///
+/// let mut u = Unstructured::new(&[0xFF; 16]);
+/// assert!(arbitrary_bit_index(&mut u, 12).unwrap() < 12);
+/// ```
+pub fn arbitrary_bit_index(u: &mut Unstructured, n_bits: u32) -> Result<u32> {
+	check!(n_bits > 0, invalid_bit_width);
+	u.int_in_range(0..=n_bits - 1)
+}
+
+/// Generate an arbitrary range of bits within an integer of `n_bits` bits.
+///
+/// The range is never inverted, and may be empty.
+///
+/// This is only available with the `arbitrary` feature.
+///
+/// # Example
+///
+/// ```
+/// use arbitrary::Unstructured;
+/// use intbits::{arbitrary_bit_range, Bits};
+///
+/// let mut u = Unstructured::new(&[0x12, 0x34, 0x56, 0x78]);
+/// let range = arbitrary_bit_range(&mut u, 32).unwrap();
+/// assert!(range.start <= range.end && range.end <= 32);
+/// let _ = 0xABCDu32.bits(range);
+/// ```
+pub fn arbitrary_bit_range(u: &mut Unstructured, n_bits: u32) -> Result<Range<u32>> {
+	let start = u.int_in_range(0..=n_bits)?;
+	let end = u.int_in_range(start..=n_bits)?;
+	Ok(start..end)
+}
+
+macro_rules! fuzz {
+	($($t:ident),*) => {$(
+		impl<'a> Arbitrary<'a> for IntSet<$t> {
+			#[inline]
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+				$t::arbitrary(u).map(IntSet)
+			}
+
+			#[inline]
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				$t::size_hint(depth)
+			}
+		}
+
+		/// Generates values of `N` bits, by ignoring the higher bits.
+		impl<'a, const N: u32> Arbitrary<'a> for U<$t, N> {
+			#[inline]
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+				Ok(Self::new($t::arbitrary(u)? & Self::MAX.get()))
+			}
+
+			#[inline]
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				$t::size_hint(depth)
+			}
+		}
+	)*};
+}
+
+fuzz!(u8, u16, u32, u64);
+#[cfg(feature = "impl-128")]
+fuzz!(u128);
+
+impl<'a> Arbitrary<'a> for IntSet<usize> {
+	#[inline]
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		usize::arbitrary(u).map(IntSet)
+	}
+
+	#[inline]
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		usize::size_hint(depth)
+	}
+}
+
+impl<'a, const WORDS: usize> Arbitrary<'a> for FixedBitSet<WORDS> {
+	#[inline]
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		<[u64; WORDS]>::arbitrary(u).map(Self::from_words)
+	}
+
+	#[inline]
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		<[u64; WORDS]>::size_hint(depth)
+	}
+}
+
+#[cfg(feature = "impl-128")]
+impl<'a, const WORDS: usize> Arbitrary<'a> for WideInt<WORDS> {
+	#[inline]
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		<[u64; WORDS]>::arbitrary(u).map(Self::from_words)
+	}
+
+	#[inline]
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		<[u64; WORDS]>::size_hint(depth)
+	}
+}
@@ -0,0 +1,50 @@
+// Building blocks for the provided methods of `Bits`, written only in terms of
+// the required methods. These are the fallbacks for implementations outside
+// this crate. The types in this crate override them with faster versions.
+//
+// They access the whole value as `T::Bits`, so they only work for types of up
+// to 128 bits.
+
+use crate::{Bits, BitsIndex};
+use core::ops::RangeFull;
+
+/// Reverse the order of the bytes of a value.
+///
+/// Like `swap_bytes` on the narrow integers, this swaps the bytes of
+/// `T::Bits`, and then shifts the result down to the `N_BITS` of `T`.
+pub(crate) fn swap_bytes<T: Bits, I: BitsIndex<T>>(v: T) -> T {
+	let n = <T::Bits as Bits>::N_BITS;
+	let (byte, all) = (T::Bits::from(0xFF), v.bits::<I, RangeFull>(..));
+	let swapped = (0..n / 8).fold(T::Bits::default(), |s, k| {
+		s | ((all >> (8 * k)) & byte) << (n - 8 - 8 * k)
+	});
+	let mut r = v;
+	r.set_bits::<I, RangeFull>(.., swapped >> (n - T::N_BITS));
+	r
+}
+
+/// The value with all bits set.
+pub(crate) fn ones<T: Bits, I: BitsIndex<T>>(mut v: T) -> T {
+	let n = <T::Bits as Bits>::N_BITS;
+	v.set_bits::<I, RangeFull>(.., !T::Bits::default() >> (n - T::N_BITS));
+	v
+}
+
+/// Convert bits to a `u128`, one bit at a time.
+pub(crate) fn to_u128<T: Bits>(bits: T::Bits) -> u128 {
+	let one = T::Bits::from(1);
+	(0..<T::Bits as Bits>::N_BITS)
+		.filter(|&i| (bits >> i) & one != T::Bits::default())
+		.fold(0, |r, i| r | 1 << i)
+}
+
+/// A field and the same field of [`ones`], which gives its width.
+pub(crate) fn field<T, I, R>(v: T, range: R) -> (u128, u128)
+where
+	T: Bits,
+	I: BitsIndex<T>,
+	R: core::ops::RangeBounds<I> + Clone,
+{
+	let ones = ones::<T, I>(v).bits(range.clone());
+	(to_u128::<T>(v.bits(range)), to_u128::<T>(ones))
+}
@@ -1,171 +1,203 @@
+use super::range::{bounds, u32_bounds};
 use super::{Bits, BitsIndex};
-use core::ops::Bound::{Excluded, Included, Unbounded};
-use core::ops::{Bound, RangeBounds};
+use core::convert::TryFrom;
+use core::ops::RangeBounds;
 
-trait BitRange<T>: Bits {
-	fn mask(end: Bound<&T>) -> Self::Bits;
-	fn shift(start: Bound<&T>) -> Option<T>;
+// The lowest `width` bits set, for `width` in `0..=BITS`.
+//
+// The shift wraps around for a width of 0, and the result is then cleared
+// without a branch.
+macro_rules! mask {
+	($ut:ty, $width:expr) => {{
+		let width: u32 = $width;
+		(!0 as $ut).wrapping_shr(<$ut>::BITS - width) & ((width != 0) as $ut).wrapping_neg()
+	}};
 }
 
+// The implementation for `u32` indices does the actual work. All other index
+// types convert their indices to `u32` first, such that they only add a thin
+// conversion on top.
 macro_rules! bits {
-	($t:tt, $ut:tt, $n:tt, $i:tt) => {
-		#[allow(unused_comparisons)]
-		impl BitRange<$i> for $t {
-			#[inline]
-			fn mask(end: Bound<&$i>) -> $ut {
-				match end {
-					Unbounded => !0,
-					Excluded(&i) if i > $n && i - 1 == $n => !0,
-					Excluded(&i) if i <= $n && i >= 0 => !0 >> 1 >> ($n - i),
-					Included(&i) if i < 0 && i + 1 == 0 => 0,
-					Included(&i) if i <= $n && i >= 0 => !0 >> ($n - i),
-					_ => panic!("invalid bit range"),
-				}
-			}
-
-			#[inline]
-			fn shift(start: Bound<&$i>) -> Option<$i> {
-				match start {
-					Unbounded => Some(0),
-					Included(&i) if i > $n && i - 1 == $n => None,
-					Included(&i) if i <= $n && i >= 0 => Some(i),
-					Excluded(&i) if i == $n => None,
-					Excluded(&i) if i < $n && i + 1 >= 0 => Some(i + 1),
-					_ => panic!("invalid bit range"),
-				}
-			}
-		}
+	($t:tt, $ut:tt) => {
+		forward_bits!([] $t, $ut, $t::BITS, |v| v.swap_bytes(), !0);
 
-		#[allow(unused_comparisons)]
-		impl BitsIndex<$t> for $i {
+		impl BitsIndex<$t> for u32 {
 			#[inline]
-			fn bit(v: $t, i: Self) -> bool {
-				assert!(i >= 0 && i <= $n, "invalid bit index");
+			fn bit(v: $t, i: u32) -> bool {
+				check!(i < $t::BITS, invalid_bit_index);
 				v >> i & 1 != 0
 			}
 
 			#[inline]
 			fn bits<R>(v: $t, range: R) -> $ut
 			where
-				R: RangeBounds<Self>,
+				R: RangeBounds<u32>,
 			{
-				let mask = $t::mask(range.end_bound());
-				if let Some(shift) = $t::shift(range.start_bound()) {
-					(v as $ut & mask) >> shift
-				} else {
-					0
-				}
+				let (start, end) = bounds(&range, $t::BITS);
+				(v as $ut).wrapping_shr(start) & mask!($ut, end - start)
 			}
 
 			#[inline]
-			fn set_bit(v: &mut $t, i: Self, bit: bool) {
-				assert!(i >= 0 && i <= $n, "invalid bit index");
+			fn set_bit(v: &mut $t, i: u32, bit: bool) {
+				check!(i < $t::BITS, invalid_bit_index);
 				*v = *v & !(1 << i) | (bit as $t) << i;
 			}
 
 			#[inline]
 			fn set_bits<R>(v: &mut $t, range: R, bits: $ut)
 			where
-				R: RangeBounds<Self>,
+				R: RangeBounds<u32>,
 			{
-				let mask = $t::mask(range.end_bound());
-				if let Some(shift) = $t::shift(range.start_bound()) {
-					let and_mask = !(mask & !0 << shift);
-					let or_mask = bits << shift;
-					if or_mask & and_mask != 0 {
-						panic!("bits outside range");
-					}
-					*v = *v & and_mask as $t | or_mask as $t;
-				}
+				let (start, end) = bounds(&range, $t::BITS);
+				let mask = mask!($ut, end - start);
+				check!(bits & !mask == 0, bits_outside_range);
+				*v = (*v as $ut & !mask.wrapping_shl(start) | bits.wrapping_shl(start)) as $t;
 			}
 		}
+
+		bits!(@index $t, $ut: u8 u16 u64 usize i32);
+		#[cfg(feature = "impl-signed")]
+		bits!(@index $t, $ut: i8 i16 i64 isize);
+		#[cfg(feature = "impl-128")]
+		bits!(@index $t, $ut: u128);
+		#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+		bits!(@index $t, $ut: i128);
 	};
-	($t:tt, $ut:tt, $n:tt) => {
-		impl Bits for $t {
-			type Bits = $ut;
-			const N_BITS: u32 = $n + 1;
-			#[inline]
-			fn bit<I>(self, i: I) -> bool
-			where
-				I: BitsIndex<Self>,
-			{
-				I::bit(self, i)
-			}
-			#[inline]
-			fn bits<I, R>(self, range: R) -> $ut
-			where
-				I: BitsIndex<Self>,
-				R: RangeBounds<I>,
-			{
-				I::bits(self, range)
-			}
+	(@index $t:tt, $ut:tt: $($i:ident)*) => {$(
+		impl BitsIndex<$t> for $i {
 			#[inline]
-			fn set_bit<I>(&mut self, i: I, bit: bool)
-			where
-				I: BitsIndex<Self>,
-			{
-				I::set_bit(self, i, bit)
+			fn bit(v: $t, i: Self) -> bool {
+				<u32 as BitsIndex<$t>>::bit(v, u32::try_from(i).unwrap_or(u32::MAX))
 			}
+
 			#[inline]
-			fn set_bits<I, R>(&mut self, range: R, bits: $ut)
+			fn bits<R>(v: $t, range: R) -> $ut
 			where
-				I: BitsIndex<Self>,
-				R: RangeBounds<I>,
+				R: RangeBounds<Self>,
 			{
-				I::set_bits(self, range, bits)
+				<u32 as BitsIndex<$t>>::bits(v, u32_bounds(&range))
 			}
+
 			#[inline]
-			fn with_bit<I>(mut self, i: I, bit: bool) -> Self
-			where
-				I: BitsIndex<Self>,
-			{
-				I::set_bit(&mut self, i, bit);
-				self
+			fn set_bit(v: &mut $t, i: Self, bit: bool) {
+				<u32 as BitsIndex<$t>>::set_bit(v, u32::try_from(i).unwrap_or(u32::MAX), bit)
 			}
+
 			#[inline]
-			fn with_bits<I, R>(mut self, range: R, bits: $ut) -> Self
+			fn set_bits<R>(v: &mut $t, range: R, bits: $ut)
 			where
-				I: BitsIndex<Self>,
-				R: RangeBounds<I>,
+				R: RangeBounds<Self>,
 			{
-				I::set_bits(&mut self, range, bits);
-				self
+				<u32 as BitsIndex<$t>>::set_bits(v, u32_bounds(&range), bits)
 			}
 		}
-		bits!($t, $ut, $n, i8);
-		bits!($t, $ut, $n, u8);
-		bits!($t, $ut, $n, i16);
-		bits!($t, $ut, $n, u16);
-		bits!($t, $ut, $n, i32);
-		bits!($t, $ut, $n, u32);
-		bits!($t, $ut, $n, i64);
-		bits!($t, $ut, $n, u64);
-		bits!($t, $ut, $n, i128);
-		bits!($t, $ut, $n, u128);
-		bits!($t, $ut, $n, isize);
-		bits!($t, $ut, $n, usize);
+	)*};
+}
+
+bits!(u8, u8);
+bits!(u16, u16);
+bits!(u32, u32);
+bits!(u64, u64);
+bits!(usize, usize);
+
+#[cfg(feature = "impl-signed")]
+mod signed {
+	use super::*;
+	bits!(i8, u8);
+	bits!(i16, u16);
+	bits!(i32, u32);
+	bits!(i64, u64);
+	bits!(isize, usize);
+}
+
+#[cfg(feature = "impl-128")]
+mod wide {
+	use super::*;
+	bits!(u128, u128);
+}
+
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+mod wide_signed {
+	use super::*;
+	bits!(i128, u128);
+}
+
+// The types that implement `Bits` and `BitsIndex` with the enabled features,
+// for macros that are expanded in other crates, where `cfg` would check the
+// features of that crate instead. They invoke `$m!` with the given tokens,
+// followed by the list of types.
+
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_int_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize i8 i16 i32 i64 isize u128 i128 }
+	};
+}
+
+#[cfg(all(feature = "impl-128", not(feature = "impl-signed")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_int_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize u128 }
 	};
 }
 
-bits!(i8, u8, 7);
-bits!(u8, u8, 7);
-bits!(i16, u16, 15);
-bits!(u16, u16, 15);
-bits!(i32, u32, 31);
-bits!(u32, u32, 31);
-bits!(i64, u64, 63);
-bits!(u64, u64, 63);
-bits!(i128, u128, 127);
-bits!(u128, u128, 127);
+#[cfg(all(not(feature = "impl-128"), feature = "impl-signed"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_int_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+	};
+}
 
-#[cfg(target_pointer_width = "32")]
-bits!(isize, usize, 31);
+#[cfg(all(not(feature = "impl-128"), not(feature = "impl-signed")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_int_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize }
+	};
+}
 
-#[cfg(target_pointer_width = "32")]
-bits!(usize, usize, 31);
+// Like `__for_int_types`, but for the index types, which include `i32` even
+// without `impl-signed`.
 
-#[cfg(target_pointer_width = "64")]
-bits!(isize, usize, 63);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_index_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize i8 i16 i32 i64 isize u128 i128 }
+	};
+}
 
-#[cfg(target_pointer_width = "64")]
-bits!(usize, usize, 63);
+#[cfg(all(feature = "impl-128", not(feature = "impl-signed")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_index_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize i32 u128 }
+	};
+}
+
+#[cfg(all(not(feature = "impl-128"), feature = "impl-signed"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_index_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+	};
+}
+
+#[cfg(all(not(feature = "impl-128"), not(feature = "impl-signed")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_index_types {
+	($m:path; $($args:tt)*) => {
+		$m! { $($args)* u8 u16 u32 u64 usize i32 }
+	};
+}
@@ -0,0 +1,112 @@
+use super::range::bounds;
+use core::ops::RangeBounds;
+use funty::Integral;
+
+/// Access to individual bits of any [`funty::Integral`] type.
+///
+/// This offers the same functionality as [`Bits`][crate::Bits], but for code
+/// that is generic over `funty::Integral`, like code using `bitvec`. Indices
+/// are `u32`, and ranges of bits are returned in the same type as the integer
+/// itself.
+///
+/// For concrete types, use [`Bits`][crate::Bits] instead. Having both traits
+/// in scope makes calls on primitive integers ambiguous.
+///
+/// This is only available with the `funty` feature.
+///
+/// # Example
+///
+/// ```
+/// use funty::Integral;
+/// use intbits::IntegralBits;
+///
+/// fn high_nibble<T: Integral>(x: T) -> T {
+///     x.bits(T::BITS - 4..)
+/// }
+///
+/// assert_eq!(high_nibble(0xA0u8), 0xA);
+/// assert_eq!(high_nibble(-1i64), 0xF);
+/// ```
+pub trait IntegralBits: Integral {
+	/// Get a specific bit.
+	///
+	/// See [`Bits::bit`][crate::Bits::bit].
+	fn bit(self, i: u32) -> bool;
+
+	/// Get a range of bits.
+	///
+	/// See [`Bits::bits`][crate::Bits::bits].
+	fn bits<R: RangeBounds<u32>>(self, range: R) -> Self;
+
+	/// Set a specific bit.
+	///
+	/// See [`Bits::set_bit`][crate::Bits::set_bit].
+	fn set_bit(&mut self, i: u32, bit: bool);
+
+	/// Set a range of bits.
+	///
+	/// See [`Bits::set_bits`][crate::Bits::set_bits].
+	fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: Self);
+
+	/// Get a new integer with one bit set to a specific value.
+	///
+	/// See [`Bits::with_bit`][crate::Bits::with_bit].
+	fn with_bit(self, i: u32, bit: bool) -> Self;
+
+	/// Get a new integer with a range of bits set to specific values.
+	///
+	/// See [`Bits::with_bits`][crate::Bits::with_bits].
+	fn with_bits<R: RangeBounds<u32>>(self, range: R, bits: Self) -> Self;
+}
+
+/// The shift and mask (before shifting) of a range of bits.
+#[inline]
+fn shift_mask<T: Integral, R: RangeBounds<u32>>(range: &R) -> (u32, T) {
+	let (start, end) = bounds(range, T::BITS);
+	match end - start {
+		n if n == T::BITS => (0, !T::ZERO),
+		n => (start % T::BITS, (T::ONE << n).wrapping_sub(T::ONE)),
+	}
+}
+
+impl<T: Integral> IntegralBits for T {
+	#[inline]
+	fn bit(self, i: u32) -> bool {
+		check!(i < T::BITS, invalid_bit_index);
+		self >> i & T::ONE != T::ZERO
+	}
+
+	#[inline]
+	fn bits<R: RangeBounds<u32>>(self, range: R) -> Self {
+		let (shift, mask) = shift_mask::<T, R>(&range);
+		self >> shift & mask
+	}
+
+	#[inline]
+	fn set_bit(&mut self, i: u32, bit: bool) {
+		check!(i < T::BITS, invalid_bit_index);
+		let b = T::ONE << i;
+		*self = if bit { *self | b } else { *self & !b };
+	}
+
+	#[inline]
+	fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: Self) {
+		let (shift, mask) = shift_mask::<T, R>(&range);
+		if bits & !mask != T::ZERO {
+			crate::panic::bits_outside_range();
+		}
+		*self = *self & !(mask << shift) | bits << shift;
+	}
+
+	#[inline]
+	fn with_bit(mut self, i: u32, bit: bool) -> Self {
+		self.set_bit(i, bit);
+		self
+	}
+
+	#[inline]
+	fn with_bits<R: RangeBounds<u32>>(mut self, range: R, bits: Self) -> Self {
+		self.set_bits(range, bits);
+		self
+	}
+}
@@ -0,0 +1,261 @@
+use super::{BitOrder, SliceBits};
+use crate::refill::RefillBuffer;
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Reads values of any number of bits sequentially from a [`std::io::Read`]
+/// source.
+///
+/// This is like [`BitReader`][crate::BitReader], but the data is read from the
+/// source into an internal buffer of `N` bytes whenever more bits are needed.
+/// Reading past the end of the source results in an error of kind
+/// [`UnexpectedEof`][ErrorKind::UnexpectedEof].
+///
+/// This is only available with the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, IoBitReader};
+///
+/// let file: &[u8] = &[0xA5, 0xF0];
+/// let mut r = IoBitReader::<_>::new(file, BitOrder::MsbFirst);
+/// assert_eq!(r.read_bits(3).unwrap(), 0b101);
+/// assert_eq!(r.read_bits(7).unwrap(), 0b001_0111);
+/// assert!(r.read_bits(7).is_err());
+/// ```
+#[derive(Debug)]
+pub struct IoBitReader<R, const N: usize = 64> {
+	source: R,
+	buf: RefillBuffer<N>,
+}
+
+impl<R: Read, const N: usize> IoBitReader<R, N> {
+	/// Create a reader that starts at the next byte of `source`.
+	///
+	/// Panics if `N` is less than 9, since a value of 64 bits that doesn't
+	/// start at the start of a byte can span 9 bytes.
+	pub fn new(source: R, order: BitOrder) -> Self {
+		Self {
+			source,
+			buf: RefillBuffer::new(order),
+		}
+	}
+
+	/// The bit order of this reader.
+	pub fn order(&self) -> BitOrder {
+		self.buf.order()
+	}
+
+	/// The number of bits read (or skipped) so far.
+	pub fn position(&self) -> usize {
+		self.buf.position()
+	}
+
+	/// Whether the reader is at the start of a byte.
+	pub fn is_aligned(&self) -> bool {
+		self.buf.is_aligned()
+	}
+
+	/// Return the source.
+	///
+	/// Any data that was read from the source but not yet from this reader is
+	/// lost.
+	pub fn into_inner(self) -> R {
+		self.source
+	}
+
+	/// Make sure the next `n` bits are in the buffer, reading from the source
+	/// as needed.
+	fn fill(&mut self, n: u32) -> io::Result<()> {
+		if self.buf.has(n) {
+			return Ok(());
+		}
+		self.buf.compact();
+		while !self.buf.has(n) {
+			match self.source.read(self.buf.spare()) {
+				Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+				Ok(k) => self.buf.filled(k),
+				Err(e) if e.kind() == ErrorKind::Interrupted => {}
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(())
+	}
+
+	/// Get the next `n` bits without advancing the reader.
+	///
+	/// Panics if `n` is more than 64.
+	pub fn peek_bits(&mut self, n: u32) -> io::Result<u64> {
+		check!(n <= 64, bit_range_too_wide);
+		self.fill(n)?;
+		Ok(self.buf.peek(n))
+	}
+
+	/// Read the next `n` bits.
+	///
+	/// Panics if `n` is more than 64.
+	pub fn read_bits(&mut self, n: u32) -> io::Result<u64> {
+		let bits = self.peek_bits(n)?;
+		self.buf.advance(n as usize);
+		Ok(bits)
+	}
+
+	/// Read a single bit.
+	pub fn read_bit(&mut self) -> io::Result<bool> {
+		self.read_bits(1).map(|b| b != 0)
+	}
+
+	/// Skip the next `n` bits.
+	pub fn skip(&mut self, mut n: usize) -> io::Result<()> {
+		while n > 0 {
+			let k = n.min(64);
+			self.fill(k as u32)?;
+			self.buf.advance(k);
+			n -= k;
+		}
+		Ok(())
+	}
+
+	/// Skip to the start of the next byte, unless already at the start of a
+	/// byte.
+	pub fn align_to_byte(&mut self) -> io::Result<()> {
+		self.skip(self.buf.bits_to_byte())
+	}
+}
+
+/// Writes values of any number of bits sequentially to a [`std::io::Write`]
+/// sink.
+///
+/// This is like [`BitWriter`][crate::BitWriter], but the bits are collected
+/// in an internal buffer of `N` bytes, which is written to the sink whenever
+/// it is full. Call [`finish`][Self::finish] at the end, to write the last
+/// (partial) byte.
+///
+/// This is only available with the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, IoBitWriter};
+///
+/// let mut w = IoBitWriter::<_>::new(Vec::new(), BitOrder::MsbFirst);
+/// w.write_bits(0b101, 3).unwrap();
+/// w.write_bits(0b0010111, 7).unwrap();
+/// assert_eq!(w.finish().unwrap(), [0xA5, 0xC0]);
+/// ```
+#[derive(Debug)]
+pub struct IoBitWriter<W, const N: usize = 64> {
+	sink: W,
+	buf: [u8; N],
+	/// The position in `buf`, in bits.
+	pos: usize,
+	/// The number of bits that were written before the start of `buf`.
+	written: usize,
+	order: BitOrder,
+}
+
+impl<W: Write, const N: usize> IoBitWriter<W, N> {
+	/// Create a writer that writes to `sink`.
+	///
+	/// Panics if `N` is less than 9, since a value of 64 bits that doesn't
+	/// start at the start of a byte can span 9 bytes.
+	pub fn new(sink: W, order: BitOrder) -> Self {
+		check!(N >= 9, buffer_too_small);
+		Self {
+			sink,
+			buf: [0; N],
+			pos: 0,
+			written: 0,
+			order,
+		}
+	}
+
+	/// The bit order of this writer.
+	pub fn order(&self) -> BitOrder {
+		self.order
+	}
+
+	/// The number of bits written so far.
+	pub fn position(&self) -> usize {
+		self.written + self.pos
+	}
+
+	/// Whether the writer is at the start of a byte.
+	pub fn is_aligned(&self) -> bool {
+		self.pos.is_multiple_of(8)
+	}
+
+	/// Write all complete bytes in the buffer to the sink.
+	fn write_buffer(&mut self) -> io::Result<()> {
+		let n = self.pos / 8;
+		self.sink.write_all(&self.buf[..n])?;
+		let partial = !self.is_aligned() as usize;
+		self.buf.copy_within(n..n + partial, 0);
+		self.pos -= n * 8;
+		self.written += n * 8;
+		Ok(())
+	}
+
+	/// Write the lowest `n` bits of `value`.
+	///
+	/// Panics if `n` is more than 64, or when the other bits of `value` are
+	/// not 0.
+	pub fn write_bits(&mut self, value: u64, n: u32) -> io::Result<()> {
+		check!(n <= 64, bit_range_too_wide);
+		if self.pos + n as usize > N * 8 {
+			self.write_buffer()?;
+		}
+		let end = self.pos + n as usize;
+		self.buf[..].set_bits(self.pos..end, value as u128, self.order);
+		self.pos = end;
+		Ok(())
+	}
+
+	/// Write a single bit.
+	pub fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+		self.write_bits(bit as u64, 1)
+	}
+
+	/// Write zero bits up to the start of the next byte, unless already at the
+	/// start of a byte.
+	pub fn align_to_byte(&mut self) -> io::Result<()> {
+		let n = (8 - self.pos % 8) % 8;
+		self.write_bits(0, n as u32)
+	}
+
+	/// Write all complete bytes to the sink, and flush it.
+	///
+	/// A partially written byte stays in the buffer.
+	pub fn flush(&mut self) -> io::Result<()> {
+		self.write_buffer()?;
+		self.sink.flush()
+	}
+
+	/// Write all bits to the sink, with the last byte padded with 0 bits,
+	/// flush it, and return it.
+	pub fn finish(mut self) -> io::Result<W> {
+		self.align_to_byte()?;
+		self.flush()?;
+		Ok(self.sink)
+	}
+}
+
+impl crate::BitReader<'_> {
+	/// Create an [`IoBitReader`] that reads from a [`std::io::Read`] source.
+	///
+	/// This is only available with the `std` feature.
+	pub fn from_read<R: Read>(source: R, order: BitOrder) -> IoBitReader<R> {
+		IoBitReader::new(source, order)
+	}
+}
+
+impl crate::BitWriter<&mut [u8]> {
+	/// Create an [`IoBitWriter`] that writes to a [`std::io::Write`] sink.
+	///
+	/// This is the same as [`IoBitWriter::new`].
+	///
+	/// This is only available with the `std` feature.
+	pub fn into_write<W: Write>(sink: W, order: BitOrder) -> IoBitWriter<W> {
+		IoBitWriter::new(sink, order)
+	}
+}
@@ -0,0 +1,39 @@
+use super::{Bits, BitsIndex};
+use core::marker::PhantomData;
+
+struct Lanes<T, const W: u32, const N: usize>(PhantomData<T>);
+
+impl<T: Bits, const W: u32, const N: usize> Lanes<T, W, N> {
+	const VALID: () = assert!(
+		W > 0 && W as u64 * N as u64 == T::N_BITS as u64,
+		"lanes don't cover all bits"
+	);
+}
+
+/// Split an integer into `N` lanes of `W` bits, least significant lane first.
+#[inline]
+pub(crate) fn to_lanes<T, const W: u32, const N: usize>(value: T) -> [T::Bits; N]
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[allow(clippy::let_unit_value)]
+	let () = Lanes::<T, W, N>::VALID;
+	core::array::from_fn(|i| value.bits(i as u32 * W..(i as u32 + 1) * W))
+}
+
+/// Combine `N` lanes of `W` bits, least significant lane first.
+#[inline]
+pub(crate) fn from_lanes<T, const W: u32, const N: usize>(lanes: [T::Bits; N]) -> T
+where
+	T: Bits + Default,
+	u32: BitsIndex<T>,
+{
+	#[allow(clippy::let_unit_value)]
+	let () = Lanes::<T, W, N>::VALID;
+	let mut value = T::default();
+	for (i, &lane) in lanes.iter().enumerate() {
+		value.set_bits(i as u32 * W..(i as u32 + 1) * W, lane);
+	}
+	value
+}
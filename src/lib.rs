@@ -1,4 +1,5 @@
 #![no_std]
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 
 //! This crates provides two functions for accessing the individual bits of
 //! integers:
@@ -16,14 +17,148 @@
 //! - [`.with_bit(i, bit)`][Bits::with_bit]
 //! - [`.with_bits(i..j, bits)`][Bits::with_bits]
 //!
+//! The [`Bits`] trait is implemented for all primitive integer types, and
+//! for their `NonZero`, `Wrapping` and `Saturating` variants. For `NonZero`
+//! integers, the setters panic if the result would be zero. Integers of other
+//! widths, like [`u12`], are provided as [`U`].
+//!
+//! The implementations for 128-bit and signed integers can be left out by
+//! disabling the default `impl-128` and `impl-signed` features, which reduces
+//! compile time and code size on small targets. `WideInt` and the `ethnum`
+//! support need `impl-128`. Unsuffixed literals like `3` are `i32`, so `i32`
+//! can always be used as a bit index.
+//!
+//! To handle integers of different types through a trait object, use
+//! [`DynBits`]. For code that is generic over `num_traits::PrimInt`, the
+//! `num-traits` feature provides `PrimIntBits`. Similarly, the `funty` feature
+//! provides `IntegralBits` for code that is generic over `funty::Integral`.
+//!
+//! Arrays of unsigned integers can be treated as one large integer using
+//! [`ArrayBits`]. For integers wider than 128 bits that implement [`Bits`],
+//! use `WideInt`. With the `ethnum` feature, `ethnum::U256` and `ethnum::I256`
+//! implement [`Bits`] as well.
+//!
+//! With the `fixed` feature, the fixed-point types of the `fixed` crate
+//! implement [`Bits`], and `FixedBits` gives access to their integer and
+//! fractional parts.
+//!
+//! The [`batch`] module applies the same operation to every word of a slice.
+//!
+//! Bits of byte slices, such as packed binary data in a receive buffer, can
+//! be accessed using [`SliceBits`], in either [`BitOrder`]. Fields of network
+//! headers can be accessed as they are drawn in RFC diagrams using
+//! [`read_be_field`] and [`write_be_field`]. For run-length coding, [`iter_runs`]
+//! iterates over the runs of equal bits in a buffer.
+//! To count the set bits before a position, or find the position of the
+//! n-th set bit, use [`rank`] and [`select`], or [`RankIndex`] for many
+//! queries on the same buffer.
+//!
+//! To find the bytes that hold a range of bits, for example to program flash
+//! memory or set up a DMA transfer, use [`bit_range_to_bytes`]. To split a range
+//! of bits into partial and whole bytes or words, use [`aligned_segments`].
+//!
+//! With the `bitvec` feature, integers can be moved to and from a `bitvec`
+//! `BitSlice` using `load_bitslice` and `store_bitslice`.
+//!
+//! Bit streams can be read using [`BitReader`] and written using
+//! [`BitWriter`], which writes into any [`BitSink`], including `heapless` and
+//! `arrayvec` vectors with the features of the same name. With the
+//! `embedded-io-async` feature, `AsyncBitReader` reads bits from an
+//! `embedded_io_async::Read` source. With the `std` feature, `IoBitReader` and
+//! `IoBitWriter` read from and write to `std::io` streams.
+//!
+//! For random access to a bit stream, use [`BitCursor`]. Sequences of small
+//! symbols, like 2-bit nucleotides, can be packed into bytes using
+//! [`PackedSeq`]. To collect a few bits
+//! at a time before using them, use [`BitQueue`] or [`BitStack`]. For link
+//! testing, [`PrbsGenerator`] writes standard test sequences, and
+//! [`PrbsChecker`] counts bit errors in a received sequence.
+//!
+//! The [`enc8b10b`] module implements the 8b/10b line code of many serial
+//! links.
+//!
+//! The [`can`] module extracts signals from CAN frames following DBC
+//! conventions.
+//!
+//! The [`color`] module gives access to the channels of packed pixel formats
+//! like RGB565.
+//!
+//! The [`ct`] module provides branchless bit operations for cryptographic
+//! code.
+//!
+//! An integer can be used as a small set of numbers using [`IntSet`]. For
+//! larger sets, use [`FixedBitSet`], or with the `alloc` feature, the
+//! growable `DynBitSet`.
+//!
+//! With the `portable_simd` feature, which requires a nightly compiler, the
+//! same field can be accessed in all lanes of a SIMD vector at once using
+//! `SimdBits`.
+//!
+//! With the `bitflags` feature, fields can be read as and written from
+//! `bitflags` flag sets.
+//!
+//! To print all bits of a value in readable groups, use [`Bin`]. Binary strings
+//! with separators can be parsed using [`FromBinStr`]. To list the indices of
+//! the bits that are set, use [`SetBits`]. To show where a field is within a
+//! value while debugging, use [`FieldDump`].
+//!
+//! Newtypes around integers, such as register values, can implement [`Bits`]
+//! using [`impl_bits!`].
+//!
+//! With the `svd2rust` feature, register proxies of peripheral access crates
+//! can be accessed through `RegisterBits`.
+//!
+//! Integers in a [`Cell`][core::cell::Cell] can be modified in place using
+//! [`CellBits`]. Generic code can read the bits of borrowed integers using
+//! [`RefBits`].
+//!
+//! For indexing syntax, like `view[3]`, wrap a value in [`BitsOf`].
+//!
+//! A field can be modified in place using compound assignment through
+//! [`bits_mut`][Bits::bits_mut]. A single bit can be passed around as a
+//! [`BitRef`], created by [`bit_mut`][Bits::bit_mut].
+//!
+//! To assemble a value from many fields, while checking that no bit is written
+//! twice, use [`T::bits_builder()`][Bits::bits_builder].
+//!
+//! Fields with a fixed position can be described once using [`BitField`].
+//! For ranges known at compile time, [`ConstBits`] avoids any runtime bounds
+//! checks, even in debug builds. A range that is only known at runtime, but
+//! used many times, can be checked once using [`FieldSpec`]. To sort or hash
+//! integers by one of their fields, use [`FieldKey`].
+//! Layouts that are only known at runtime can be described using
+//! [`BitSchema`], and several of them over the same bits, selected by a tag
+//! field, using [`SchemaOverlay`]. With the `serde` feature, schemas can be
+//! loaded from configuration files as a `SchemaBuf` (which requires the `alloc`
+//! feature), and a [`FieldSpec`] or [`BitField`] is written as its range. To
+//! write masks in those files as binary strings, use `BitString`.
+//!
+//! Structs made of bit fields can be packed into and unpacked from integers
+//! using [`IntoBits`] and [`FromBits`], which can be derived with the
+//! `derive` feature. The same feature provides a `BitsIndex` derive, so
+//! fieldless enums can name the bits they index: `status.bit(Flag::Carry)`.
+//!
+//! All error types implement `core::error::Error`, and convert into [`Error`]
+//! for propagation with `?`.
+//!
+//! With the `defmt` feature, the error types, sets, integer types and
+//! [`SchemaDisplay`] implement `defmt::Format`, for logging on embedded
+//! targets.
+//!
+//! With the `arbitrary` feature, the sets and integer types implement
+//! `Arbitrary`, and `arbitrary_bit_range` generates valid ranges of bits for
+//! fuzzing. With the `rand` feature, `random_with_pattern` generates random
+//! values with some bits fixed, and `ExactOnes` generates random values with
+//! a given number of bits set.
+//!
 //! # Example
 //!
 //! ```
 //! use intbits::Bits;
 //!
-//! assert_eq!(2.bit(0), false);
-//! assert_eq!(2.bit(1), true);
-//! assert_eq!(2.bit(2), false);
+//! assert_eq!(2u32.bit(0), false);
+//! assert_eq!(2u32.bit(1), true);
+//! assert_eq!(2u32.bit(2), false);
 //!
 //! assert_eq!(0b1011u32.bits(0..2), 0b11);
 //! assert_eq!(0b1011u32.bits(2..4), 0b10);
@@ -32,25 +167,78 @@
 //! assert_eq!(0xFFu8.with_bits(4..8, 3), 0x3F);
 //! ```
 
-use core::ops::RangeBounds;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::Hash;
+use core::ops::{BitAnd, BitOr, BitXor, Not, Range, RangeBounds, Shl, Shr};
 
 /// Extension trait to provide access to individual bits of integers.
-pub trait Bits {
+///
+/// The bounds on this trait and on [`Bits::Bits`] make it possible to write
+/// generic code for any integer type.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{Bits, BitsIndex};
+///
+/// /// Swap the two nibbles in the lowest byte.
+/// fn swap_nibbles<T: Bits>(x: T) -> T
+/// where
+///     u32: BitsIndex<T>,
+/// {
+///     let (low, high) = (x.bits(0..4), x.bits(4..8));
+///     x.with_bits(0..8, low << 4 | high)
+/// }
+///
+/// assert_eq!(swap_nibbles(0x1234u16), 0x1243);
+/// assert_eq!(swap_nibbles(0xF1u8), 0x1F);
+/// ```
+///
+/// # References
+///
+/// This trait is not implemented for `&T`, since the setters can't work
+/// through a shared reference. Generic code that only reads bits can use
+/// [`RefBits`] to take borrowed integers.
+pub trait Bits: Copy + Eq + Hash + fmt::Debug {
 	/// The (unsigned) type used to represent bits of this type.
 	///
 	/// For unsigned integers, this is `Self`.
 	/// For signed integers, this is the unsigned variant of `Self`.
 	///
+	/// It is always an unsigned primitive integer type, which is why it has
+	/// the bounds of one.
+	///
 	/// # Example
 	///
 	/// ```
 	/// # use intbits::Bits;
 	/// assert_eq!(0 as <u8 as Bits>::Bits, 0u8);
+	/// # #[cfg(feature = "impl-signed")]
 	/// assert_eq!(0 as <i64 as Bits>::Bits, 0u64);
 	/// assert_eq!(0 as <usize as Bits>::Bits, 0usize);
+	/// # #[cfg(feature = "impl-signed")]
 	/// assert_eq!(0 as <isize as Bits>::Bits, 0usize);
 	/// ```
-	type Bits;
+	type Bits: Bits<Bits = Self::Bits>
+		+ Ord
+		+ Default
+		+ From<u8>
+		+ fmt::Display
+		+ fmt::Binary
+		+ fmt::LowerHex
+		+ fmt::UpperHex
+		+ Not<Output = Self::Bits>
+		+ BitAnd<Output = Self::Bits>
+		+ BitOr<Output = Self::Bits>
+		+ BitXor<Output = Self::Bits>
+		+ Shl<u32, Output = Self::Bits>
+		+ Shr<u32, Output = Self::Bits>;
 
 	/// The number of bits this type has.
 	///
@@ -59,10 +247,39 @@ pub trait Bits {
 	/// ```
 	/// # use intbits::Bits;
 	/// assert_eq!(u8::N_BITS, 8);
-	/// assert_eq!(i64::N_BITS, 64);
+	/// assert_eq!(u64::N_BITS, 64);
 	/// ```
 	const N_BITS: u32;
 
+	/// The index of the most significant bit: [`N_BITS`][Bits::N_BITS] - 1.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(u8::MAX_BIT, 7);
+	/// assert_eq!(1u32.with_bit(u32::MAX_BIT, true), 0x8000_0001);
+	/// ```
+	const MAX_BIT: u32 = Self::N_BITS - 1;
+
+	/// The range of all bit indices: `0..N_BITS`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// fn count_ones<T: Bits>(v: T) -> usize
+	/// where
+	///     u32: intbits::BitsIndex<T>,
+	/// {
+	///     T::BIT_INDICES.filter(|&i| v.bit(i)).count()
+	/// }
+	///
+	/// assert_eq!(u16::BIT_INDICES, 0..16);
+	/// assert_eq!(count_ones(0xFFFFu16), 16);
+	/// ```
+	const BIT_INDICES: Range<u32> = 0..Self::N_BITS;
+
 	/// Get a specific bit.
 	///
 	/// Panics if the index is out of range.
@@ -85,7 +302,9 @@ pub trait Bits {
 	/// The bits are returned in the least significant bits of the return
 	/// value. The other bits, if any, will be 0.
 	///
-	/// Empty ranges are allowed, and will result in 0.
+	/// Empty ranges are allowed, and will result in 0. Inverted ranges, with
+	/// a start after their end, are treated as empty, so computed bounds that
+	/// collapse don't need to be checked first.
 	///
 	/// Panics when the range bounds are out of range.
 	///
@@ -128,6 +347,8 @@ pub trait Bits {
 	/// The bits should be given in the least significant bits of the second
 	/// argument. The other bits should be 0.
 	///
+	/// Empty and inverted ranges are allowed, and leave the value unchanged.
+	///
 	/// Panics when the range bounds are out of range or when the irrelevant
 	/// bits of the second argument are not 0.
 	///
@@ -138,6 +359,10 @@ pub trait Bits {
 	/// let mut a = 0xFFu8;
 	/// a.set_bits(4..8, 3);
 	/// assert_eq!(a, 0x3F);
+	///
+	/// let (hi, lo) = (2, 5);
+	/// a.set_bits(lo..hi, 0);
+	/// assert_eq!(a, 0x3F);
 	/// ```
 	fn set_bits<I, R>(&mut self, range: R, bits: Self::Bits)
 	where
@@ -179,9 +404,814 @@ pub trait Bits {
 		I: BitsIndex<Self>,
 		R: RangeBounds<I>,
 		Self: Sized;
+
+	/// Get a new integer with a range of bits copied from another value.
+	///
+	/// The bits outside the range are kept. This is equivalent to
+	/// `self.with_bits(range, other.bits(range))`.
+	///
+	/// Panics when the range bounds are out of range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let old = 0x0A50u16;
+	/// let new = 0x1234u16;
+	/// assert_eq!(new.with_bits_from(old, 4..8), 0x1254);
+	/// ```
+	#[inline]
+	fn with_bits_from<I, R>(self, other: Self, range: R) -> Self
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I> + Clone,
+		Self: Sized,
+	{
+		let bits = other.bits(range.clone());
+		self.with_bits(range, bits)
+	}
+
+	/// Move a range of bits to start at bit `to`.
+	///
+	/// The source bits are cleared, and then the field is written at its new
+	/// position. The ranges may overlap. Bits outside both ranges are kept.
+	///
+	/// Panics when the range bounds are out of range, or when the field
+	/// doesn't fit at its new position.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let mut reg = 0xF0A5u16;
+	/// reg.move_bits(0..8, 4);
+	/// assert_eq!(reg, 0xFA50);
+	/// reg.move_bits(12..16, 0);
+	/// assert_eq!(reg, 0x0A5F);
+	/// ```
+	#[inline]
+	fn move_bits<R: RangeBounds<u32>>(&mut self, from: R, to: u32)
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		let (start, end) = range::bounds(&from, Self::N_BITS);
+		let width = end - start;
+		if to > Self::N_BITS - width {
+			panic::invalid_bit_range();
+		}
+		let bits = self.bits(start..end);
+		self.set_bits(start..end, Self::Bits::default());
+		self.set_bits(to..to + width, bits);
+	}
+
+	/// Get a range of bits, as if the bytes of the integer were in the opposite
+	/// order.
+	///
+	/// This is useful for fields of a big endian value that was loaded as a
+	/// little endian integer, or the other way around. It is equivalent to
+	/// `self.swap_bytes().bits(range)`.
+	///
+	/// Panics when the range bounds are out of range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let a = u32::from_le_bytes([0x12, 0x34, 0x56, 0x78]);
+	/// assert_eq!(a.bits_be(20..28), 0x23);
+	/// ```
+	#[inline]
+	fn bits_be<I, R>(self, range: R) -> Self::Bits
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		generic::swap_bytes::<Self, I>(self).bits(range)
+	}
+
+	/// Set a range of bits, as if the bytes of the integer were in the opposite
+	/// order.
+	///
+	/// See [`bits_be`][Self::bits_be].
+	///
+	/// Panics when the range bounds are out of range or when the irrelevant
+	/// bits of the second argument are not 0.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let mut a = 0u32;
+	/// a.set_bits_be(20..28, 0xAB);
+	/// assert_eq!(a.to_le_bytes(), [0x0A, 0xB0, 0, 0]);
+	/// ```
+	#[inline]
+	fn set_bits_be<I, R>(&mut self, range: R, bits: Self::Bits)
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		let mut swapped = generic::swap_bytes::<Self, I>(*self);
+		swapped.set_bits(range, bits);
+		*self = generic::swap_bytes::<Self, I>(swapped);
+	}
+
+	/// Get a range of bits as a signed fixed-point number.
+	///
+	/// The field is interpreted as a two's complement number with `frac_bits`
+	/// fractional bits, as in the Q format. For example, a 16-bit Q1.15 field
+	/// has `frac_bits` 15.
+	///
+	/// Panics when the range bounds are out of range or when `frac_bits` is 128
+	/// or more.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(0xC000_0000u32.bits_q(16..32, 15), -0.5);
+	/// assert_eq!(0x0180u16.bits_q(4..12, 4), 1.5);
+	/// ```
+	#[inline]
+	fn bits_q<I, R>(self, range: R, frac_bits: u32) -> f32
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I> + Clone,
+		Self: Sized,
+	{
+		let (bits, ones) = generic::field(self, range);
+		signed::q_to_f32(signed::sign_extend(bits, ones), frac_bits)
+	}
+
+	/// Get a range of bits as an unsigned fixed-point number.
+	///
+	/// Like [`bits_q`][Self::bits_q], but for unsigned (UQ format) fields.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(0xC000u16.bits_uq(8..16, 8), 0.75);
+	/// ```
+	#[inline]
+	fn bits_uq<I, R>(self, range: R, frac_bits: u32) -> f32
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		signed::uq_to_f32(generic::to_u128::<Self>(self.bits(range)), frac_bits)
+	}
+
+	/// Get a range of bits as a signed fixed-point number, multiplied by an
+	/// integer scale.
+	///
+	/// This calculates `value * scale`, where `value` is the number returned by
+	/// [`bits_q`][Self::bits_q], without using floating point. The result is
+	/// rounded towards negative infinity. Use a `scale` of 1000 to get
+	/// thousandths, for example.
+	///
+	/// Panics when the range bounds are out of range, when `frac_bits` is 128
+	/// or more, or when the result does not fit in an `i64`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// // -1.25 in Q3.4, in millis.
+	/// assert_eq!(0xEC_u8.bits_q_scaled(0..8, 4, 1000), -1250);
+	/// ```
+	#[inline]
+	fn bits_q_scaled<I, R>(self, range: R, frac_bits: u32, scale: i64) -> i64
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I> + Clone,
+		Self: Sized,
+	{
+		let (bits, ones) = generic::field(self, range);
+		signed::q_scaled(signed::sign_extend(bits, ones), frac_bits, scale)
+	}
+
+	/// Get a range of bits as a one's complement number.
+	///
+	/// The most significant bit of the range is the sign bit. Negative zero
+	/// (all bits set) is returned as 0.
+	///
+	/// Panics when the range bounds are out of range or when the range is
+	/// wider than 64 bits.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(0x0Bu8.bits_ones_complement(0..4), -4);
+	/// assert_eq!(0x0Fu8.bits_ones_complement(0..4), 0);
+	/// assert_eq!(0x07u8.bits_ones_complement(0..4), 7);
+	/// ```
+	#[inline]
+	fn bits_ones_complement<I, R>(self, range: R) -> i64
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I> + Clone,
+		Self: Sized,
+	{
+		let (bits, ones) = generic::field(self, range);
+		signed::ones_complement(bits, ones)
+	}
+
+	/// Get a range of bits as a sign-magnitude number.
+	///
+	/// The most significant bit of the range is the sign bit, and the other
+	/// bits are the magnitude. Negative zero (only the sign bit set) is
+	/// returned as 0.
+	///
+	/// Panics when the range bounds are out of range or when the range is
+	/// wider than 64 bits.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(0x0Bu8.bits_sign_magnitude(0..4), -3);
+	/// assert_eq!(0x08u8.bits_sign_magnitude(0..4), 0);
+	/// assert_eq!(0x07u8.bits_sign_magnitude(0..4), 7);
+	/// ```
+	#[inline]
+	fn bits_sign_magnitude<I, R>(self, range: R) -> i64
+	where
+		I: BitsIndex<Self>,
+		R: RangeBounds<I> + Clone,
+		Self: Sized,
+	{
+		let (bits, ones) = generic::field(self, range);
+		signed::sign_magnitude(bits, ones)
+	}
+
+	/// Get a range of bits, converted to another type.
+	///
+	/// This is meant for fields that represent an enum. The bits are converted
+	/// using [`TryFrom`], and an [`InvalidValue`] with the raw bits is returned
+	/// if that fails.
+	///
+	/// Panics when the range bounds are out of range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::{Bits, InvalidValue};
+	/// # use core::convert::TryFrom;
+	/// #[derive(Debug, PartialEq)]
+	/// enum Mode { Off, Slow, Fast }
+	///
+	/// impl TryFrom<u8> for Mode {
+	///     type Error = ();
+	///     fn try_from(v: u8) -> Result<Self, ()> {
+	///         match v {
+	///             0 => Ok(Mode::Off),
+	///             1 => Ok(Mode::Slow),
+	///             2 => Ok(Mode::Fast),
+	///             _ => Err(()),
+	///         }
+	///     }
+	/// }
+	///
+	/// assert_eq!(0x20u8.bits_as::<Mode, _, _>(4..6), Ok(Mode::Fast));
+	/// assert_eq!(0x30u8.bits_as::<Mode, _, _>(4..6), Err(InvalidValue(3)));
+	/// ```
+	#[inline]
+	fn bits_as<E, I, R>(self, range: R) -> Result<E, InvalidValue<Self::Bits>>
+	where
+		E: TryFrom<Self::Bits>,
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		let bits = self.bits(range);
+		E::try_from(bits).map_err(|_| InvalidValue(bits))
+	}
+
+	/// Get a range of bits as a wider unsigned integer.
+	///
+	/// This is the same as [`bits`][Bits::bits] followed by a lossless
+	/// conversion, which only exists if `W` is at least as wide as
+	/// [`Self::Bits`][Bits::Bits].
+	///
+	/// Panics when the range bounds are out of range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let mut acc = 0u64;
+	/// acc.set_bits(0..4, 0xA5u8.bits_widened::<u64, _, _>(4..8));
+	/// acc.set_bits(4..20, 0x1234_5678u32.bits_widened::<u64, _, _>(8..24));
+	/// assert_eq!(acc, 0x3456A);
+	/// ```
+	#[inline]
+	fn bits_widened<W, I, R>(self, range: R) -> W
+	where
+		W: From<Self::Bits>,
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		W::from(self.bits(range))
+	}
+
+	/// Set a range of bits from a value of another type.
+	///
+	/// This is meant for fields that represent an enum. The value is converted
+	/// using [`Into`].
+	///
+	/// Panics when the range bounds are out of range or when the converted
+	/// value does not fit in the range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// enum Mode { Off, Slow, Fast }
+	///
+	/// impl From<Mode> for u8 {
+	///     fn from(m: Mode) -> u8 {
+	///         m as u8
+	///     }
+	/// }
+	///
+	/// let mut a = 0u8;
+	/// a.set_bits_from(4..6, Mode::Fast);
+	/// assert_eq!(a, 0x20);
+	/// ```
+	#[inline]
+	fn set_bits_from<E, I, R>(&mut self, range: R, value: E)
+	where
+		E: Into<Self::Bits>,
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		self.set_bits(range, value.into())
+	}
+
+	/// Get a range of bits as a set of `bitflags` flags.
+	///
+	/// The bits are converted to `F`, and bits that don't correspond to a
+	/// defined flag are handled according to `unknown`. An [`InvalidValue`]
+	/// with the raw bits is returned if unknown bits are rejected, or if the
+	/// bits don't fit in `F`.
+	///
+	/// Panics when the range bounds are out of range.
+	///
+	/// This is only available with the `bitflags` feature.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::{Bits, InvalidValue, UnknownBits};
+	/// bitflags::bitflags! {
+	///     #[derive(Debug, PartialEq)]
+	///     struct Irq: u8 {
+	///         const RX = 1;
+	///         const TX = 2;
+	///     }
+	/// }
+	///
+	/// let status = 0x0000_0700u32;
+	/// assert_eq!(status.bits_as_flags::<Irq, _, _>(8..12, UnknownBits::Reject), Err(InvalidValue(7)));
+	/// assert_eq!(status.bits_as_flags(8..12, UnknownBits::Truncate), Ok(Irq::RX | Irq::TX));
+	/// ```
+	#[cfg(feature = "bitflags")]
+	#[inline]
+	fn bits_as_flags<F, I, R>(
+		self,
+		range: R,
+		unknown: UnknownBits,
+	) -> Result<F, InvalidValue<Self::Bits>>
+	where
+		F: bitflags::Flags,
+		F::Bits: TryFrom<Self::Bits>,
+		Self::Bits: From<F::Bits>,
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		let bits = self.bits(range);
+		let known = Self::Bits::from(F::all().bits());
+		let bits = match unknown {
+			UnknownBits::Reject if bits & !known != Self::Bits::default() => {
+				return Err(InvalidValue(bits))
+			}
+			UnknownBits::Truncate => bits & known,
+			_ => bits,
+		};
+		match F::Bits::try_from(bits) {
+			Ok(b) => Ok(F::from_bits_retain(b)),
+			Err(_) => Err(InvalidValue(bits)),
+		}
+	}
+
+	/// Set a range of bits from a set of `bitflags` flags.
+	///
+	/// All bits of the flags are written, including unknown bits.
+	///
+	/// Panics when the range bounds are out of range or when the bits of the
+	/// flags do not fit in the range.
+	///
+	/// This is only available with the `bitflags` feature.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// bitflags::bitflags! {
+	///     struct Irq: u8 {
+	///         const RX = 1;
+	///         const TX = 2;
+	///     }
+	/// }
+	///
+	/// let mut enable = 0u32;
+	/// enable.set_bits_from_flags(8..12, Irq::TX);
+	/// assert_eq!(enable, 0x200);
+	/// ```
+	#[cfg(feature = "bitflags")]
+	#[inline]
+	fn set_bits_from_flags<F, I, R>(&mut self, range: R, flags: F)
+	where
+		F: bitflags::Flags,
+		Self::Bits: From<F::Bits>,
+		I: BitsIndex<Self>,
+		R: RangeBounds<I>,
+		Self: Sized,
+	{
+		self.set_bits(range, flags.bits().into())
+	}
+
+	/// Start assembling a new value field by field.
+	///
+	/// See [`BitsBuilder`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let v = u32::bits_builder()
+	///     .field(0..4, 0xA)
+	///     .field(8..16, 0x12)
+	///     .bit(31, true)
+	///     .build();
+	/// assert_eq!(v, Ok(0x8000_120A));
+	/// ```
+	#[inline]
+	fn bits_builder() -> BitsBuilder<Self>
+	where
+		BitsBuilder<Self>: Default,
+		Self: Sized,
+	{
+		BitsBuilder::default()
+	}
+
+	/// The number of significant bits: the index of the highest bit that is
+	/// set, plus one.
+	///
+	/// This is 0 for zero, and [`N_BITS`][Bits::N_BITS] for negative numbers.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(0u32.bit_len(), 0);
+	/// assert_eq!(1u32.bit_len(), 1);
+	/// assert_eq!(0x80u8.bit_len(), 8);
+	/// assert_eq!(300u64.bit_len(), 9);
+	/// # #[cfg(feature = "impl-signed")]
+	/// assert_eq!((-1i16).bit_len(), 16);
+	/// ```
+	#[inline]
+	fn bit_len(self) -> u32
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		// One `Self::Bits` at a time, from the top, for types wider than that.
+		let word = <Self::Bits as Bits>::N_BITS;
+		let mut end = Self::N_BITS;
+		while end > 0 {
+			let start = end.saturating_sub(word);
+			let len = self.bit_len_in(start..end);
+			if len > 0 {
+				return start + len;
+			}
+			end = start;
+		}
+		0
+	}
+
+	/// The number of significant bits of a range of bits.
+	///
+	/// This is the [`bit_len`][Bits::bit_len] of [`bits(range)`][Bits::bits].
+	///
+	/// Panics when the range bounds are out of range, or when the range is
+	/// wider than [`Self::Bits`][Bits::Bits].
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(0xF0_05u16.bit_len_in(0..8), 3);
+	/// assert_eq!(0xF0_05u16.bit_len_in(4..12), 0);
+	/// ```
+	#[inline]
+	fn bit_len_in<R>(self, range: R) -> u32
+	where
+		R: RangeBounds<u32>,
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		// Binary search, using only the operations available on `Self::Bits`.
+		let zero = Self::Bits::default();
+		let mut bits = self.bits(range);
+		let mut len = 0;
+		let mut step = 64;
+		while step > 0 {
+			if step < Self::N_BITS && bits >> step != zero {
+				bits = bits >> step;
+				len += step;
+			}
+			step /= 2;
+		}
+		len + (bits != zero) as u32
+	}
+
+	/// Check if all bits that are set in `mask` are also set in `self`.
+	///
+	/// This is `self & mask == mask`. An empty mask is always contained.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert!(0b1110u8.contains_all_bits(0b0110));
+	/// assert!(!0b1110u8.contains_all_bits(0b0011));
+	/// ```
+	#[inline]
+	fn contains_all_bits(self, mask: Self::Bits) -> bool
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		self.bits(Self::BIT_INDICES) & mask == mask
+	}
+
+	/// Check if any of the bits that are set in `mask` is also set in `self`.
+	///
+	/// This is `self & mask != 0`. This is false for an empty mask.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert!(0b1110u8.contains_any_bits(0b0011));
+	/// assert!(!0b1110u8.contains_any_bits(0b0001));
+	/// ```
+	#[inline]
+	fn contains_any_bits(self, mask: Self::Bits) -> bool
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		self.bits(Self::BIT_INDICES) & mask != Self::Bits::default()
+	}
+
+	/// Check if none of the bits that are set in `mask` are set in `self`.
+	///
+	/// This is `self & mask == 0`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert!(0b1110u8.disjoint_bits(0b0001));
+	/// assert!(!0xFFu8.disjoint_bits(0x80));
+	/// ```
+	#[inline]
+	fn disjoint_bits(self, mask: Self::Bits) -> bool
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		!self.contains_any_bits(mask)
+	}
+
+	/// Check that all bits in the given reserved ranges are zero.
+	///
+	/// Returns the first range that has a bit set as an error.
+	///
+	/// Panics if a range is out of range, or wider than
+	/// [`Self::Bits`][Bits::Bits].
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::{Bits, ReservedBitsSet};
+	/// assert_eq!(0x00FFu16.assert_reserved_zero(&[8..12, 14..16]), Ok(()));
+	/// assert_eq!(
+	///     0x80FFu16.assert_reserved_zero(&[8..12, 14..16]),
+	///     Err(ReservedBitsSet { start: 14, end: 16 }),
+	/// );
+	/// ```
+	#[inline]
+	fn assert_reserved_zero(self, reserved: &[Range<u32>]) -> Result<(), ReservedBitsSet>
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		for range in reserved {
+			if self.bits(range.clone()) != Self::Bits::default() {
+				return Err(ReservedBitsSet {
+					start: range.start,
+					end: range.end,
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Set several fields at once.
+	///
+	/// This is the same as calling [`set_bits`][Bits::set_bits] for every
+	/// field, but the value is only updated once.
+	///
+	/// Panics if any of the ranges overlap, or if a value doesn't fit in its
+	/// range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let mut ctrl = 0xFF00_0000u32;
+	/// ctrl.set_bits_multi(&[(0..4, 0x5), (4..6, 0b10), (8..16, 0xC3), (24..32, 0)]);
+	/// assert_eq!(ctrl, 0x0000_C325);
+	/// ```
+	#[inline]
+	fn set_bits_multi<R>(&mut self, fields: &[(R, Self::Bits)])
+	where
+		R: RangeBounds<u32>,
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		multi::set_bits_multi(self, fields)
+	}
+
+	/// Get a new integer with several fields set at once.
+	///
+	/// See [`set_bits_multi`][Bits::set_bits_multi].
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let ctrl = 0u16.with_bits_multi([(0..4, 0xA), (12..16, 0x3)]);
+	/// assert_eq!(ctrl, 0x300A);
+	/// ```
+	#[inline]
+	fn with_bits_multi<R, const N: usize>(mut self, fields: [(R, Self::Bits); N]) -> Self
+	where
+		R: RangeBounds<u32>,
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		multi::set_bits_multi(&mut self, &fields);
+		self
+	}
+
+	/// Split the integer into `N` lanes of `W` bits each, starting with the
+	/// least significant bits.
+	///
+	/// `W * N` must be equal to [`N_BITS`][Bits::N_BITS], which is checked at
+	/// compile time. Panics if `W` is wider than [`Self::Bits`][Bits::Bits].
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(0x12345678u32.to_lanes::<8, 4>(), [0x78, 0x56, 0x34, 0x12]);
+	/// assert_eq!(0xABCDu16.to_lanes::<4, 4>(), [0xD, 0xC, 0xB, 0xA]);
+	/// ```
+	///
+	/// Lanes that don't cover all bits don't compile:
+	///
+	/// ```compile_fail
+	/// # use intbits::Bits;
+	/// 0x12345678u32.to_lanes::<8, 3>();
+	/// ```
+	#[inline]
+	fn to_lanes<const W: u32, const N: usize>(self) -> [Self::Bits; N]
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		lanes::to_lanes::<Self, W, N>(self)
+	}
+
+	/// Combine `N` lanes of `W` bits each into an integer, starting with the
+	/// least significant bits.
+	///
+	/// This is the inverse of [`to_lanes`][Bits::to_lanes], with the same
+	/// requirements. Panics if a lane doesn't fit in `W` bits.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// assert_eq!(u32::from_lanes::<8, 4>([0x78, 0x56, 0x34, 0x12]), 0x12345678);
+	/// assert_eq!(u16::from_lanes::<4, 4>([0xD, 0xC, 0xB, 0xA]), 0xABCD);
+	/// ```
+	#[inline]
+	fn from_lanes<const W: u32, const N: usize>(lanes: [Self::Bits; N]) -> Self
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized + Default,
+	{
+		lanes::from_lanes::<Self, W, N>(lanes)
+	}
+
+	/// Get a mutable view of a range of bits, supporting compound assignment.
+	///
+	/// See [`BitsMut`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let mut reg = 0x00F0u16;
+	/// let mut mode = reg.bits_mut(8..12);
+	/// mode |= 0b0010;
+	/// mode ^= 0b0110;
+	/// assert_eq!(reg, 0x04F0);
+	/// ```
+	#[inline]
+	fn bits_mut<R: RangeBounds<u32>>(&mut self, range: R) -> BitsMut<'_, Self>
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		BitsMut::new(self, range)
+	}
+
+	/// Get a mutable reference to a single bit.
+	///
+	/// See [`BitRef`].
+	///
+	/// Panics if the index is out of range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// # use intbits::Bits;
+	/// let mut flags = 0u8;
+	/// flags.bit_mut(3).set(true);
+	/// assert_eq!(flags, 8);
+	/// ```
+	#[inline]
+	fn bit_mut(&mut self, i: u32) -> BitRef<'_, Self>
+	where
+		u32: BitsIndex<Self>,
+		Self: Sized,
+	{
+		BitRef::new(self, i)
+	}
 }
 
 /// Trait for types that can be used to index the bits of `T`.
+///
+/// With the `derive` feature, it can be derived for fieldless enums, which
+/// then index the bit given by their discriminant.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use intbits::{Bits, BitsIndex};
+///
+/// #[derive(BitsIndex)]
+/// enum Flag {
+///     Carry = 0,
+///     Zero = 1,
+///     Negative = 7,
+/// }
+///
+/// let mut status = 0u8;
+/// status.set_bit(Flag::Zero, true);
+/// status.set_bit(Flag::Negative, true);
+/// assert!(status.bit(Flag::Zero));
+/// assert!(!status.bit(Flag::Carry));
+/// assert_eq!(status.bits(Flag::Carry..=Flag::Zero), 0b10);
+/// # }
+/// ```
 pub trait BitsIndex<T> {
 	/// See [`Bits::bit`].
 	fn bit(value: T, index: Self) -> bool;
@@ -199,7 +1229,166 @@ pub trait BitsIndex<T> {
 		R: RangeBounds<Self>;
 }
 
+#[macro_use]
+mod macros;
+#[macro_use]
+mod panic;
+
+mod array;
+#[cfg(feature = "embedded-io-async")]
+mod async_reader;
+pub mod batch;
+mod bitplane;
+mod bitset;
+#[cfg(feature = "bitvec")]
+mod bitslice;
+#[cfg(feature = "serde")]
+mod bitstring;
+mod builder;
+pub mod can;
+mod cell;
+pub mod color;
+mod convert;
+pub mod ct;
+mod cursor;
+mod dynbits;
+#[cfg(feature = "alloc")]
+mod dynbitset;
+pub mod enc8b10b;
+mod error;
+mod field;
+#[cfg(feature = "fixed")]
+mod fixed_point;
+#[cfg(feature = "bitflags")]
+mod flags;
+mod format;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+mod generic;
 mod impls;
+#[cfg(feature = "funty")]
+mod integral;
+#[cfg(feature = "std")]
+mod io;
+mod lanes;
+mod multi;
+mod narrow;
+mod newtype;
+mod nonzero;
+#[cfg(feature = "num-traits")]
+mod num;
+mod packed;
+mod packed_seq;
+mod parse;
+mod prbs;
+mod proxy;
+mod queue;
+#[cfg(feature = "rand")]
+mod random;
+mod range;
+mod rank;
+mod raw;
+mod reader;
+mod reference;
+#[cfg(any(feature = "std", feature = "embedded-io-async"))]
+mod refill;
+#[cfg(feature = "svd2rust")]
+mod register;
+mod schema;
+mod set;
+mod signed;
+#[cfg(feature = "portable_simd")]
+mod simd;
+mod slice;
+mod span;
+#[cfg(all(feature = "ethnum", feature = "impl-128"))]
+mod u256;
+mod view;
+#[cfg(feature = "impl-128")]
+mod wide;
+mod wrapping;
+mod writer;
+
+pub use array::ArrayBits;
+#[cfg(feature = "embedded-io-async")]
+pub use async_reader::AsyncBitReader;
+pub use bitplane::{merge_bitplanes, split_bitplanes};
+pub use bitset::{BitSetIter, FixedBitSet};
+#[cfg(feature = "bitvec")]
+pub use bitslice::{load_bitslice, store_bitslice};
+#[cfg(feature = "serde")]
+pub use bitstring::BitString;
+pub use builder::{BitsBuilder, BuildError};
+pub use cell::CellBits;
+pub use convert::InvalidValue;
+pub use cursor::BitCursor;
+pub use dynbits::DynBits;
+#[cfg(feature = "alloc")]
+pub use dynbitset::DynBitSet;
+pub use error::Error;
+pub use field::{BitField, ConstBits, FieldKey, FieldSpec};
+#[cfg(feature = "fixed")]
+pub use fixed_point::FixedBits;
+#[cfg(feature = "bitflags")]
+pub use flags::UnknownBits;
+pub use format::{Bin, FieldDump, SetBits};
+#[cfg(feature = "arbitrary")]
+pub use fuzz::{arbitrary_bit_index, arbitrary_bit_range};
+#[cfg(feature = "funty")]
+pub use integral::IntegralBits;
+#[cfg(feature = "std")]
+pub use io::{IoBitReader, IoBitWriter};
+pub use multi::merge_bits;
+pub use narrow::*;
+#[cfg(feature = "num-traits")]
+pub use num::PrimIntBits;
+pub use packed::{FromBits, IntoBits};
+pub use packed_seq::{PackedSeq, PackedSeqIter};
+pub use parse::{FromBinStr, ParseBinError};
+pub use prbs::{PrbsChecker, PrbsGenerator, PrbsPattern};
+pub use proxy::{BitRef, BitsMut};
+pub use queue::{BitQueue, BitStack};
+#[cfg(feature = "rand")]
+pub use random::{random_with_pattern, ExactOnes};
+pub use range::{normalize, normalize_range, ToRange};
+pub use rank::{rank, select, RankIndex};
+pub use raw::{pack_raw10, pack_raw12, unpack_raw10, unpack_raw12};
+pub use reader::{BitReader, DecodeError, OutOfBits, ReadError};
+pub use reference::RefBits;
+#[cfg(feature = "svd2rust")]
+pub use register::RegisterBits;
+#[cfg(feature = "alloc")]
+pub use schema::SchemaBuf;
+pub use schema::{
+	validate_partition, BitSchema, OverlayError, PartitionError, ReservedBitsSet, SchemaDisplay,
+	SchemaError, SchemaField, SchemaOverlay,
+};
+pub use set::{IntSet, IntSetIter};
+#[cfg(feature = "portable_simd")]
+pub use simd::SimdBits;
+pub use slice::{
+	copy_bits_slice, find_bit_pattern, iter_runs, pack_bits, read_be_field, reverse_bit_order,
+	reverse_bit_order_copy, shift_bits_left, shift_bits_right, unpack_bits, write_be_field,
+	BitOrder, Runs, SliceBits,
+};
+pub use span::{
+	aligned_byte_range, aligned_segments, bit_range_to_bytes, bytes_to_bit_range, AlignedSegments,
+	Segment,
+};
+
+pub use view::{BitsOf, BitsOfIter};
+#[cfg(feature = "impl-128")]
+pub use wide::WideInt;
+pub use writer::{BitSink, BitWriter};
+
+#[doc(hidden)]
+pub use packed::__private;
+
+#[cfg(feature = "derive")]
+pub use intbits_derive::{BitsIndex, FromBits, IntoBits};
+
+#[cfg(test)]
+extern crate self as intbits;
 
 #[cfg(test)]
 mod test;
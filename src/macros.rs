@@ -0,0 +1,125 @@
+// Implements `Bits` for a type by forwarding everything to `BitsIndex`.
+//
+// `$swap` reverses the bytes of a value of the type, and `$ones` is the value
+// with all bits set.
+macro_rules! forward_bits {
+	([$($g:tt)*] $t:ty, $ut:ty, $n:expr, |$v:ident| $swap:expr, $ones:expr) => {
+		impl<$($g)*> Bits for $t {
+			type Bits = $ut;
+			const N_BITS: u32 = $n;
+			#[inline]
+			fn bit<I>(self, i: I) -> bool
+			where
+				I: BitsIndex<Self>,
+			{
+				I::bit(self, i)
+			}
+			#[inline]
+			fn bits<I, R>(self, range: R) -> $ut
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I>,
+			{
+				I::bits(self, range)
+			}
+			#[inline]
+			fn set_bit<I>(&mut self, i: I, bit: bool)
+			where
+				I: BitsIndex<Self>,
+			{
+				I::set_bit(self, i, bit)
+			}
+			#[inline]
+			fn set_bits<I, R>(&mut self, range: R, bits: $ut)
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I>,
+			{
+				I::set_bits(self, range, bits)
+			}
+			#[inline]
+			fn with_bit<I>(mut self, i: I, bit: bool) -> Self
+			where
+				I: BitsIndex<Self>,
+			{
+				I::set_bit(&mut self, i, bit);
+				self
+			}
+			#[inline]
+			fn with_bits<I, R>(mut self, range: R, bits: $ut) -> Self
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I>,
+			{
+				I::set_bits(&mut self, range, bits);
+				self
+			}
+			#[inline]
+			fn bits_be<I, R>(self, range: R) -> $ut
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I>,
+			{
+				let swap = |$v: Self| $swap;
+				I::bits(swap(self), range)
+			}
+			#[inline]
+			fn set_bits_be<I, R>(&mut self, range: R, bits: $ut)
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I>,
+			{
+				let swap = |$v: Self| $swap;
+				let mut swapped = swap(*self);
+				I::set_bits(&mut swapped, range, bits);
+				*self = swap(swapped);
+			}
+			#[inline]
+			fn bits_q<I, R>(self, range: R, frac_bits: u32) -> f32
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I> + Clone,
+			{
+				let ones = I::bits($ones, range.clone()) as u128;
+				let bits = I::bits(self, range) as u128;
+				crate::signed::q_to_f32(crate::signed::sign_extend(bits, ones), frac_bits)
+			}
+			#[inline]
+			fn bits_uq<I, R>(self, range: R, frac_bits: u32) -> f32
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I>,
+			{
+				crate::signed::uq_to_f32(I::bits(self, range) as u128, frac_bits)
+			}
+			#[inline]
+			fn bits_q_scaled<I, R>(self, range: R, frac_bits: u32, scale: i64) -> i64
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I> + Clone,
+			{
+				let ones = I::bits($ones, range.clone()) as u128;
+				let bits = I::bits(self, range) as u128;
+				crate::signed::q_scaled(crate::signed::sign_extend(bits, ones), frac_bits, scale)
+			}
+			#[inline]
+			fn bits_ones_complement<I, R>(self, range: R) -> i64
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I> + Clone,
+			{
+				let ones = I::bits($ones, range.clone()) as u128;
+				crate::signed::ones_complement(I::bits(self, range) as u128, ones)
+			}
+			#[inline]
+			fn bits_sign_magnitude<I, R>(self, range: R) -> i64
+			where
+				I: BitsIndex<Self>,
+				R: RangeBounds<I> + Clone,
+			{
+				let ones = I::bits($ones, range.clone()) as u128;
+				crate::signed::sign_magnitude(I::bits(self, range) as u128, ones)
+			}
+		}
+	};
+}
@@ -0,0 +1,58 @@
+use super::range::bounds;
+use super::{Bits, BitsIndex};
+use core::ops::{BitAnd, BitXor, RangeBounds};
+
+/// Set several non-overlapping fields at once.
+///
+/// All fields are combined into a single mask and value first, such that the
+/// integer itself is only updated once.
+#[inline]
+pub(crate) fn set_bits_multi<T, R>(value: &mut T, fields: &[(R, T::Bits)])
+where
+	T: Bits,
+	R: RangeBounds<u32>,
+	u32: BitsIndex<T>,
+{
+	let zero = T::Bits::default();
+	let (mut mask, mut merged) = (zero, zero);
+	for (range, bits) in fields {
+		let (start, end) = bounds(range, T::N_BITS);
+		if start == end {
+			check!(*bits == zero, bits_outside_range);
+			continue;
+		}
+		let ones = !zero >> (T::N_BITS - (end - start));
+		check!(*bits & !ones == zero, bits_outside_range);
+		check!(mask & ones << start == zero, overlapping_bit_ranges);
+		mask = mask | ones << start;
+		merged = merged | *bits << start;
+	}
+	let old = value.bits(T::BIT_INDICES);
+	value.set_bits(T::BIT_INDICES, old & !mask | merged);
+}
+
+/// Take the bits of `b` where `mask` is set, and the bits of `a` elsewhere.
+///
+/// This is `a & !mask | b & mask`, but computed as `a ^ (a ^ b) & mask`, which
+/// takes one operation less. Together with a mask made from a condition, this
+/// can be used to update bits without branches.
+///
+/// # Example
+///
+/// ```
+/// use intbits::merge_bits;
+///
+/// assert_eq!(merge_bits(0x1234u16, 0xABCD, 0x0FF0), 0x1BC4);
+/// assert_eq!(merge_bits(-1i8, 0, 0x0F), -16);
+///
+/// let enable = true;
+/// let mask = (enable as u32).wrapping_neg() & 0x30;
+/// assert_eq!(merge_bits(0x0F, 0x20, mask), 0x2F);
+/// ```
+#[inline]
+pub fn merge_bits<T>(a: T, b: T, mask: T) -> T
+where
+	T: Copy + BitAnd<Output = T> + BitXor<Output = T>,
+{
+	a ^ (a ^ b) & mask
+}
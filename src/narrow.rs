@@ -0,0 +1,197 @@
+use super::range::{bounds, u32_bounds};
+use super::{Bits, BitsIndex, InvalidValue};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::RangeBounds;
+
+/// An unsigned integer of `N` bits, stored in a `T`.
+///
+/// Values that don't fit in `N` bits are rejected when the integer is
+/// created, and [`Bits`] only accepts indices below `N`. This is useful for
+/// fields extracted from registers, to carry the width of the field in its
+/// type.
+///
+/// Type aliases like [`u7`] and [`u24`] are provided for all widths, each
+/// stored in the smallest primitive type that fits.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{u12, Bits};
+/// use core::convert::TryFrom;
+///
+/// let x = u12::new(0xABC);
+/// assert_eq!(u12::N_BITS, 12);
+/// assert_eq!(x.bits(8..12), 0xA);
+/// assert_eq!(u16::from(x.with_bits(8..12, 0x1)), 0x1BC);
+/// assert!(u12::try_new(0x1000).is_none());
+/// assert!(u12::try_from(0x1000u16).is_err());
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U<T, const N: u32>(T);
+
+macro_rules! narrow {
+	($($t:ident: $($alias:ident $n:tt)*;)*) => {$(
+		impl<const N: u32> U<$t, N> {
+			const VALID: () = assert!(N > 0 && N <= $t::BITS, "invalid bit width");
+
+			/// The largest value, with all `N` bits set.
+			pub const MAX: Self = U(!0 >> ($t::BITS - N));
+
+			/// The smallest value, 0.
+			pub const MIN: Self = U(0);
+
+			/// Create a value.
+			///
+			/// Panics if `v` does not fit in `N` bits.
+			#[inline]
+			pub const fn new(v: $t) -> Self {
+				match Self::try_new(v) {
+					Some(v) => v,
+					None => panic!("bits outside range"),
+				}
+			}
+
+			/// Create a value, or get `None` if `v` does not fit in `N` bits.
+			#[inline]
+			pub const fn try_new(v: $t) -> Option<Self> {
+				#[allow(clippy::let_unit_value)]
+				let () = Self::VALID;
+				if v <= Self::MAX.0 {
+					Some(U(v))
+				} else {
+					None
+				}
+			}
+
+			/// Get the value.
+			#[inline]
+			pub const fn get(self) -> $t {
+				self.0
+			}
+
+			/// Reverse the order of the bytes.
+			///
+			/// Panics if `N` is not a multiple of 8.
+			#[inline]
+			pub const fn swap_bytes(self) -> Self {
+				assert!(N % 8 == 0, "bit width is not a whole number of bytes");
+				U(self.0.swap_bytes() >> ($t::BITS - N))
+			}
+		}
+
+		impl<const N: u32> From<U<$t, N>> for $t {
+			#[inline]
+			fn from(v: U<$t, N>) -> $t {
+				v.0
+			}
+		}
+
+		impl<const N: u32> TryFrom<$t> for U<$t, N> {
+			type Error = InvalidValue<$t>;
+
+			#[inline]
+			fn try_from(v: $t) -> Result<Self, InvalidValue<$t>> {
+				Self::try_new(v).ok_or(InvalidValue(v))
+			}
+		}
+
+		impl<const N: u32> fmt::Debug for U<$t, N> {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				fmt::Debug::fmt(&self.0, f)
+			}
+		}
+
+		impl<const N: u32> fmt::Display for U<$t, N> {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				fmt::Display::fmt(&self.0, f)
+			}
+		}
+
+		#[cfg(feature = "defmt")]
+		impl<const N: u32> defmt::Format for U<$t, N> {
+			fn format(&self, f: defmt::Formatter) {
+				defmt::Format::format(&self.0, f)
+			}
+		}
+
+		forward_bits!([const N: u32] U<$t, N>, $t, N, |v| v.swap_bytes(), U::<$t, N>::MAX);
+
+		narrow!(@index $t: u8 u16 u32 u64 usize i32);
+		#[cfg(feature = "impl-signed")]
+		narrow!(@index $t: i8 i16 i64 isize);
+		#[cfg(feature = "impl-128")]
+		narrow!(@index $t: u128);
+		#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+		narrow!(@index $t: i128);
+
+		$(
+			#[doc = concat!("A ", stringify!($n), "-bit unsigned integer.")]
+			#[allow(non_camel_case_types)]
+			pub type $alias = U<$t, $n>;
+		)*
+	)*};
+	(@index $t:ident: $($i:ident)*) => {$(
+		impl<const N: u32> BitsIndex<U<$t, N>> for $i {
+			#[inline]
+			fn bit(v: U<$t, N>, i: Self) -> bool {
+				let i = u32::try_from(i).unwrap_or(N);
+				check!(i < N, invalid_bit_index);
+				v.0.bit(i)
+			}
+
+			#[inline]
+			fn bits<R>(v: U<$t, N>, range: R) -> <U<$t, N> as Bits>::Bits
+			where
+				R: RangeBounds<Self>,
+			{
+				let (start, end) = bounds(&u32_bounds(&range), N);
+				v.0.bits(start..end)
+			}
+
+			#[inline]
+			fn set_bit(v: &mut U<$t, N>, i: Self, bit: bool) {
+				let i = u32::try_from(i).unwrap_or(N);
+				check!(i < N, invalid_bit_index);
+				v.0.set_bit(i, bit)
+			}
+
+			#[inline]
+			fn set_bits<R>(v: &mut U<$t, N>, range: R, bits: <U<$t, N> as Bits>::Bits)
+			where
+				R: RangeBounds<Self>,
+			{
+				let (start, end) = bounds(&u32_bounds(&range), N);
+				v.0.set_bits(start..end, bits)
+			}
+		}
+	)*};
+}
+
+narrow!(
+	u8:
+		u1 1 u2 2 u3 3 u4 4 u5 5 u6 6 u7 7;
+	u16:
+		u9 9 u10 10 u11 11 u12 12 u13 13 u14 14 u15 15;
+	u32:
+		u17 17 u18 18 u19 19 u20 20 u21 21 u22 22 u23 23 u24 24
+		u25 25 u26 26 u27 27 u28 28 u29 29 u30 30 u31 31;
+	u64:
+		u33 33 u34 34 u35 35 u36 36 u37 37 u38 38 u39 39 u40 40
+		u41 41 u42 42 u43 43 u44 44 u45 45 u46 46 u47 47 u48 48
+		u49 49 u50 50 u51 51 u52 52 u53 53 u54 54 u55 55 u56 56
+		u57 57 u58 58 u59 59 u60 60 u61 61 u62 62 u63 63;
+);
+
+#[cfg(feature = "impl-128")]
+narrow!(
+	u128:
+		u65 65 u66 66 u67 67 u68 68 u69 69 u70 70 u71 71 u72 72
+		u73 73 u74 74 u75 75 u76 76 u77 77 u78 78 u79 79 u80 80
+		u81 81 u82 82 u83 83 u84 84 u85 85 u86 86 u87 87 u88 88
+		u89 89 u90 90 u91 91 u92 92 u93 93 u94 94 u95 95 u96 96
+		u97 97 u98 98 u99 99 u100 100 u101 101 u102 102 u103 103 u104 104
+		u105 105 u106 106 u107 107 u108 108 u109 109 u110 110 u111 111 u112 112
+		u113 113 u114 114 u115 115 u116 116 u117 117 u118 118 u119 119 u120 120
+		u121 121 u122 122 u123 123 u124 124 u125 125 u126 126 u127 127;
+);
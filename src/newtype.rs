@@ -0,0 +1,171 @@
+/// Implement [`Bits`][crate::Bits] for a newtype around a primitive integer.
+///
+/// `impl_bits!(MyReg => u32)` requires `MyReg` to be a tuple struct with a
+/// single `u32` field, deriving `Clone`, `Copy`, `PartialEq`, `Eq`, `Hash` and
+/// `Debug`. All methods forward to the inner integer, including the
+/// [`BitsIndex`][crate::BitsIndex] implementations for all primitive index
+/// types enabled by the `impl-128` and `impl-signed` features.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{impl_bits, Bits};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// struct Status(u32);
+///
+/// impl_bits!(Status => u32);
+///
+/// let mut s = Status(0x0F00);
+/// s.set_bits(0..4, 5);
+/// assert_eq!(s.bits(8..12), 0xF);
+/// assert_eq!(s.with_bit(31, true), Status(0x8000_0F05));
+/// ```
+#[macro_export]
+macro_rules! impl_bits {
+	(@index $t:ty => $inner:ty: $($i:ident)*) => {$(
+		impl $crate::BitsIndex<$t> for $i {
+			#[inline]
+			fn bit(v: $t, i: Self) -> bool {
+				<$i as $crate::BitsIndex<$inner>>::bit(v.0, i)
+			}
+			#[inline]
+			fn bits<R>(v: $t, range: R) -> <$inner as $crate::Bits>::Bits
+			where
+				R: ::core::ops::RangeBounds<Self>,
+			{
+				<$i as $crate::BitsIndex<$inner>>::bits(v.0, range)
+			}
+			#[inline]
+			fn set_bit(v: &mut $t, i: Self, bit: bool) {
+				<$i as $crate::BitsIndex<$inner>>::set_bit(&mut v.0, i, bit)
+			}
+			#[inline]
+			fn set_bits<R>(v: &mut $t, range: R, bits: <$inner as $crate::Bits>::Bits)
+			where
+				R: ::core::ops::RangeBounds<Self>,
+			{
+				<$i as $crate::BitsIndex<$inner>>::set_bits(&mut v.0, range, bits)
+			}
+		}
+	)*};
+	($t:ty => $inner:ty) => {
+		impl $crate::Bits for $t {
+			type Bits = <$inner as $crate::Bits>::Bits;
+			const N_BITS: u32 = <$inner as $crate::Bits>::N_BITS;
+			#[inline]
+			fn bit<I>(self, i: I) -> bool
+			where
+				I: $crate::BitsIndex<Self>,
+			{
+				I::bit(self, i)
+			}
+			#[inline]
+			fn bits<I, R>(self, range: R) -> Self::Bits
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I>,
+			{
+				I::bits(self, range)
+			}
+			#[inline]
+			fn set_bit<I>(&mut self, i: I, bit: bool)
+			where
+				I: $crate::BitsIndex<Self>,
+			{
+				I::set_bit(self, i, bit)
+			}
+			#[inline]
+			fn set_bits<I, R>(&mut self, range: R, bits: Self::Bits)
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I>,
+			{
+				I::set_bits(self, range, bits)
+			}
+			#[inline]
+			fn with_bit<I>(mut self, i: I, bit: bool) -> Self
+			where
+				I: $crate::BitsIndex<Self>,
+			{
+				I::set_bit(&mut self, i, bit);
+				self
+			}
+			#[inline]
+			fn with_bits<I, R>(mut self, range: R, bits: Self::Bits) -> Self
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I>,
+			{
+				I::set_bits(&mut self, range, bits);
+				self
+			}
+			#[inline]
+			fn bits_be<I, R>(self, range: R) -> Self::Bits
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I>,
+			{
+				I::bits(Self(self.0.swap_bytes()), range)
+			}
+			#[inline]
+			fn set_bits_be<I, R>(&mut self, range: R, bits: Self::Bits)
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I>,
+			{
+				let mut swapped = Self(self.0.swap_bytes());
+				I::set_bits(&mut swapped, range, bits);
+				self.0 = swapped.0.swap_bytes();
+			}
+			// The remaining methods move the field to the lowest bits of the
+			// inner type, and use its implementation from there.
+			#[inline]
+			fn bits_q<I, R>(self, range: R, frac_bits: u32) -> f32
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I> + Clone,
+			{
+				let width = I::bits(Self(!0), range.clone()).count_ones();
+				$crate::Bits::bits_q(I::bits(self, range), 0..width, frac_bits)
+			}
+			#[inline]
+			fn bits_uq<I, R>(self, range: R, frac_bits: u32) -> f32
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I>,
+			{
+				$crate::Bits::bits_uq(I::bits(self, range), 0u32.., frac_bits)
+			}
+			#[inline]
+			fn bits_q_scaled<I, R>(self, range: R, frac_bits: u32, scale: i64) -> i64
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I> + Clone,
+			{
+				let width = I::bits(Self(!0), range.clone()).count_ones();
+				$crate::Bits::bits_q_scaled(I::bits(self, range), 0..width, frac_bits, scale)
+			}
+			#[inline]
+			fn bits_ones_complement<I, R>(self, range: R) -> i64
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I> + Clone,
+			{
+				let width = I::bits(Self(!0), range.clone()).count_ones();
+				$crate::Bits::bits_ones_complement(I::bits(self, range), 0..width)
+			}
+			#[inline]
+			fn bits_sign_magnitude<I, R>(self, range: R) -> i64
+			where
+				I: $crate::BitsIndex<Self>,
+				R: ::core::ops::RangeBounds<I> + Clone,
+			{
+				let width = I::bits(Self(!0), range.clone()).count_ones();
+				$crate::Bits::bits_sign_magnitude(I::bits(self, range), 0..width)
+			}
+		}
+
+		$crate::__for_index_types!($crate::impl_bits; @index $t => $inner:);
+	};
+}
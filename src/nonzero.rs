@@ -0,0 +1,74 @@
+use super::{Bits, BitsIndex};
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+use core::num::NonZeroI128;
+#[cfg(feature = "impl-128")]
+use core::num::NonZeroU128;
+#[cfg(feature = "impl-signed")]
+use core::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize};
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+use core::ops::RangeBounds;
+
+// The setters panic if the result would be zero.
+macro_rules! nonzero {
+	($($nz:ident $t:ident $ut:ident),*) => {$(
+		forward_bits!([] $nz, $ut, <$t as Bits>::N_BITS, |v| match $nz::new(v.get().swap_bytes()) {
+			Some(v) => v,
+			None => unreachable!(),
+		}, match $nz::new(!0) {
+			Some(v) => v,
+			None => unreachable!(),
+		});
+
+		impl<I: BitsIndex<$t>> BitsIndex<$nz> for I {
+			#[inline]
+			fn bit(v: $nz, i: Self) -> bool {
+				<I as BitsIndex<$t>>::bit(v.get(), i)
+			}
+
+			#[inline]
+			fn bits<R>(v: $nz, range: R) -> $ut
+			where
+				R: RangeBounds<Self>,
+			{
+				<I as BitsIndex<$t>>::bits(v.get(), range)
+			}
+
+			#[inline]
+			fn set_bit(v: &mut $nz, i: Self, bit: bool) {
+				let mut x = v.get();
+				<I as BitsIndex<$t>>::set_bit(&mut x, i, bit);
+				*v = $nz::new(x).unwrap_or_else(|| crate::panic::result_is_zero());
+			}
+
+			#[inline]
+			fn set_bits<R>(v: &mut $nz, range: R, bits: $ut)
+			where
+				R: RangeBounds<Self>,
+			{
+				let mut x = v.get();
+				<I as BitsIndex<$t>>::set_bits(&mut x, range, bits);
+				*v = $nz::new(x).unwrap_or_else(|| crate::panic::result_is_zero());
+			}
+		}
+	)*};
+}
+
+nonzero!(
+	NonZeroU8 u8 u8,
+	NonZeroU16 u16 u16,
+	NonZeroU32 u32 u32,
+	NonZeroU64 u64 u64,
+	NonZeroUsize usize usize
+);
+#[cfg(feature = "impl-signed")]
+nonzero!(
+	NonZeroI8 i8 u8,
+	NonZeroI16 i16 u16,
+	NonZeroI32 i32 u32,
+	NonZeroI64 i64 u64,
+	NonZeroIsize isize usize
+);
+#[cfg(feature = "impl-128")]
+nonzero!(NonZeroU128 u128 u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+nonzero!(NonZeroI128 i128 u128);
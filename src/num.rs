@@ -0,0 +1,202 @@
+use super::range::bounds;
+#[cfg(feature = "impl-128")]
+use super::WideInt;
+use super::U;
+use core::ops::RangeBounds;
+use num_traits::{Bounded, PrimInt, ToPrimitive};
+
+/// Access to individual bits of any [`PrimInt`] type.
+///
+/// This offers the same functionality as [`Bits`][crate::Bits], but for code
+/// that is generic over `num_traits::PrimInt`. Indices are `u32`, and
+/// ranges of bits are returned in the same type as the integer itself.
+///
+/// For concrete types, use [`Bits`][crate::Bits] instead. Having both traits
+/// in scope makes calls on primitive integers ambiguous.
+///
+/// This is only available with the `num-traits` feature.
+///
+/// # Example
+///
+/// ```
+/// use intbits::PrimIntBits;
+/// use num_traits::PrimInt;
+///
+/// fn high_nibble<T: PrimInt>(x: T) -> T {
+///     x.bits(T::zero().count_zeros() - 4..)
+/// }
+///
+/// assert_eq!(high_nibble(0xA0u8), 0xA);
+/// assert_eq!(high_nibble(-1i64), 0xF);
+/// ```
+pub trait PrimIntBits: PrimInt {
+	/// Get a specific bit.
+	///
+	/// See [`Bits::bit`][crate::Bits::bit].
+	fn bit(self, i: u32) -> bool;
+
+	/// Get a range of bits.
+	///
+	/// See [`Bits::bits`][crate::Bits::bits].
+	fn bits<R: RangeBounds<u32>>(self, range: R) -> Self;
+
+	/// Set a specific bit.
+	///
+	/// See [`Bits::set_bit`][crate::Bits::set_bit].
+	fn set_bit(&mut self, i: u32, bit: bool);
+
+	/// Set a range of bits.
+	///
+	/// See [`Bits::set_bits`][crate::Bits::set_bits].
+	fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: Self);
+
+	/// Get a new integer with one bit set to a specific value.
+	///
+	/// See [`Bits::with_bit`][crate::Bits::with_bit].
+	fn with_bit(self, i: u32, bit: bool) -> Self;
+
+	/// Get a new integer with a range of bits set to specific values.
+	///
+	/// See [`Bits::with_bits`][crate::Bits::with_bits].
+	fn with_bits<R: RangeBounds<u32>>(self, range: R, bits: Self) -> Self;
+}
+
+/// The number of bits of `T`.
+#[inline]
+fn n_bits<T: PrimInt>() -> u32 {
+	T::zero().count_zeros()
+}
+
+/// The shift and mask (before shifting) of a range of bits.
+#[inline]
+fn shift_mask<T: PrimInt, R: RangeBounds<u32>>(range: &R) -> Option<(u32, T)> {
+	let n = n_bits::<T>();
+	let (start, end) = bounds(range, n);
+	if start == end {
+		None
+	} else {
+		Some((start, (!T::zero()).unsigned_shr(n - (end - start))))
+	}
+}
+
+impl<T: PrimInt> PrimIntBits for T {
+	#[inline]
+	fn bit(self, i: u32) -> bool {
+		check!(i < n_bits::<T>(), invalid_bit_index);
+		self.unsigned_shr(i) & T::one() != T::zero()
+	}
+
+	#[inline]
+	fn bits<R: RangeBounds<u32>>(self, range: R) -> Self {
+		match shift_mask::<T, R>(&range) {
+			Some((shift, mask)) => self.unsigned_shr(shift) & mask,
+			None => T::zero(),
+		}
+	}
+
+	#[inline]
+	fn set_bit(&mut self, i: u32, bit: bool) {
+		check!(i < n_bits::<T>(), invalid_bit_index);
+		let b = T::one().unsigned_shl(i);
+		*self = if bit { *self | b } else { *self & !b };
+	}
+
+	#[inline]
+	fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: Self) {
+		let (shift, mask) = shift_mask::<T, R>(&range).unwrap_or((0, T::zero()));
+		if bits & !mask != T::zero() {
+			crate::panic::bits_outside_range();
+		}
+		*self = *self & !mask.unsigned_shl(shift) | bits.unsigned_shl(shift);
+	}
+
+	#[inline]
+	fn with_bit(mut self, i: u32, bit: bool) -> Self {
+		self.set_bit(i, bit);
+		self
+	}
+
+	#[inline]
+	fn with_bits<R: RangeBounds<u32>>(mut self, range: R, bits: Self) -> Self {
+		self.set_bits(range, bits);
+		self
+	}
+}
+
+macro_rules! narrow_num {
+	($($t:ident),*) => {$(
+		impl<const N: u32> Bounded for U<$t, N> {
+			#[inline]
+			fn min_value() -> Self {
+				Self::MIN
+			}
+
+			#[inline]
+			fn max_value() -> Self {
+				Self::MAX
+			}
+		}
+
+		impl<const N: u32> ToPrimitive for U<$t, N> {
+			#[inline]
+			fn to_i64(&self) -> Option<i64> {
+				self.get().to_i64()
+			}
+
+			#[inline]
+			fn to_u64(&self) -> Option<u64> {
+				self.get().to_u64()
+			}
+
+			#[inline]
+			fn to_i128(&self) -> Option<i128> {
+				self.get().to_i128()
+			}
+
+			#[inline]
+			fn to_u128(&self) -> Option<u128> {
+				self.get().to_u128()
+			}
+		}
+	)*};
+}
+
+narrow_num!(u8, u16, u32, u64);
+#[cfg(feature = "impl-128")]
+narrow_num!(u128);
+
+#[cfg(feature = "impl-128")]
+impl<const WORDS: usize> Bounded for WideInt<WORDS> {
+	#[inline]
+	fn min_value() -> Self {
+		Self::ZERO
+	}
+
+	#[inline]
+	fn max_value() -> Self {
+		Self::MAX
+	}
+}
+
+#[cfg(feature = "impl-128")]
+impl<const WORDS: usize> ToPrimitive for WideInt<WORDS> {
+	#[inline]
+	fn to_i64(&self) -> Option<i64> {
+		self.to_u128()?.to_i64()
+	}
+
+	#[inline]
+	fn to_u64(&self) -> Option<u64> {
+		self.to_u128()?.to_u64()
+	}
+
+	#[inline]
+	fn to_u128(&self) -> Option<u128> {
+		let words = self.words();
+		if words.iter().skip(2).any(|&w| w != 0) {
+			return None;
+		}
+		let word = |i: usize| words.get(i).copied().unwrap_or(0) as u128;
+		Some(word(0) | word(1) << 64)
+	}
+}
@@ -0,0 +1,152 @@
+/// Conversion from the bits of an integer of type `T`.
+///
+/// Usually derived using `#[derive(FromBits)]` (requires the `derive`
+/// feature). See [`IntoBits`] for an example.
+pub trait FromBits<T> {
+	/// Unpack a value from the bits of `bits`.
+	fn from_bits(bits: T) -> Self;
+}
+
+/// Conversion into the bits of an integer of type `T`.
+///
+/// Usually derived using `#[derive(IntoBits)]` (requires the `derive`
+/// feature).
+///
+/// The struct needs a `#[bits(T)]` attribute specifying the underlying integer
+/// type, and every field needs a `#[bits(..)]` attribute with either a single
+/// bit index (for `bool` fields) or a range of bits (for unsigned integer
+/// fields).
+///
+/// Overlapping fields, fields outside of the range of `T`, fields too wide for
+/// their type, and field types wider than `T` are rejected at compile time. When packing, a field
+/// value that does not fit in its range results in a panic, just like
+/// [`Bits::set_bits`][crate::Bits::set_bits].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use intbits::{FromBits, IntoBits};
+///
+/// #[derive(FromBits, IntoBits, Debug, PartialEq)]
+/// #[bits(u16)]
+/// struct Header {
+///     #[bits(0..4)]
+///     version: u8,
+///     #[bits(4..=11)]
+///     length: u8,
+///     #[bits(15)]
+///     last: bool,
+/// }
+///
+/// let h = Header { version: 4, length: 0x2A, last: true };
+/// assert_eq!(h.into_bits(), 0x82A4);
+/// assert_eq!(Header::from_bits(0x82A4), Header { version: 4, length: 0x2A, last: true });
+/// # }
+/// ```
+pub trait IntoBits<T> {
+	/// Pack `self` into the bits of an integer.
+	fn into_bits(self) -> T;
+}
+
+#[doc(hidden)]
+pub mod __private {
+	use core::convert::TryInto;
+	use core::ops::{Bound, RangeBounds};
+
+	pub fn map_bounds<I, R: RangeBounds<I>>(
+		range: &R,
+		f: impl Fn(&I) -> u32,
+	) -> (Bound<u32>, Bound<u32>) {
+		let map = |b: Bound<&I>| match b {
+			Bound::Included(i) => Bound::Included(f(i)),
+			Bound::Excluded(i) => Bound::Excluded(f(i)),
+			Bound::Unbounded => Bound::Unbounded,
+		};
+		(map(range.start_bound()), map(range.end_bound()))
+	}
+
+	pub const fn check_layout(n_bits: u32, fields: &[(u32, u32)]) {
+		let mut i = 0;
+		while i < fields.len() {
+			let (start, end) = fields[i];
+			if start >= end {
+				panic!("empty or inverted bit field");
+			}
+			if end > n_bits {
+				panic!("bit field out of range");
+			}
+			let mut j = 0;
+			while j < i {
+				let (s, e) = fields[j];
+				if start < e && s < end {
+					panic!("overlapping bit fields");
+				}
+				j += 1;
+			}
+			i += 1;
+		}
+	}
+
+	pub const fn check_field_width(width: u32, n_bits: u32) {
+		if width > n_bits {
+			panic!("bit field wider than field type");
+		}
+	}
+
+	pub const fn check_field_type(field_bits: u32, n_bits: u32) {
+		if field_bits > n_bits {
+			panic!("field type wider than underlying integer type");
+		}
+	}
+
+	#[inline]
+	pub fn field_bits<T: TryInto<U>, U>(value: T) -> U {
+		match value.try_into() {
+			Ok(bits) => bits,
+			Err(_) => crate::panic::bits_outside_range(),
+		}
+	}
+}
+
+// Implements `BitsIndex` for an enum derived with `#[derive(BitsIndex)]`, for
+// every integer type enabled in this crate. The enum has an inherent
+// `__intbits_index` method that gives the bit index of a variant.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_enum_index {
+	($name:ident) => {
+		$crate::__for_int_types!($crate::__impl_enum_index; @impl $name:);
+	};
+	(@impl $name:ident: $($t:ident)*) => {$(
+		impl $crate::BitsIndex<$t> for $name {
+			#[inline]
+			fn bit(value: $t, index: Self) -> bool {
+				<u32 as $crate::BitsIndex<$t>>::bit(value, index.__intbits_index())
+			}
+
+			#[inline]
+			fn bits<R>(value: $t, range: R) -> <$t as $crate::Bits>::Bits
+			where
+				R: ::core::ops::RangeBounds<Self>,
+			{
+				let range = $crate::__private::map_bounds(&range, Self::__intbits_index);
+				<u32 as $crate::BitsIndex<$t>>::bits(value, range)
+			}
+
+			#[inline]
+			fn set_bit(value: &mut $t, index: Self, bit: bool) {
+				<u32 as $crate::BitsIndex<$t>>::set_bit(value, index.__intbits_index(), bit)
+			}
+
+			#[inline]
+			fn set_bits<R>(value: &mut $t, range: R, bits: <$t as $crate::Bits>::Bits)
+			where
+				R: ::core::ops::RangeBounds<Self>,
+			{
+				let range = $crate::__private::map_bounds(&range, Self::__intbits_index);
+				<u32 as $crate::BitsIndex<$t>>::set_bits(value, range, bits)
+			}
+		}
+	)*};
+}
@@ -0,0 +1,221 @@
+use super::{BitOrder, SliceBits};
+use core::iter::FusedIterator;
+
+/// A sequence of `SYMBOL_BITS` bit symbols, packed tightly into a byte buffer.
+///
+/// Like [`BitCursor`][crate::BitCursor], this wraps any buffer: `&[u8]` for
+/// reading, or `&mut [u8]` (or an owned buffer) for reading and writing.
+/// Symbol `i` occupies bits `i * SYMBOL_BITS..(i + 1) * SYMBOL_BITS` of the
+/// buffer, in the given bit order, like with [`pack_bits`][crate::pack_bits].
+///
+/// Symbols of 1, 2, 4 or 8 bits never cross a byte boundary, and are
+/// accessed with a single shift and mask. Other widths up to 32 bits are
+/// supported as well.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, PackedSeq};
+///
+/// // The nucleotides GATTACA, two bits each.
+/// let mut dna = PackedSeq::<_, 2>::with_len([0u8; 2], 7, BitOrder::MsbFirst);
+/// for (i, &base) in [2, 0, 3, 3, 0, 1, 0].iter().enumerate() {
+///     dna.set(i, base);
+/// }
+/// assert_eq!(dna.get_ref(), &[0b10_00_11_11, 0b00_01_00_00]);
+/// assert_eq!(dna.get(5), 1);
+/// assert!(dna.iter().eq([2, 0, 3, 3, 0, 1, 0]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PackedSeq<B, const SYMBOL_BITS: u32> {
+	inner: B,
+	len: usize,
+	order: BitOrder,
+}
+
+impl<B, const SYMBOL_BITS: u32> PackedSeq<B, SYMBOL_BITS> {
+	const VALID: () = assert!(SYMBOL_BITS > 0 && SYMBOL_BITS <= 32, "invalid symbol width");
+
+	/// Get the underlying buffer back.
+	pub fn into_inner(self) -> B {
+		self.inner
+	}
+
+	/// Get a reference to the underlying buffer.
+	pub fn get_ref(&self) -> &B {
+		&self.inner
+	}
+
+	/// Get a mutable reference to the underlying buffer.
+	pub fn get_mut(&mut self) -> &mut B {
+		&mut self.inner
+	}
+
+	/// The bit order of this sequence.
+	pub fn order(&self) -> BitOrder {
+		self.order
+	}
+
+	/// The number of symbols.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Check whether there are no symbols.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+}
+
+impl<B: AsRef<[u8]>, const SYMBOL_BITS: u32> PackedSeq<B, SYMBOL_BITS> {
+	/// Create a sequence of as many symbols as fit in `inner`.
+	pub fn new(inner: B, order: BitOrder) -> Self {
+		#[allow(clippy::let_unit_value)]
+		let () = Self::VALID;
+		let len = inner.as_ref().len() * 8 / SYMBOL_BITS as usize;
+		Self { inner, len, order }
+	}
+
+	/// Create a sequence of `len` symbols, stored at the start of `inner`.
+	///
+	/// Panics if `inner` is too small.
+	pub fn with_len(inner: B, len: usize, order: BitOrder) -> Self {
+		let seq = Self::new(inner, order);
+		check!(len <= seq.len, buffer_too_small);
+		Self { len, ..seq }
+	}
+
+	/// Get the symbol at index `i`.
+	///
+	/// Panics if the index is out of range.
+	#[inline]
+	pub fn get(&self, i: usize) -> u32 {
+		check!(i < self.len, invalid_symbol_index);
+		get::<SYMBOL_BITS>(self.inner.as_ref(), i, self.order)
+	}
+
+	/// Iterate over the symbols, in order.
+	#[inline]
+	pub fn iter(&self) -> PackedSeqIter<'_, SYMBOL_BITS> {
+		PackedSeqIter {
+			buf: self.inner.as_ref(),
+			front: 0,
+			back: self.len,
+			order: self.order,
+		}
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>, const SYMBOL_BITS: u32> PackedSeq<B, SYMBOL_BITS> {
+	/// Set the symbol at index `i`.
+	///
+	/// Panics if the index is out of range, or if the symbol doesn't fit in
+	/// `SYMBOL_BITS` bits.
+	#[inline]
+	pub fn set(&mut self, i: usize, symbol: u32) {
+		check!(i < self.len, invalid_symbol_index);
+		check!(symbol >> (SYMBOL_BITS - 1) >> 1 == 0, bits_outside_range);
+		let buf = self.inner.as_mut();
+		if 8 % SYMBOL_BITS == 0 {
+			let shift = shift::<SYMBOL_BITS>(i, self.order);
+			let mask = mask::<SYMBOL_BITS>() << shift;
+			let byte = &mut buf[i * SYMBOL_BITS as usize / 8];
+			*byte = *byte & !mask | (symbol as u8) << shift;
+		} else {
+			let start = i * SYMBOL_BITS as usize;
+			buf.set_bits(
+				start..start + SYMBOL_BITS as usize,
+				symbol as u128,
+				self.order,
+			);
+		}
+	}
+}
+
+impl<'a, B: AsRef<[u8]>, const SYMBOL_BITS: u32> IntoIterator for &'a PackedSeq<B, SYMBOL_BITS> {
+	type Item = u32;
+	type IntoIter = PackedSeqIter<'a, SYMBOL_BITS>;
+
+	#[inline]
+	fn into_iter(self) -> PackedSeqIter<'a, SYMBOL_BITS> {
+		self.iter()
+	}
+}
+
+// The mask of a symbol, for widths that divide 8.
+#[inline]
+fn mask<const SYMBOL_BITS: u32>() -> u8 {
+	(0xFFu16 >> (8 - SYMBOL_BITS)) as u8
+}
+
+// The shift of symbol `i` within its byte, for widths that divide 8.
+#[inline]
+fn shift<const SYMBOL_BITS: u32>(i: usize, order: BitOrder) -> u32 {
+	let offset = (i * SYMBOL_BITS as usize % 8) as u32;
+	match order {
+		BitOrder::LsbFirst => offset,
+		BitOrder::MsbFirst => 8 - offset - SYMBOL_BITS,
+	}
+}
+
+#[inline]
+fn get<const SYMBOL_BITS: u32>(buf: &[u8], i: usize, order: BitOrder) -> u32 {
+	if 8 % SYMBOL_BITS == 0 {
+		let byte = buf[i * SYMBOL_BITS as usize / 8];
+		(byte >> shift::<SYMBOL_BITS>(i, order) & mask::<SYMBOL_BITS>()) as u32
+	} else {
+		let start = i * SYMBOL_BITS as usize;
+		buf.bits(start..start + SYMBOL_BITS as usize, order) as u32
+	}
+}
+
+/// An iterator over the symbols of a [`PackedSeq`].
+///
+/// Created by [`PackedSeq::iter`].
+#[derive(Clone, Debug)]
+pub struct PackedSeqIter<'a, const SYMBOL_BITS: u32> {
+	buf: &'a [u8],
+	front: usize,
+	back: usize,
+	order: BitOrder,
+}
+
+impl<const SYMBOL_BITS: u32> Iterator for PackedSeqIter<'_, SYMBOL_BITS> {
+	type Item = u32;
+
+	#[inline]
+	fn next(&mut self) -> Option<u32> {
+		if self.front == self.back {
+			return None;
+		}
+		self.front += 1;
+		Some(get::<SYMBOL_BITS>(self.buf, self.front - 1, self.order))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let n = self.back - self.front;
+		(n, Some(n))
+	}
+
+	#[inline]
+	fn nth(&mut self, n: usize) -> Option<u32> {
+		self.front = self.front.saturating_add(n).min(self.back);
+		self.next()
+	}
+}
+
+impl<const SYMBOL_BITS: u32> DoubleEndedIterator for PackedSeqIter<'_, SYMBOL_BITS> {
+	#[inline]
+	fn next_back(&mut self) -> Option<u32> {
+		if self.front == self.back {
+			return None;
+		}
+		self.back -= 1;
+		Some(get::<SYMBOL_BITS>(self.buf, self.back, self.order))
+	}
+}
+
+impl<const SYMBOL_BITS: u32> ExactSizeIterator for PackedSeqIter<'_, SYMBOL_BITS> {}
+
+impl<const SYMBOL_BITS: u32> FusedIterator for PackedSeqIter<'_, SYMBOL_BITS> {}
@@ -0,0 +1,44 @@
+// Out-of-line panics, one per message.
+//
+// Keeping the panic machinery out of the (inlined) hot paths keeps the code
+// small at every call site. Not every function is used with every set of
+// features.
+
+macro_rules! panics {
+	($($name:ident: $msg:literal,)*) => {$(
+		#[cold]
+		#[inline(never)]
+		#[allow(dead_code)]
+		pub(crate) fn $name() -> ! {
+			panic!($msg)
+		}
+	)*};
+}
+
+panics! {
+	invalid_bit_index: "invalid bit index",
+	invalid_bit_range: "invalid bit range",
+	bits_outside_range: "bits outside range",
+	bit_range_too_wide: "bit range too wide",
+	buffer_too_small: "buffer too small",
+	buffer_length_mismatch: "buffers must be of the same length",
+	result_is_zero: "result is zero",
+	invalid_fractional_bits: "invalid number of fractional bits",
+	fixed_point_out_of_range: "fixed-point value out of range",
+	invalid_bitplanes: "invalid number of bitplanes",
+	shift_out_of_range: "shift out of range",
+	invalid_bit_width: "invalid bit width",
+	overlapping_bit_ranges: "overlapping bit ranges",
+	invalid_prbs_seed: "invalid PRBS seed",
+	invalid_control_symbol: "invalid control symbol",
+	invalid_symbol_index: "invalid symbol index",
+}
+
+// Like `assert!`, but panics through one of the functions above.
+macro_rules! check {
+	($cond:expr, $panic:ident) => {
+		if !$cond {
+			$crate::panic::$panic();
+		}
+	};
+}
@@ -0,0 +1,127 @@
+use super::{Bin, U};
+use core::fmt;
+use core::str::FromStr;
+
+/// The error returned when parsing a binary string fails.
+///
+/// Positions are byte offsets into the original string, including the `0b`
+/// prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ParseBinError {
+	/// The string contains no binary digits.
+	Empty,
+	/// The character at this position is not `0`, `1` or `_`.
+	InvalidDigit { position: usize },
+	/// The value doesn't fit in the type. The digit at this position is the
+	/// first one that doesn't fit.
+	TooLong { position: usize },
+}
+
+impl fmt::Display for ParseBinError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ParseBinError::Empty => write!(f, "no binary digits"),
+			ParseBinError::InvalidDigit { position } => {
+				write!(f, "invalid binary digit at position {}", position)
+			}
+			ParseBinError::TooLong { position } => {
+				write!(f, "too many bits at position {}", position)
+			}
+		}
+	}
+}
+
+/// Parse a binary string of at most `n_bits` bits.
+pub(crate) fn parse_bin(s: &str, n_bits: u32) -> Result<u128, ParseBinError> {
+	let offset = if s.starts_with("0b") { 2 } else { 0 };
+	let mut value = 0u128;
+	let mut digits = false;
+	for (i, c) in s[offset..].char_indices() {
+		let position = offset + i;
+		let bit = match c {
+			'0' => 0,
+			'1' => 1,
+			'_' => continue,
+			_ => return Err(ParseBinError::InvalidDigit { position }),
+		};
+		if value >> (n_bits - 1) != 0 {
+			return Err(ParseBinError::TooLong { position });
+		}
+		value = value << 1 | bit;
+		digits = true;
+	}
+	if digits {
+		Ok(value)
+	} else {
+		Err(ParseBinError::Empty)
+	}
+}
+
+/// Parsing integers from binary strings, like `"0b1010_1100"`.
+///
+/// The `0b` prefix is optional, and underscores can be used anywhere to
+/// separate digits. Signed integers are parsed from their two's complement
+/// representation.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{FromBinStr, ParseBinError};
+///
+/// assert_eq!(u8::from_bin_str("0b1010_1100"), Ok(0xAC));
+/// assert_eq!(i8::from_bin_str("1111_1110"), Ok(-2));
+/// assert_eq!(
+///     u8::from_bin_str("0b1010_2100"),
+///     Err(ParseBinError::InvalidDigit { position: 7 })
+/// );
+/// assert_eq!(
+///     u8::from_bin_str("1_0000_0000"),
+///     Err(ParseBinError::TooLong { position: 10 })
+/// );
+/// ```
+pub trait FromBinStr: Sized {
+	/// Parse a binary string.
+	fn from_bin_str(s: &str) -> Result<Self, ParseBinError>;
+}
+
+macro_rules! from_bin_str {
+	($($t:ident $ut:ident),*) => {$(
+		impl FromBinStr for $t {
+			#[inline]
+			fn from_bin_str(s: &str) -> Result<Self, ParseBinError> {
+				parse_bin(s, $t::BITS).map(|v| v as $ut as $t)
+			}
+		}
+	)*};
+}
+
+from_bin_str! {
+	u8 u8, u16 u16, u32 u32, u64 u64, u128 u128, usize usize,
+	i8 u8, i16 u16, i32 u32, i64 u64, i128 u128, isize usize
+}
+
+macro_rules! narrow_from_bin_str {
+	($($t:ident),*) => {$(
+		impl<const N: u32> FromBinStr for U<$t, N> {
+			#[inline]
+			fn from_bin_str(s: &str) -> Result<Self, ParseBinError> {
+				parse_bin(s, N).map(|v| Self::new(v as $t))
+			}
+		}
+	)*};
+}
+
+narrow_from_bin_str!(u8, u16, u32, u64);
+#[cfg(feature = "impl-128")]
+narrow_from_bin_str!(u128);
+
+/// Parses a binary string using [`FromBinStr`], with the default grouping.
+impl<T: FromBinStr> FromStr for Bin<T> {
+	type Err = ParseBinError;
+
+	#[inline]
+	fn from_str(s: &str) -> Result<Self, ParseBinError> {
+		T::from_bin_str(s).map(Bin::new)
+	}
+}
@@ -0,0 +1,242 @@
+use super::{BitReader, BitSink, BitWriter, OutOfBits};
+use core::iter::FusedIterator;
+
+/// A standard pseudo-random binary sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PrbsPattern {
+	/// x⁷ + x⁶ + 1, with a period of 127 bits.
+	Prbs7,
+	/// x¹⁵ + x¹⁴ + 1, with a period of 32767 bits.
+	Prbs15,
+	/// x²³ + x¹⁸ + 1, with a period of 2²³ - 1 bits.
+	Prbs23,
+	/// x³¹ + x²⁸ + 1, with a period of 2³¹ - 1 bits.
+	Prbs31,
+}
+
+impl PrbsPattern {
+	/// The degree of the polynomial, which is the number of bits of state.
+	pub const fn order(self) -> u32 {
+		match self {
+			PrbsPattern::Prbs7 => 7,
+			PrbsPattern::Prbs15 => 15,
+			PrbsPattern::Prbs23 => 23,
+			PrbsPattern::Prbs31 => 31,
+		}
+	}
+
+	/// The degree of the middle term of the polynomial.
+	const fn tap(self) -> u32 {
+		match self {
+			PrbsPattern::Prbs7 => 6,
+			PrbsPattern::Prbs15 => 14,
+			PrbsPattern::Prbs23 => 18,
+			PrbsPattern::Prbs31 => 28,
+		}
+	}
+
+	/// The bits of the state.
+	const fn mask(self) -> u32 {
+		!0 >> (32 - self.order())
+	}
+
+	/// The next bit of the sequence after the bits in `state`, with the most
+	/// recent bit in the least significant bit.
+	#[inline]
+	fn feedback(self, state: u32) -> u32 {
+		(state >> (self.order() - 1) ^ state >> (self.tap() - 1)) & 1
+	}
+}
+
+/// Generates a pseudo-random binary sequence, using a linear feedback shift
+/// register.
+///
+/// Iterating over a generator gives the bits of the sequence, forever.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, BitWriter, PrbsGenerator, PrbsPattern};
+///
+/// let mut prbs = PrbsGenerator::new(PrbsPattern::Prbs7);
+/// let mut buf = [0; 4];
+/// prbs.write_to(&mut BitWriter::new(&mut buf, BitOrder::MsbFirst), 32).unwrap();
+/// assert_eq!(buf, [0x02, 0x0C, 0x28, 0xF2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrbsGenerator {
+	pattern: PrbsPattern,
+	state: u32,
+}
+
+impl PrbsGenerator {
+	/// Create a generator, starting with all bits of the state set.
+	pub fn new(pattern: PrbsPattern) -> Self {
+		Self {
+			pattern,
+			state: pattern.mask(),
+		}
+	}
+
+	/// Create a generator, starting with a specific state.
+	///
+	/// Panics if the state is zero, or has more bits than the
+	/// [`order`][PrbsPattern::order] of the pattern.
+	pub fn with_seed(pattern: PrbsPattern, seed: u32) -> Self {
+		check!(seed != 0 && seed & !pattern.mask() == 0, invalid_prbs_seed);
+		Self {
+			pattern,
+			state: seed,
+		}
+	}
+
+	/// The pattern of this generator.
+	pub fn pattern(&self) -> PrbsPattern {
+		self.pattern
+	}
+
+	/// The current state of the shift register, with the most recently
+	/// generated bit in the least significant bit.
+	pub fn state(&self) -> u32 {
+		self.state
+	}
+
+	/// Generate the next bit of the sequence.
+	#[inline]
+	pub fn next_bit(&mut self) -> bool {
+		let bit = self.pattern.feedback(self.state);
+		self.state = (self.state << 1 | bit) & self.pattern.mask();
+		bit != 0
+	}
+
+	/// Write the next `n` bits of the sequence.
+	///
+	/// Returns an error, without writing anything, if `n` bits don't fit.
+	pub fn write_to<S: BitSink>(
+		&mut self,
+		w: &mut BitWriter<S>,
+		n: usize,
+	) -> Result<(), OutOfBits> {
+		if n > w.remaining_bits() {
+			return Err(OutOfBits);
+		}
+		for _ in 0..n {
+			w.write_bit(self.next_bit())?;
+		}
+		Ok(())
+	}
+}
+
+impl Iterator for PrbsGenerator {
+	type Item = bool;
+
+	#[inline]
+	fn next(&mut self) -> Option<bool> {
+		Some(self.next_bit())
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(usize::MAX, None)
+	}
+}
+
+impl FusedIterator for PrbsGenerator {}
+
+/// Checks a received pseudo-random binary sequence, and counts bit errors.
+///
+/// The checker doesn't need to know where the sequence starts. It locks onto
+/// the sequence by loading the first [`order`][PrbsPattern::order] received
+/// bits into its state. From then on, it generates the sequence itself, and
+/// counts the received bits that differ. An error in the bits used to lock
+/// results in a high error rate. Use [`reset`][Self::reset] to lock again.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{PrbsChecker, PrbsGenerator, PrbsPattern};
+///
+/// let mut prbs = PrbsGenerator::new(PrbsPattern::Prbs15).skip(1000);
+/// let mut checker = PrbsChecker::new(PrbsPattern::Prbs15);
+/// for i in 0..10_000 {
+///     let bit = prbs.next().unwrap();
+///     checker.check_bit(bit ^ (i % 1000 == 999));
+/// }
+/// assert!(checker.is_locked());
+/// assert_eq!(checker.bit_errors(), 10);
+/// assert_eq!(checker.bits_checked(), 10_000 - 15);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrbsChecker {
+	pattern: PrbsPattern,
+	state: u32,
+	filled: u32,
+	bits: u64,
+	errors: u64,
+}
+
+impl PrbsChecker {
+	/// Create a checker that is not locked yet.
+	pub fn new(pattern: PrbsPattern) -> Self {
+		Self {
+			pattern,
+			state: 0,
+			filled: 0,
+			bits: 0,
+			errors: 0,
+		}
+	}
+
+	/// The pattern of this checker.
+	pub fn pattern(&self) -> PrbsPattern {
+		self.pattern
+	}
+
+	/// Whether enough bits were received to lock onto the sequence.
+	pub fn is_locked(&self) -> bool {
+		self.filled == self.pattern.order()
+	}
+
+	/// The number of bits that were checked since locking.
+	pub fn bits_checked(&self) -> u64 {
+		self.bits
+	}
+
+	/// The number of bits that were received incorrectly since locking.
+	pub fn bit_errors(&self) -> u64 {
+		self.errors
+	}
+
+	/// Unlock, and clear the counters.
+	pub fn reset(&mut self) {
+		*self = Self::new(self.pattern);
+	}
+
+	/// Check the next received bit.
+	///
+	/// Returns `false` if the bit was received incorrectly. Bits that are used
+	/// to lock onto the sequence are always correct.
+	#[inline]
+	pub fn check_bit(&mut self, bit: bool) -> bool {
+		let mask = self.pattern.mask();
+		if !self.is_locked() {
+			self.state = (self.state << 1 | bit as u32) & mask;
+			self.filled += 1;
+			return true;
+		}
+		let expected = self.pattern.feedback(self.state);
+		self.state = (self.state << 1 | expected) & mask;
+		self.bits += 1;
+		let ok = (expected != 0) == bit;
+		self.errors += !ok as u64;
+		ok
+	}
+
+	/// Check all remaining bits of a reader.
+	pub fn check_from(&mut self, r: &mut BitReader) {
+		while let Ok(bit) = r.read_bit() {
+			self.check_bit(bit);
+		}
+	}
+}
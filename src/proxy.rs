@@ -0,0 +1,184 @@
+use super::range::bounds;
+use super::{Bits, BitsIndex};
+use core::ops::{AddAssign, BitAndAssign, BitOrAssign, BitXorAssign, RangeBounds};
+
+/// A mutable view of a range of bits of an integer.
+///
+/// Created by [`Bits::bits_mut`]. Every operation is written to the integer
+/// immediately.
+///
+/// The compound assignment operators act on the value of the field: `|=`,
+/// `&=` and `^=` panic if the result doesn't fit in the field, like
+/// [`set_bits`][Bits::set_bits], while `+=` wraps around within the field.
+///
+/// # Example
+///
+/// ```
+/// use intbits::Bits;
+///
+/// let mut reg = 0x1234u16;
+/// let mut field = reg.bits_mut(4..8);
+/// field |= 0b1000;
+/// field += 9;
+/// assert_eq!(field.get(), 0x4);
+/// field.set(0xF);
+/// assert_eq!(reg, 0x12F4);
+/// ```
+pub struct BitsMut<'a, T> {
+	value: &'a mut T,
+	start: u32,
+	end: u32,
+}
+
+impl<'a, T> BitsMut<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[inline]
+	pub(crate) fn new<R: RangeBounds<u32>>(value: &'a mut T, range: R) -> Self {
+		let (start, end) = bounds(&range, T::N_BITS);
+		Self { value, start, end }
+	}
+
+	/// Get the value of the field.
+	#[inline]
+	pub fn get(&self) -> T::Bits {
+		self.value.bits(self.start..self.end)
+	}
+
+	/// Set the value of the field.
+	///
+	/// Panics if `bits` doesn't fit in the field.
+	#[inline]
+	pub fn set(&mut self, bits: T::Bits) {
+		self.value.set_bits(self.start..self.end, bits);
+	}
+
+	/// The bits of the field, in the lowest bits.
+	#[inline]
+	fn mask(&self) -> T::Bits {
+		let width = self.end - self.start;
+		if width == 0 {
+			T::Bits::default()
+		} else {
+			!T::Bits::default() >> (<T::Bits as Bits>::N_BITS - width)
+		}
+	}
+}
+
+impl<'a, T> BitOrAssign<T::Bits> for BitsMut<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[inline]
+	fn bitor_assign(&mut self, bits: T::Bits) {
+		self.set(self.get() | bits);
+	}
+}
+
+impl<'a, T> BitAndAssign<T::Bits> for BitsMut<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[inline]
+	fn bitand_assign(&mut self, bits: T::Bits) {
+		self.set(self.get() & bits);
+	}
+}
+
+impl<'a, T> BitXorAssign<T::Bits> for BitsMut<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[inline]
+	fn bitxor_assign(&mut self, bits: T::Bits) {
+		self.set(self.get() ^ bits);
+	}
+}
+
+impl<'a, T> AddAssign<T::Bits> for BitsMut<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[inline]
+	fn add_assign(&mut self, bits: T::Bits) {
+		// Wrapping addition using only bitwise operations, as `T::Bits`
+		// doesn't implement `Add`.
+		let zero = T::Bits::default();
+		let (mut sum, mut carry) = (self.get(), bits & self.mask());
+		while carry != zero {
+			let c = sum & carry;
+			sum = sum ^ carry;
+			carry = c << 1;
+		}
+		self.set(sum & self.mask());
+	}
+}
+
+/// A mutable reference to a single bit of an integer.
+///
+/// Created by [`Bits::bit_mut`]. Every operation is written to the integer
+/// immediately.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitRef, Bits};
+///
+/// fn arm(mut flag: BitRef<u32>) -> bool {
+///     flag.replace(true)
+/// }
+///
+/// let mut status = 0u32;
+/// assert!(!arm(status.bit_mut(5)));
+/// assert!(arm(status.bit_mut(5)));
+/// status.bit_mut(0).toggle();
+/// assert_eq!(status, 0x21);
+/// ```
+pub struct BitRef<'a, T> {
+	value: &'a mut T,
+	index: u32,
+}
+
+impl<'a, T> BitRef<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[inline]
+	pub(crate) fn new(value: &'a mut T, index: u32) -> Self {
+		check!(index < T::N_BITS, invalid_bit_index);
+		Self { value, index }
+	}
+
+	/// Get the bit.
+	#[inline]
+	pub fn get(&self) -> bool {
+		self.value.bit(self.index)
+	}
+
+	/// Set the bit.
+	#[inline]
+	pub fn set(&mut self, bit: bool) {
+		self.value.set_bit(self.index, bit);
+	}
+
+	/// Flip the bit.
+	#[inline]
+	pub fn toggle(&mut self) {
+		self.set(!self.get());
+	}
+
+	/// Set the bit, and return its previous value.
+	#[inline]
+	pub fn replace(&mut self, bit: bool) -> bool {
+		let old = self.get();
+		self.set(bit);
+		old
+	}
+}
@@ -0,0 +1,178 @@
+use super::OutOfBits;
+
+/// A first-in first-out queue of bits, stored in an integer.
+///
+/// Bits are pushed on top of the bits already in the queue, and popped from
+/// the bottom. At most as many bits as the integer has can be stored.
+///
+/// # Example
+///
+/// ```
+/// use intbits::BitQueue;
+///
+/// let mut q = BitQueue::<u32>::new();
+/// q.push_bits(0b101, 3).unwrap();
+/// q.push_bits(0xAB, 8).unwrap();
+/// assert_eq!(q.len_bits(), 11);
+/// assert_eq!(q.pop_bits(2), Ok(0b01));
+/// assert_eq!(q.pop_bits(9), Ok(0xAB << 1 | 1));
+/// assert!(q.is_empty());
+/// assert!(q.pop_bits(1).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitQueue<T> {
+	bits: T,
+	len: u32,
+}
+
+/// A last-in first-out stack of bits, stored in an integer.
+///
+/// Bits are pushed below the bits already on the stack, and popped from the
+/// bottom. At most as many bits as the integer has can be stored.
+///
+/// # Example
+///
+/// ```
+/// use intbits::BitStack;
+///
+/// let mut s = BitStack::<u16>::new();
+/// s.push_bits(0b101, 3).unwrap();
+/// s.push_bits(0xAB, 8).unwrap();
+/// assert_eq!(s.pop_bits(8), Ok(0xAB));
+/// assert_eq!(s.pop_bits(3), Ok(0b101));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitStack<T> {
+	bits: T,
+	len: u32,
+}
+
+macro_rules! queue {
+	($($t:ident),*) => {$(
+		queue!(@common BitQueue $t);
+		queue!(@common BitStack $t);
+
+		impl BitQueue<$t> {
+			/// Add the lowest `n` bits of `value` to the end of the queue.
+			///
+			/// Returns an error, and doesn't change the queue, if there is no
+			/// room for `n` more bits.
+			///
+			/// Panics if `n` is too large for the integer, or if `value` has
+			/// other bits set.
+			#[inline]
+			pub fn push_bits(&mut self, value: $t, n: u32) -> Result<(), OutOfBits> {
+				Self::check(value, n);
+				if n > $t::BITS - self.len {
+					return Err(OutOfBits);
+				}
+				self.bits |= value.checked_shl(self.len).unwrap_or(0);
+				self.len += n;
+				Ok(())
+			}
+		}
+
+		impl BitStack<$t> {
+			/// Push the lowest `n` bits of `value` onto the stack.
+			///
+			/// Returns an error, and doesn't change the stack, if there is no
+			/// room for `n` more bits.
+			///
+			/// Panics if `n` is too large for the integer, or if `value` has
+			/// other bits set.
+			#[inline]
+			pub fn push_bits(&mut self, value: $t, n: u32) -> Result<(), OutOfBits> {
+				Self::check(value, n);
+				if n > $t::BITS - self.len {
+					return Err(OutOfBits);
+				}
+				self.bits = self.bits.checked_shl(n).unwrap_or(0) | value;
+				self.len += n;
+				Ok(())
+			}
+		}
+	)*};
+	(@common $s:ident $t:ident) => {
+		impl $s<$t> {
+			/// Create an empty one.
+			#[inline]
+			pub const fn new() -> Self {
+				Self { bits: 0, len: 0 }
+			}
+
+			/// The number of bits currently stored.
+			#[inline]
+			pub const fn len_bits(&self) -> u32 {
+				self.len
+			}
+
+			/// The maximum number of bits that can be stored.
+			#[inline]
+			pub const fn capacity_bits(&self) -> u32 {
+				$t::BITS
+			}
+
+			/// Check if no bits are stored.
+			#[inline]
+			pub const fn is_empty(&self) -> bool {
+				self.len == 0
+			}
+
+			/// Remove all bits.
+			#[inline]
+			pub fn clear(&mut self) {
+				*self = Self::new();
+			}
+
+			/// Get the next `n` bits, without removing them.
+			///
+			/// The first bit that would be popped is the least significant bit.
+			///
+			/// Returns an error if fewer than `n` bits are stored.
+			///
+			/// Panics if `n` is too large for the integer.
+			#[inline]
+			pub fn peek_bits(&self, n: u32) -> Result<$t, OutOfBits> {
+				check!(n <= $t::BITS, invalid_bit_width);
+				if n > self.len {
+					return Err(OutOfBits);
+				}
+				Ok(self.bits & Self::mask(n))
+			}
+
+			/// Remove the next `n` bits, and return them.
+			///
+			/// The first bit that was popped is the least significant bit.
+			///
+			/// Returns an error, and doesn't change anything, if fewer than `n`
+			/// bits are stored.
+			///
+			/// Panics if `n` is too large for the integer.
+			#[inline]
+			pub fn pop_bits(&mut self, n: u32) -> Result<$t, OutOfBits> {
+				let value = self.peek_bits(n)?;
+				self.bits = self.bits.checked_shr(n).unwrap_or(0);
+				self.len -= n;
+				Ok(value)
+			}
+
+			/// The lowest `n` bits set.
+			#[inline]
+			fn mask(n: u32) -> $t {
+				if n == 0 {
+					0
+				} else {
+					!0 >> ($t::BITS - n)
+				}
+			}
+
+			#[inline]
+			fn check(value: $t, n: u32) {
+				check!(n <= $t::BITS, invalid_bit_width);
+				check!(value & !Self::mask(n) == 0, bits_outside_range);
+			}
+		}
+	};
+}
+
+queue!(u8, u16, u32, u64, u128, usize);
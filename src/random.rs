@@ -0,0 +1,89 @@
+use super::{merge_bits, Bits, BitsIndex};
+use rand::distr::{Distribution, StandardUniform};
+use rand::Rng;
+
+/// Generate a random value, with the bits in `mask` set to those of `value`.
+///
+/// All bits outside of `mask` are random. This is useful to generate test
+/// values for registers where some bits have to have a fixed value.
+///
+/// Panics when the bits of `value` outside of `mask` are not 0.
+///
+/// This is only available with the `rand` feature.
+///
+/// # Example
+///
+/// ```
+/// use intbits::random_with_pattern;
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let mut rng = SmallRng::seed_from_u64(1);
+/// for _ in 0..10 {
+///     let v: u32 = random_with_pattern(&mut rng, 0xF000_000F, 0xA000_0005);
+///     assert_eq!(v & 0xF000_000F, 0xA000_0005);
+/// }
+/// ```
+pub fn random_with_pattern<T, R>(rng: &mut R, mask: T::Bits, value: T::Bits) -> T
+where
+	T: Bits,
+	R: Rng + ?Sized,
+	u32: BitsIndex<T>,
+	StandardUniform: Distribution<T>,
+{
+	check!(value & !mask == T::Bits::default(), bits_outside_range);
+	let mut v: T = rng.random();
+	let random = v.bits(T::BIT_INDICES);
+	v.set_bits(T::BIT_INDICES, merge_bits(random, value, mask));
+	v
+}
+
+/// A distribution of values with exactly `k` bits set, all equally likely.
+///
+/// Panics when sampling a type with fewer than `k` bits.
+///
+/// This is only available with the `rand` feature.
+///
+/// # Example
+///
+/// ```
+/// use intbits::ExactOnes;
+/// use rand::{rngs::SmallRng, Rng, SeedableRng};
+///
+/// let mut rng = SmallRng::seed_from_u64(1);
+/// let v: u64 = rng.sample(ExactOnes::new(3));
+/// assert_eq!(v.count_ones(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExactOnes {
+	k: u32,
+}
+
+impl ExactOnes {
+	/// Create a distribution of values with exactly `k` bits set.
+	pub const fn new(k: u32) -> Self {
+		Self { k }
+	}
+
+	/// The number of bits that are set.
+	pub const fn k(&self) -> u32 {
+		self.k
+	}
+}
+
+impl<T> Distribution<T> for ExactOnes
+where
+	T: Bits + Default,
+	u32: BitsIndex<T>,
+{
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+		check!(self.k <= T::N_BITS, bit_range_too_wide);
+		// Robert Floyd's algorithm for a random subset of k indices.
+		let mut v = T::default();
+		for j in T::N_BITS - self.k..T::N_BITS {
+			let i = rng.random_range(0..=j);
+			let i = if v.bit(i) { j } else { i };
+			v.set_bit(i, true);
+		}
+		v
+	}
+}
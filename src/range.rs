@@ -0,0 +1,164 @@
+use super::Bits;
+use core::convert::TryFrom;
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
+use core::ops::{Range, RangeBounds};
+
+macro_rules! bounds {
+	($name:ident, $t:ty) => {
+		/// Normalize a range of bit indices to `start..end`, given the total
+		/// number of bits.
+		///
+		/// Panics when the bounds are out of range. Inverted ranges are treated
+		/// as empty, just like for the integer types.
+		#[inline]
+		pub(crate) fn $name<R: RangeBounds<$t>>(range: &R, n_bits: $t) -> ($t, $t) {
+			let start = match range.start_bound() {
+				Unbounded => 0,
+				Included(&i) => i,
+				Excluded(&i) => i.saturating_add(1),
+			};
+			let end = match range.end_bound() {
+				Unbounded => n_bits,
+				Excluded(&i) => i,
+				Included(&i) => i.saturating_add(1),
+			};
+			check!(start <= n_bits && end <= n_bits, invalid_bit_range);
+			(start, end.max(start))
+		}
+	};
+}
+
+bounds!(bounds, u32);
+bounds!(bounds_usize, usize);
+
+/// Normalize a range of bit indices to `start..end`, as done by all methods
+/// of this crate that take a range of bits.
+///
+/// | Range                        | Result        |
+/// |------------------------------|---------------|
+/// | `a..b`                       | `a..b`        |
+/// | `a..=b`                      | `a..b + 1`    |
+/// | `a..`                        | `a..n_bits`   |
+/// | `..b`                        | `0..b`        |
+/// | `..=b`                       | `0..b + 1`    |
+/// | `..`                         | `0..n_bits`   |
+/// | `(Excluded(a), Excluded(b))` | `a + 1..b`    |
+///
+/// Panics if the start or end is beyond `n_bits`. An inverted range, with
+/// its start after its end, is treated as the empty range at its start.
+///
+/// # Example
+///
+/// ```
+/// use intbits::normalize_range;
+///
+/// assert_eq!(normalize_range(4..=7, 32), 4..8);
+/// assert_eq!(normalize_range(20.., 32), 20..32);
+/// assert_eq!(normalize_range(..=31, 32), 0..32);
+/// ```
+#[inline]
+pub fn normalize_range<R: RangeBounds<u32>>(range: R, n_bits: u32) -> Range<u32> {
+	let (start, end) = bounds(&range, n_bits);
+	start..end
+}
+
+/// Conversion of a pair of indices into a range.
+///
+/// The methods of this crate take any [`RangeBounds`], which can't be
+/// implemented for tuples and arrays outside the standard library. This trait
+/// provides a short conversion instead, for ranges coming from tables.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{Bits, ToRange};
+///
+/// const FIELDS: [[u32; 2]; 2] = [[0, 4], [4, 12]];
+///
+/// assert_eq!(0xABCu16.bits(FIELDS[1].to_range()), 0xAB);
+/// assert_eq!(0xABCu16.bits((0, 4).to_range()), 0xC);
+/// ```
+pub trait ToRange<I> {
+	/// Get the range from the first index (inclusive) to the second
+	/// (exclusive).
+	fn to_range(self) -> Range<I>;
+}
+
+impl<I> ToRange<I> for (I, I) {
+	#[inline]
+	fn to_range(self) -> Range<I> {
+		self.0..self.1
+	}
+}
+
+impl<I> ToRange<I> for [I; 2] {
+	#[inline]
+	fn to_range(self) -> Range<I> {
+		let [start, end] = self;
+		start..end
+	}
+}
+
+/// Get the mask and shift for a range of bits of `T`.
+///
+/// The range is normalized like by [`normalize_range`]. The mask has the bits
+/// of the range set, at their position in the integer, and the shift is the
+/// index of the first bit of the range. An empty range gives a mask of 0.
+///
+/// For a non-empty range, `x.bits(range)` is `(x & mask) >> shift` and
+/// `x.set_bits(range, v)` is `x & !mask | v << shift`.
+///
+/// Panics when the range bounds are out of range.
+///
+/// # Example
+///
+/// ```
+/// use intbits::normalize;
+///
+/// assert_eq!(normalize::<u16, _>(4..8), (0x00F0, 4));
+/// assert_eq!(normalize::<u8, _>(4..), (0xF0, 4));
+/// assert_eq!(normalize::<u32, _>(8..8), (0, 8));
+/// ```
+#[inline]
+pub fn normalize<T, R>(range: R) -> (T::Bits, u32)
+where
+	T: Bits,
+	R: RangeBounds<u32>,
+{
+	let (start, end) = bounds(&range, T::N_BITS);
+	let zero = T::Bits::default();
+	if start == end {
+		(zero, start)
+	} else {
+		(!zero >> (T::N_BITS - (end - start)) << start, start)
+	}
+}
+
+/// Convert a range of any index type to a range of `u32`.
+///
+/// An excluded start or included end of -1 is accepted, as in `..=-1`.
+///
+/// Panics when a bound doesn't fit in a `u32`.
+pub(crate) fn u32_bounds<I, R>(range: &R) -> (Bound<u32>, Bound<u32>)
+where
+	I: Copy,
+	u32: TryFrom<I>,
+	i8: TryFrom<I>,
+	R: RangeBounds<I>,
+{
+	let convert = |i: I| u32::try_from(i).unwrap_or_else(|_| crate::panic::invalid_bit_range());
+	let minus_one = |i: I| i8::try_from(i).ok() == Some(-1);
+	let start = match range.start_bound() {
+		Unbounded => Unbounded,
+		Excluded(&i) if minus_one(i) => Included(0),
+		Included(&i) => Included(convert(i)),
+		Excluded(&i) => Excluded(convert(i)),
+	};
+	let end = match range.end_bound() {
+		Unbounded => Unbounded,
+		Included(&i) if minus_one(i) => Excluded(0),
+		Included(&i) => Included(convert(i)),
+		Excluded(&i) => Excluded(convert(i)),
+	};
+	(start, end)
+}
@@ -0,0 +1,209 @@
+use super::BitOrder;
+
+/// The number of set bits in the bytes.
+#[inline]
+fn count_ones(bytes: &[u8]) -> usize {
+	let mut chunks = bytes.chunks_exact(8);
+	let mut n = 0;
+	for chunk in &mut chunks {
+		let mut word = [0; 8];
+		word.copy_from_slice(chunk);
+		n += u64::from_ne_bytes(word).count_ones() as usize;
+	}
+	n + chunks
+		.remainder()
+		.iter()
+		.map(|b| b.count_ones() as usize)
+		.sum::<usize>()
+}
+
+/// The bits of a byte, with the first bit in the given order in the lowest
+/// bit.
+#[inline]
+fn lsb_first(byte: u8, order: BitOrder) -> u8 {
+	match order {
+		BitOrder::LsbFirst => byte,
+		BitOrder::MsbFirst => byte.reverse_bits(),
+	}
+}
+
+/// The index of the `k`th set bit of a byte, in the given order.
+#[inline]
+fn select_in_byte(byte: u8, mut k: usize, order: BitOrder) -> Option<usize> {
+	let mut byte = lsb_first(byte, order);
+	while byte != 0 {
+		if k == 0 {
+			return Some(byte.trailing_zeros() as usize);
+		}
+		byte &= byte - 1;
+		k -= 1;
+	}
+	None
+}
+
+/// The index of the `k`th set bit in the bytes, in the given order.
+fn select_in(bytes: &[u8], mut k: usize, order: BitOrder) -> Option<usize> {
+	let mut chunks = bytes.chunks(8);
+	let mut offset = 0;
+	for chunk in &mut chunks {
+		let n = count_ones(chunk);
+		if k < n {
+			for &byte in chunk {
+				let n = byte.count_ones() as usize;
+				if k < n {
+					return select_in_byte(byte, k, order).map(|i| offset + i);
+				}
+				k -= n;
+				offset += 8;
+			}
+		}
+		k -= n;
+		offset += chunk.len() * 8;
+	}
+	None
+}
+
+/// The number of set bits before bit `i` of a buffer.
+///
+/// Bit `i` is the `i`th bit in the given [`BitOrder`], as in
+/// [`SliceBits`][crate::SliceBits]. `i` may be equal to the number of bits, to
+/// count all set bits.
+///
+/// Panics when `i` is more than the number of bits.
+///
+/// For many queries on the same buffer, use [`RankIndex`].
+///
+/// # Example
+///
+/// ```
+/// use intbits::{rank, BitOrder};
+///
+/// let buf = [0b1000_0001, 0xFF];
+/// assert_eq!(rank(&buf, 1, BitOrder::LsbFirst), 1);
+/// assert_eq!(rank(&buf, 1, BitOrder::MsbFirst), 1);
+/// assert_eq!(rank(&buf, 12, BitOrder::LsbFirst), 6);
+/// assert_eq!(rank(&buf, 16, BitOrder::LsbFirst), 10);
+/// ```
+pub fn rank(buf: &[u8], i: usize, order: BitOrder) -> usize {
+	check!(i <= buf.len() * 8, invalid_bit_index);
+	let (byte, bit) = (i / 8, i % 8);
+	let partial = match buf.get(byte) {
+		Some(&b) => (lsb_first(b, order) & !(!0 << bit)).count_ones() as usize,
+		None => 0,
+	};
+	count_ones(&buf[..byte]) + partial
+}
+
+/// The index of the `k`th set bit of a buffer, counting from 0.
+///
+/// This is the inverse of [`rank`]: `rank(buf, select(buf, k)?) == k`.
+/// Returns `None` if fewer than `k + 1` bits are set.
+///
+/// For many queries on the same buffer, use [`RankIndex`].
+///
+/// # Example
+///
+/// ```
+/// use intbits::{select, BitOrder};
+///
+/// let buf = [0b1000_0001, 0xFF];
+/// assert_eq!(select(&buf, 0, BitOrder::LsbFirst), Some(0));
+/// assert_eq!(select(&buf, 1, BitOrder::LsbFirst), Some(7));
+/// assert_eq!(select(&buf, 2, BitOrder::MsbFirst), Some(8));
+/// assert_eq!(select(&buf, 10, BitOrder::MsbFirst), None);
+/// ```
+pub fn select(buf: &[u8], k: usize, order: BitOrder) -> Option<usize> {
+	select_in(buf, k, order)
+}
+
+/// A buffer with a precomputed index, for fast [`rank`] and [`select`]
+/// queries.
+///
+/// The index stores the number of set bits before every block of
+/// [`BLOCK_BYTES`][Self::BLOCK_BYTES] bytes, in storage provided by the
+/// caller. Queries then only have to count the bits within one block.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, RankIndex};
+///
+/// let buf = [0x55; 1000];
+/// let mut storage = [0; RankIndex::blocks_needed(1000)];
+/// let index = RankIndex::new(&buf, BitOrder::LsbFirst, &mut storage);
+///
+/// assert_eq!(index.count_ones(), 4000);
+/// assert_eq!(index.rank(7001), 3501);
+/// assert_eq!(index.select(3500), Some(7000));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RankIndex<'a> {
+	buf: &'a [u8],
+	blocks: &'a [usize],
+	total: usize,
+	order: BitOrder,
+}
+
+impl<'a> RankIndex<'a> {
+	/// The number of bytes per block.
+	pub const BLOCK_BYTES: usize = 64;
+
+	/// The number of blocks needed to index a buffer of `len` bytes.
+	pub const fn blocks_needed(len: usize) -> usize {
+		len.div_ceil(Self::BLOCK_BYTES)
+	}
+
+	/// Build the index of `buf` into `storage`.
+	///
+	/// Panics if `storage` is shorter than
+	/// [`blocks_needed(buf.len())`][Self::blocks_needed].
+	pub fn new(buf: &'a [u8], order: BitOrder, storage: &'a mut [usize]) -> Self {
+		let blocks = &mut storage[..Self::blocks_needed(buf.len())];
+		let mut total = 0;
+		for (count, block) in blocks.iter_mut().zip(buf.chunks(Self::BLOCK_BYTES)) {
+			*count = total;
+			total += count_ones(block);
+		}
+		Self {
+			buf,
+			blocks,
+			total,
+			order,
+		}
+	}
+
+	/// The buffer.
+	pub fn buf(&self) -> &'a [u8] {
+		self.buf
+	}
+
+	/// The total number of set bits.
+	pub fn count_ones(&self) -> usize {
+		self.total
+	}
+
+	/// The number of set bits before bit `i`.
+	///
+	/// See [`rank`].
+	pub fn rank(&self, i: usize) -> usize {
+		check!(i <= self.buf.len() * 8, invalid_bit_index);
+		let block = i / 8 / Self::BLOCK_BYTES;
+		match self.blocks.get(block) {
+			Some(&n) => {
+				let bytes = &self.buf[block * Self::BLOCK_BYTES..];
+				n + rank(bytes, i % (8 * Self::BLOCK_BYTES), self.order)
+			}
+			None => self.total,
+		}
+	}
+
+	/// The index of the `k`th set bit, counting from 0.
+	///
+	/// See [`select`].
+	pub fn select(&self, k: usize) -> Option<usize> {
+		let block = self.blocks.partition_point(|&n| n <= k).checked_sub(1)?;
+		let start = block * Self::BLOCK_BYTES;
+		let end = (start + Self::BLOCK_BYTES).min(self.buf.len());
+		select_in(&self.buf[start..end], k - self.blocks[block], self.order).map(|i| start * 8 + i)
+	}
+}
@@ -0,0 +1,123 @@
+/// Unpack MIPI RAW10 data into 10-bit samples.
+///
+/// Every group of 5 bytes holds 4 pixels: the first 4 bytes are the upper 8
+/// bits of each pixel, and the last byte holds their lower 2 bits, starting
+/// with the first pixel in the least significant bits.
+///
+/// As many complete groups as fit in both `input` and `out` are unpacked.
+/// Returns the number of samples written.
+///
+/// # Example
+///
+/// ```
+/// use intbits::unpack_raw10;
+///
+/// let mut out = [0; 4];
+/// assert_eq!(unpack_raw10(&[0x12, 0x34, 0x56, 0x78, 0b11_10_01_00], &mut out), 4);
+/// assert_eq!(out, [0x048, 0x0D1, 0x15A, 0x1E3]);
+/// ```
+pub fn unpack_raw10(input: &[u8], out: &mut [u16]) -> usize {
+	let mut n = 0;
+	for (group, pixels) in input.chunks_exact(5).zip(out.chunks_exact_mut(4)) {
+		let mut word = [0; 8];
+		word[..5].copy_from_slice(group);
+		let word = u64::from_le_bytes(word);
+		let low = (word >> 32) as u16;
+		for (i, p) in pixels.iter_mut().enumerate() {
+			*p = ((word >> (8 * i)) as u8 as u16) << 2 | low >> (2 * i) & 3;
+		}
+		n += 4;
+	}
+	n
+}
+
+/// Pack 10-bit samples into MIPI RAW10 data.
+///
+/// This is the counterpart of [`unpack_raw10`]. As many complete groups as
+/// fit in both `input` and `out` are packed. Returns the number of bytes
+/// written.
+///
+/// Panics when a sample does not fit in 10 bits.
+///
+/// # Example
+///
+/// ```
+/// use intbits::pack_raw10;
+///
+/// let mut out = [0; 5];
+/// assert_eq!(pack_raw10(&[0x048, 0x0D1, 0x15A, 0x1E3], &mut out), 5);
+/// assert_eq!(out, [0x12, 0x34, 0x56, 0x78, 0b11_10_01_00]);
+/// ```
+pub fn pack_raw10(input: &[u16], out: &mut [u8]) -> usize {
+	let mut n = 0;
+	for (pixels, group) in input.chunks_exact(4).zip(out.chunks_exact_mut(5)) {
+		let mut word = 0u64;
+		for (i, &p) in pixels.iter().enumerate() {
+			check!(p < 1 << 10, bits_outside_range);
+			word |= ((p >> 2) as u64) << (8 * i) | ((p & 3) as u64) << (32 + 2 * i);
+		}
+		group.copy_from_slice(&word.to_le_bytes()[..5]);
+		n += 5;
+	}
+	n
+}
+
+/// Unpack MIPI RAW12 data into 12-bit samples.
+///
+/// Every group of 3 bytes holds 2 pixels: the first 2 bytes are the upper 8
+/// bits of each pixel, and the last byte holds their lower 4 bits, starting
+/// with the first pixel in the least significant bits.
+///
+/// As many complete groups as fit in both `input` and `out` are unpacked.
+/// Returns the number of samples written.
+///
+/// # Example
+///
+/// ```
+/// use intbits::unpack_raw12;
+///
+/// let mut out = [0; 2];
+/// assert_eq!(unpack_raw12(&[0x12, 0x34, 0xBA], &mut out), 2);
+/// assert_eq!(out, [0x12A, 0x34B]);
+/// ```
+pub fn unpack_raw12(input: &[u8], out: &mut [u16]) -> usize {
+	let mut n = 0;
+	for (group, pixels) in input.chunks_exact(3).zip(out.chunks_exact_mut(2)) {
+		pixels[0] = (group[0] as u16) << 4 | (group[2] & 0xF) as u16;
+		pixels[1] = (group[1] as u16) << 4 | (group[2] >> 4) as u16;
+		n += 2;
+	}
+	n
+}
+
+/// Pack 12-bit samples into MIPI RAW12 data.
+///
+/// This is the counterpart of [`unpack_raw12`]. As many complete groups as
+/// fit in both `input` and `out` are packed. Returns the number of bytes
+/// written.
+///
+/// Panics when a sample does not fit in 12 bits.
+///
+/// # Example
+///
+/// ```
+/// use intbits::pack_raw12;
+///
+/// let mut out = [0; 3];
+/// assert_eq!(pack_raw12(&[0x12A, 0x34B], &mut out), 3);
+/// assert_eq!(out, [0x12, 0x34, 0xBA]);
+/// ```
+pub fn pack_raw12(input: &[u16], out: &mut [u8]) -> usize {
+	let mut n = 0;
+	for (pixels, group) in input.chunks_exact(2).zip(out.chunks_exact_mut(3)) {
+		check!(
+			pixels[0] < 1 << 12 && pixels[1] < 1 << 12,
+			bits_outside_range
+		);
+		group[0] = (pixels[0] >> 4) as u8;
+		group[1] = (pixels[1] >> 4) as u8;
+		group[2] = (pixels[0] & 0xF) as u8 | ((pixels[1] & 0xF) as u8) << 4;
+		n += 3;
+	}
+	n
+}
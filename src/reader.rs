@@ -0,0 +1,387 @@
+use super::{BitOrder, SliceBits};
+use core::fmt;
+
+/// The error returned when reading past the end of a bit stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutOfBits;
+
+impl fmt::Display for OutOfBits {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "not enough bits left in the stream")
+	}
+}
+
+/// The error returned when decoding a variable length code from a bit stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeError {
+	/// The stream ended in the middle of the code.
+	OutOfBits,
+	/// The code is malformed, or represents a value that doesn't fit in the
+	/// return type.
+	InvalidCode,
+}
+
+impl From<OutOfBits> for DecodeError {
+	fn from(_: OutOfBits) -> Self {
+		DecodeError::OutOfBits
+	}
+}
+
+impl fmt::Display for DecodeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DecodeError::OutOfBits => fmt::Display::fmt(&OutOfBits, f),
+			DecodeError::InvalidCode => write!(f, "invalid code in the stream"),
+		}
+	}
+}
+
+/// The error returned when reading from a bit stream that is backed by an
+/// I/O source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadError<E> {
+	/// The source ended before enough bits were read.
+	OutOfBits,
+	/// The source returned an error.
+	Io(E),
+}
+
+impl<E> From<OutOfBits> for ReadError<E> {
+	fn from(_: OutOfBits) -> Self {
+		ReadError::OutOfBits
+	}
+}
+
+impl<E: fmt::Display> fmt::Display for ReadError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ReadError::OutOfBits => fmt::Display::fmt(&OutOfBits, f),
+			ReadError::Io(e) => write!(f, "error while reading: {}", e),
+		}
+	}
+}
+
+/// Reads values of any number of bits sequentially from a byte slice.
+///
+/// Values are read in the given [`BitOrder`]: with
+/// [`MsbFirst`][BitOrder::MsbFirst], the first bit read is the most significant
+/// bit of the first byte and of the returned value, as is common in codecs
+/// and network protocols. With [`LsbFirst`][BitOrder::LsbFirst], it is the
+/// least significant bit of both, as in DEFLATE.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, BitReader};
+///
+/// let mut r = BitReader::new(&[0xA5, 0xF0], BitOrder::MsbFirst);
+/// assert_eq!(r.read_bits(3), Ok(0b101));
+/// assert_eq!(r.peek_bits(2), Ok(0b00));
+/// assert_eq!(r.read_bits(7), Ok(0b001_0111));
+/// assert_eq!(r.remaining_bits(), 6);
+/// r.align_to_byte();
+/// assert!(r.read_bits(1).is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+	data: &'a [u8],
+	pos: usize,
+	order: BitOrder,
+}
+
+impl<'a> BitReader<'a> {
+	/// Create a reader that starts at the first bit of `data`.
+	pub fn new(data: &'a [u8], order: BitOrder) -> Self {
+		Self {
+			data,
+			pos: 0,
+			order,
+		}
+	}
+
+	/// The bit order of this reader.
+	pub fn order(&self) -> BitOrder {
+		self.order
+	}
+
+	/// The number of bits read (or skipped) so far.
+	pub fn position(&self) -> usize {
+		self.pos
+	}
+
+	/// The number of bits left to read.
+	pub fn remaining_bits(&self) -> usize {
+		self.data.len() * 8 - self.pos
+	}
+
+	/// Whether the reader is at the start of a byte.
+	pub fn is_aligned(&self) -> bool {
+		self.pos.is_multiple_of(8)
+	}
+
+	/// Get the next `n` bits without advancing the reader.
+	///
+	/// Panics if `n` is more than 64.
+	#[inline]
+	pub fn peek_bits(&self, n: u32) -> Result<u64, OutOfBits> {
+		check!(n <= 64, bit_range_too_wide);
+		if n as usize > self.remaining_bits() {
+			return Err(OutOfBits);
+		}
+		Ok(self.data.bits(self.pos..self.pos + n as usize, self.order) as u64)
+	}
+
+	/// Read the next `n` bits.
+	///
+	/// Panics if `n` is more than 64.
+	#[inline]
+	pub fn read_bits(&mut self, n: u32) -> Result<u64, OutOfBits> {
+		let bits = self.peek_bits(n)?;
+		self.pos += n as usize;
+		Ok(bits)
+	}
+
+	/// Read a single bit.
+	#[inline]
+	pub fn read_bit(&mut self) -> Result<bool, OutOfBits> {
+		self.read_bits(1).map(|b| b != 0)
+	}
+
+	/// Skip the next `n` bits.
+	pub fn skip(&mut self, n: usize) -> Result<(), OutOfBits> {
+		if n > self.remaining_bits() {
+			return Err(OutOfBits);
+		}
+		self.pos += n;
+		Ok(())
+	}
+
+	/// Skip to the start of the next byte, unless already at the start of a
+	/// byte.
+	pub fn align_to_byte(&mut self) {
+		self.pos = self.pos.div_ceil(8) * 8;
+	}
+
+	/// Read an Exp-Golomb code, and return the number of leading zeros and
+	/// the value plus one.
+	fn read_exp_golomb(&mut self) -> Result<u128, DecodeError> {
+		let start = self.pos;
+		let mut zeros = 0;
+		while !self.read_bit().inspect_err(|_| self.pos = start)? {
+			zeros += 1;
+			if zeros > 64 {
+				self.pos = start;
+				return Err(DecodeError::InvalidCode);
+			}
+		}
+		match self.read_bits(zeros) {
+			Ok(bits) => Ok(1 << zeros | bits as u128),
+			Err(e) => {
+				self.pos = start;
+				Err(e.into())
+			}
+		}
+	}
+
+	/// Read an unsigned Exp-Golomb code, `ue(v)` in H.264.
+	///
+	/// On error, the reader is left at the start of the code.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitReader};
+	///
+	/// // 1, 010, 011, 00100
+	/// let mut r = BitReader::new(&[0b1010_0110, 0b0100_0000], BitOrder::MsbFirst);
+	/// assert_eq!(r.read_ue(), Ok(0));
+	/// assert_eq!(r.read_ue(), Ok(1));
+	/// assert_eq!(r.read_ue(), Ok(2));
+	/// assert_eq!(r.read_ue(), Ok(3));
+	/// ```
+	pub fn read_ue(&mut self) -> Result<u64, DecodeError> {
+		let start = self.pos;
+		let v = self.read_exp_golomb()? - 1;
+		if v > u64::MAX as u128 {
+			self.pos = start;
+			return Err(DecodeError::InvalidCode);
+		}
+		Ok(v as u64)
+	}
+
+	/// Read a signed Exp-Golomb code, `se(v)` in H.264.
+	///
+	/// The unsigned codes 0, 1, 2, 3, 4, ... represent the values 0, 1, -1, 2,
+	/// -2, ...
+	///
+	/// On error, the reader is left at the start of the code.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitReader};
+	///
+	/// // 1, 010, 011, 00100
+	/// let mut r = BitReader::new(&[0b1010_0110, 0b0100_0000], BitOrder::MsbFirst);
+	/// assert_eq!(r.read_se(), Ok(0));
+	/// assert_eq!(r.read_se(), Ok(1));
+	/// assert_eq!(r.read_se(), Ok(-1));
+	/// assert_eq!(r.read_se(), Ok(2));
+	/// ```
+	pub fn read_se(&mut self) -> Result<i64, DecodeError> {
+		let start = self.pos;
+		let k = self.read_exp_golomb()? - 1;
+		let v = if k % 2 == 1 {
+			(k / 2 + 1) as i128
+		} else {
+			-((k / 2) as i128)
+		};
+		if v > i64::MAX as i128 || v < i64::MIN as i128 {
+			self.pos = start;
+			return Err(DecodeError::InvalidCode);
+		}
+		Ok(v as i64)
+	}
+
+	/// Read a unary code: the number of 0 bits before the next 1 bit.
+	///
+	/// Long runs of zeros are counted up to 64 bits at a time.
+	///
+	/// On error, the reader is left at the start of the code.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitReader};
+	///
+	/// let mut r = BitReader::new(&[0b1001_0000, 0b0000_0001], BitOrder::MsbFirst);
+	/// assert_eq!(r.read_unary(), Ok(0));
+	/// assert_eq!(r.read_unary(), Ok(2));
+	/// assert_eq!(r.read_unary(), Ok(11));
+	/// assert!(r.read_unary().is_err());
+	/// ```
+	pub fn read_unary(&mut self) -> Result<u64, DecodeError> {
+		let start = self.pos;
+		let mut count = 0;
+		loop {
+			let n = self.remaining_bits().min(64) as u32;
+			if n == 0 {
+				self.pos = start;
+				return Err(DecodeError::OutOfBits);
+			}
+			let chunk = self.peek_bits(n)?;
+			if chunk == 0 {
+				count += n as u64;
+				self.pos += n as usize;
+				continue;
+			}
+			let zeros = match self.order {
+				BitOrder::LsbFirst => chunk.trailing_zeros(),
+				BitOrder::MsbFirst => chunk.leading_zeros() - (64 - n),
+			};
+			self.pos += zeros as usize + 1;
+			return Ok(count + zeros as u64);
+		}
+	}
+
+	/// Read a Rice code with parameter `k`: a unary quotient followed by a
+	/// `k` bit remainder.
+	///
+	/// This is a Golomb code with divisor `2^k`, as used in FLAC.
+	///
+	/// On error, the reader is left at the start of the code.
+	///
+	/// Panics if `k` is more than 64.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitReader};
+	///
+	/// // 001 + 10: 2 << 2 | 2
+	/// let mut r = BitReader::new(&[0b0011_0000], BitOrder::MsbFirst);
+	/// assert_eq!(r.read_rice(2), Ok(10));
+	/// ```
+	pub fn read_rice(&mut self, k: u32) -> Result<u64, DecodeError> {
+		check!(k <= 64, bit_range_too_wide);
+		let start = self.pos;
+		let q = self.read_unary()?;
+		let r = self.read_bits(k).inspect_err(|_| self.pos = start)?;
+		let v = (q as u128) << k | r as u128;
+		if v > u64::MAX as u128 {
+			self.pos = start;
+			return Err(DecodeError::InvalidCode);
+		}
+		Ok(v as u64)
+	}
+
+	/// Read the 7-bit groups of a LEB128 code, returning the value bits, the
+	/// number of bits, and the last group.
+	fn read_leb128(&mut self, signed: bool) -> Result<(u64, u32, u8), DecodeError> {
+		let start = self.pos;
+		let mut value = 0u64;
+		let mut shift = 0;
+		loop {
+			let group = self.read_bits(8).inspect_err(|_| self.pos = start)? as u8;
+			let bits = (group & 0x7F) as u64;
+			let valid = match shift {
+				0..=62 => true,
+				63 if signed => bits == 0 || bits == 0x7F,
+				63 => bits <= 1,
+				_ => false,
+			};
+			if !valid {
+				self.pos = start;
+				return Err(DecodeError::InvalidCode);
+			}
+			value |= bits << shift;
+			shift += 7;
+			if group & 0x80 == 0 {
+				return Ok((value, shift, group));
+			}
+		}
+	}
+
+	/// Read an unsigned LEB128 varint, starting at any bit position.
+	///
+	/// Each group of 8 bits is read like [`read_bits(8)`][Self::read_bits],
+	/// so on a byte aligned stream this is the usual byte based encoding.
+	///
+	/// On error, the reader is left at the start of the code.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitReader};
+	///
+	/// let mut r = BitReader::new(&[0xE5, 0x8E, 0x26], BitOrder::LsbFirst);
+	/// assert_eq!(r.read_uleb128(), Ok(624485));
+	/// ```
+	pub fn read_uleb128(&mut self) -> Result<u64, DecodeError> {
+		self.read_leb128(false).map(|(v, _, _)| v)
+	}
+
+	/// Read a signed LEB128 varint, starting at any bit position.
+	///
+	/// On error, the reader is left at the start of the code.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitReader};
+	///
+	/// let mut r = BitReader::new(&[0xC0, 0xBB, 0x78], BitOrder::LsbFirst);
+	/// assert_eq!(r.read_sleb128(), Ok(-123456));
+	/// ```
+	pub fn read_sleb128(&mut self) -> Result<i64, DecodeError> {
+		let (value, shift, last) = self.read_leb128(true)?;
+		if shift < 64 && last & 0x40 != 0 {
+			Ok((value | !0 << shift) as i64)
+		} else {
+			Ok(value as i64)
+		}
+	}
+}
@@ -0,0 +1,67 @@
+use super::{Bits, BitsIndex};
+use core::ops::RangeBounds;
+
+/// Extension trait to read individual bits of integers through a shared
+/// reference.
+///
+/// [`Bits`] is not implemented for `&T`, since its setters can't work through
+/// a shared reference. This trait provides the getters instead, so generic
+/// code that only reads bits can take borrowed integers, such as the items of
+/// an iterator over a slice.
+///
+/// # Example
+///
+/// ```
+/// use intbits::RefBits;
+///
+/// fn low_nibble<T: RefBits>(x: T) -> <T::Value as intbits::Bits>::Bits
+/// where
+///     u32: intbits::BitsIndex<T::Value>,
+/// {
+///     x.bits(0..4)
+/// }
+///
+/// let regs = [0x12u32, 0x34, 0x56];
+/// assert_eq!(regs.iter().map(low_nibble).sum::<u32>(), 12);
+/// assert!(regs.iter().all(|r| r.bit(4)));
+/// ```
+pub trait RefBits: Copy {
+	/// The type of integer that is referenced.
+	type Value: Bits;
+
+	/// Get a specific bit.
+	///
+	/// See [`Bits::bit`].
+	fn bit<I>(self, i: I) -> bool
+	where
+		I: BitsIndex<Self::Value>;
+
+	/// Get a range of bits.
+	///
+	/// See [`Bits::bits`].
+	fn bits<I, R>(self, range: R) -> <Self::Value as Bits>::Bits
+	where
+		I: BitsIndex<Self::Value>,
+		R: RangeBounds<I>;
+}
+
+impl<T: Bits> RefBits for &T {
+	type Value = T;
+
+	#[inline]
+	fn bit<I>(self, i: I) -> bool
+	where
+		I: BitsIndex<T>,
+	{
+		(*self).bit(i)
+	}
+
+	#[inline]
+	fn bits<I, R>(self, range: R) -> T::Bits
+	where
+		I: BitsIndex<T>,
+		R: RangeBounds<I>,
+	{
+		(*self).bits(range)
+	}
+}
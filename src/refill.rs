@@ -0,0 +1,85 @@
+use super::{BitOrder, SliceBits};
+
+/// The buffer of the readers that read bits from a byte source.
+///
+/// The reader fills the buffer from its source: when [`has`][Self::has]
+/// returns false, it calls [`compact`][Self::compact], and then reads into
+/// [`spare`][Self::spare] until enough bits are available.
+#[derive(Debug)]
+pub(crate) struct RefillBuffer<const N: usize> {
+	buf: [u8; N],
+	/// The number of bytes in `buf` that were read from the source.
+	len: usize,
+	/// The position in `buf`, in bits.
+	pos: usize,
+	/// The number of bits that were read before the start of `buf`.
+	consumed: usize,
+	order: BitOrder,
+}
+
+impl<const N: usize> RefillBuffer<N> {
+	/// Panics if `N` is less than 9, since a value of 64 bits that doesn't
+	/// start at the start of a byte can span 9 bytes.
+	pub(crate) fn new(order: BitOrder) -> Self {
+		check!(N >= 9, buffer_too_small);
+		Self {
+			buf: [0; N],
+			len: 0,
+			pos: 0,
+			consumed: 0,
+			order,
+		}
+	}
+
+	pub(crate) fn order(&self) -> BitOrder {
+		self.order
+	}
+
+	pub(crate) fn position(&self) -> usize {
+		self.consumed + self.pos
+	}
+
+	pub(crate) fn is_aligned(&self) -> bool {
+		self.pos.is_multiple_of(8)
+	}
+
+	/// The number of bits up to the start of the next byte.
+	pub(crate) fn bits_to_byte(&self) -> usize {
+		(8 - self.pos % 8) % 8
+	}
+
+	/// Whether the next `n` bits are in the buffer.
+	pub(crate) fn has(&self, n: u32) -> bool {
+		self.pos + n as usize <= self.len * 8
+	}
+
+	/// Drop the bytes that were completely read, to make room at the end.
+	pub(crate) fn compact(&mut self) {
+		let start = self.pos / 8;
+		self.buf.copy_within(start..self.len, 0);
+		self.len -= start;
+		self.pos -= start * 8;
+		self.consumed += start * 8;
+	}
+
+	/// The unused part of the buffer, for reading from the source.
+	pub(crate) fn spare(&mut self) -> &mut [u8] {
+		&mut self.buf[self.len..]
+	}
+
+	/// Mark `n` more bytes of [`spare`][Self::spare] as read from the source.
+	pub(crate) fn filled(&mut self, n: usize) {
+		self.len += n;
+	}
+
+	/// Get the next `n` bits, which must be in the buffer.
+	pub(crate) fn peek(&self, n: u32) -> u64 {
+		let range = self.pos..self.pos + n as usize;
+		self.buf[..self.len].bits(range, self.order) as u64
+	}
+
+	/// Advance past `n` bits, which must be in the buffer.
+	pub(crate) fn advance(&mut self, n: usize) {
+		self.pos += n;
+	}
+}
@@ -0,0 +1,121 @@
+use super::{Bits, BitsIndex};
+use core::ops::RangeBounds;
+
+/// Adapter for register proxies generated by `svd2rust`.
+///
+/// Implement [`raw_bits`][RegisterBits::raw_bits] and
+/// [`set_raw_bits`][RegisterBits::set_raw_bits] by forwarding to the `bits`
+/// methods of the register proxy, and the other methods give access to the
+/// fields of the register. Because of the orphan rules, this has to be done in
+/// the crate that defines the proxy types, such as the `generic` module of the
+/// PAC crate.
+///
+/// Writing arbitrary bits to a register is unsafe in `svd2rust`, so the
+/// methods that modify the register are unsafe as well.
+///
+/// This is only available with the `svd2rust` feature.
+///
+/// # Example
+///
+/// ```
+/// use intbits::RegisterBits;
+///
+/// struct W {
+///     bits: u32,
+/// }
+///
+/// impl W {
+///     pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+///         self.bits = bits;
+///         self
+///     }
+/// }
+///
+/// impl RegisterBits for W {
+///     type Raw = u32;
+///
+///     fn raw_bits(&self) -> u32 {
+///         self.bits
+///     }
+///
+///     unsafe fn set_raw_bits(&mut self, raw: u32) {
+///         self.bits(raw);
+///     }
+/// }
+///
+/// let mut w = W { bits: 0x1200 };
+/// // Safety: every value of this register is valid.
+/// unsafe { w.modify_bits(4..8, 0xA).modify_bit(0, true) };
+/// assert_eq!(w.read_bits(0..16), 0x12A1);
+/// ```
+pub trait RegisterBits {
+	/// The raw integer type of the register.
+	type Raw: Bits;
+
+	/// Get the raw value of the register.
+	fn raw_bits(&self) -> Self::Raw;
+
+	/// Set the raw value of the register.
+	///
+	/// # Safety
+	///
+	/// `raw` must be a valid value for the register, as for the `bits` method
+	/// of `svd2rust` register writers.
+	unsafe fn set_raw_bits(&mut self, raw: Self::Raw);
+
+	/// Get a specific bit of the register.
+	///
+	/// See [`Bits::bit`].
+	#[inline]
+	fn read_bit<I>(&self, i: I) -> bool
+	where
+		I: BitsIndex<Self::Raw>,
+	{
+		self.raw_bits().bit(i)
+	}
+
+	/// Get a range of bits of the register.
+	///
+	/// See [`Bits::bits`].
+	#[inline]
+	fn read_bits<I, R>(&self, range: R) -> <Self::Raw as Bits>::Bits
+	where
+		I: BitsIndex<Self::Raw>,
+		R: RangeBounds<I>,
+	{
+		self.raw_bits().bits(range)
+	}
+
+	/// Set a specific bit of the register, keeping the other bits.
+	///
+	/// See [`Bits::set_bit`].
+	///
+	/// # Safety
+	///
+	/// The resulting value must be valid for the register.
+	#[inline]
+	unsafe fn modify_bit<I>(&mut self, i: I, bit: bool) -> &mut Self
+	where
+		I: BitsIndex<Self::Raw>,
+	{
+		self.set_raw_bits(self.raw_bits().with_bit(i, bit));
+		self
+	}
+
+	/// Set a range of bits of the register, keeping the other bits.
+	///
+	/// See [`Bits::set_bits`].
+	///
+	/// # Safety
+	///
+	/// The resulting value must be valid for the register.
+	#[inline]
+	unsafe fn modify_bits<I, R>(&mut self, range: R, bits: <Self::Raw as Bits>::Bits) -> &mut Self
+	where
+		I: BitsIndex<Self::Raw>,
+		R: RangeBounds<I>,
+	{
+		self.set_raw_bits(self.raw_bits().with_bits(range, bits));
+		self
+	}
+}
@@ -0,0 +1,712 @@
+use super::{Bits, BitsIndex};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, Range};
+
+/// A named field of a [`BitSchema`].
+///
+/// The name is borrowed, or with the `alloc` feature, it can also be owned.
+///
+/// With the `serde` feature, this implements `Serialize` and `Deserialize`, so
+/// fields can be loaded from a configuration file and then passed to
+/// [`BitSchema::new`]. Without the `alloc` feature, the name can only be
+/// borrowed from the input, which many formats don't support. See
+/// `SchemaBuf` for loading a whole schema.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaField<'a> {
+	#[cfg_attr(all(feature = "serde", not(feature = "alloc")), serde(borrow))]
+	name: Name<'a>,
+	/// The bits of the field.
+	pub range: Range<u32>,
+}
+
+impl<'a> SchemaField<'a> {
+	/// Create a new field.
+	pub const fn new(name: &'a str, range: Range<u32>) -> Self {
+		Self {
+			name: Name::Borrowed(name),
+			range,
+		}
+	}
+
+	/// Create a new field that owns its name.
+	///
+	/// This is only available with the `alloc` feature.
+	#[cfg(feature = "alloc")]
+	pub fn owned(name: String, range: Range<u32>) -> Self {
+		Self {
+			name: Name::Owned(name),
+			range,
+		}
+	}
+
+	/// The name of the field.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+}
+
+/// The name of a [`SchemaField`].
+///
+/// This compares, hashes and formats as the `str` it contains, no matter if
+/// it is borrowed or owned.
+#[derive(Clone)]
+enum Name<'a> {
+	Borrowed(&'a str),
+	#[cfg(feature = "alloc")]
+	Owned(String),
+}
+
+impl Deref for Name<'_> {
+	type Target = str;
+
+	fn deref(&self) -> &str {
+		match self {
+			Name::Borrowed(name) => name,
+			#[cfg(feature = "alloc")]
+			Name::Owned(name) => name,
+		}
+	}
+}
+
+impl PartialEq for Name<'_> {
+	fn eq(&self, other: &Self) -> bool {
+		**self == **other
+	}
+}
+
+impl Eq for Name<'_> {}
+
+impl Hash for Name<'_> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		(**self).hash(state)
+	}
+}
+
+impl fmt::Debug for Name<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&**self, f)
+	}
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Name<'_> {
+	fn format(&self, f: defmt::Formatter) {
+		defmt::write!(f, "{=str}", &**self)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Name<'_> {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(self)
+	}
+}
+
+#[cfg(all(feature = "serde", not(feature = "alloc")))]
+impl<'de: 'a, 'a> serde::Deserialize<'de> for Name<'a> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		serde::Deserialize::deserialize(deserializer).map(Name::Borrowed)
+	}
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+impl<'de> serde::Deserialize<'de> for Name<'_> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		serde::Deserialize::deserialize(deserializer).map(Name::Owned)
+	}
+}
+
+/// The error returned by fallible [`BitSchema`] operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SchemaError {
+	/// The field with this index is inverted or doesn't fit in the integer.
+	OutOfRange { field: usize },
+	/// The fields with these indices overlap.
+	Overlap { first: usize, second: usize },
+	/// The fields with these indices have the same name.
+	DuplicateName { first: usize, second: usize },
+	/// There is no field with the given name.
+	UnknownField,
+}
+
+impl fmt::Display for SchemaError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SchemaError::OutOfRange { field } => write!(f, "field {} is out of range", field),
+			SchemaError::Overlap { first, second } => {
+				write!(f, "fields {} and {} overlap", first, second)
+			}
+			SchemaError::DuplicateName { first, second } => {
+				write!(f, "fields {} and {} have the same name", first, second)
+			}
+			SchemaError::UnknownField => write!(f, "unknown field"),
+		}
+	}
+}
+
+/// The error returned by [`SchemaOverlay::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OverlayError {
+	/// The tag range is inverted or doesn't fit in the integer.
+	TagOutOfRange,
+	/// The tag of the variant with this index doesn't fit in the tag range.
+	TagTooWide { variant: usize },
+	/// The variants with these indices have the same tag.
+	DuplicateTag { first: usize, second: usize },
+}
+
+impl fmt::Display for OverlayError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			OverlayError::TagOutOfRange => write!(f, "tag is out of range"),
+			OverlayError::TagTooWide { variant } => {
+				write!(f, "tag of variant {} doesn't fit", variant)
+			}
+			OverlayError::DuplicateTag { first, second } => {
+				write!(f, "variants {} and {} have the same tag", first, second)
+			}
+		}
+	}
+}
+
+/// The error returned by [`validate_partition`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PartitionError {
+	/// The range with this index is inverted or doesn't fit in the integer.
+	OutOfRange { field: usize },
+	/// The ranges with these indices overlap.
+	Overlap { first: usize, second: usize },
+	/// These bits are not part of any range.
+	Gap { start: u32, end: u32 },
+}
+
+impl fmt::Display for PartitionError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			PartitionError::OutOfRange { field } => write!(f, "field {} is out of range", field),
+			PartitionError::Overlap { first, second } => {
+				write!(f, "fields {} and {} overlap", first, second)
+			}
+			PartitionError::Gap { start, end } => write!(f, "bits {}..{} are not used", start, end),
+		}
+	}
+}
+
+/// The error returned when reserved bits are not zero.
+///
+/// Contains the range of reserved bits that has a bit set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReservedBitsSet {
+	/// The first bit of the range.
+	pub start: u32,
+	/// The end of the range, exclusive.
+	pub end: u32,
+}
+
+impl fmt::Display for ReservedBitsSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "reserved bits {}..{} are not zero", self.start, self.end)
+	}
+}
+
+/// Check that a list of ranges covers every bit of `T` exactly once.
+///
+/// The ranges may be in any order. Empty ranges are allowed, and ignored.
+/// Unused bits, such as reserved bits, have to be listed explicitly.
+///
+/// For a gap, the first one is reported.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{validate_partition, PartitionError};
+///
+/// assert_eq!(validate_partition::<u16>(&[8..16, 0..3, 3..8]), Ok(()));
+/// assert_eq!(
+///     validate_partition::<u16>(&[0..4, 3..16]),
+///     Err(PartitionError::Overlap { first: 0, second: 1 }),
+/// );
+/// assert_eq!(
+///     validate_partition::<u16>(&[0..4, 12..16]),
+///     Err(PartitionError::Gap { start: 4, end: 12 }),
+/// );
+/// ```
+pub fn validate_partition<T: Bits>(ranges: &[Range<u32>]) -> Result<(), PartitionError> {
+	for (i, a) in ranges.iter().enumerate() {
+		if a.start > a.end || a.end > T::N_BITS {
+			return Err(PartitionError::OutOfRange { field: i });
+		}
+		for (j, b) in ranges[..i].iter().enumerate() {
+			if a.start < b.end && b.start < a.end && !a.is_empty() && !b.is_empty() {
+				return Err(PartitionError::Overlap {
+					first: j,
+					second: i,
+				});
+			}
+		}
+	}
+	// Without overlaps, follow the ranges from bit 0 until there is a gap.
+	let mut start = 0;
+	while start < T::N_BITS {
+		match ranges.iter().find(|r| r.start == start && r.end > start) {
+			Some(r) => start = r.end,
+			None => {
+				let end = ranges
+					.iter()
+					.map(|r| r.start)
+					.filter(|&s| s > start)
+					.min()
+					.unwrap_or(T::N_BITS);
+				return Err(PartitionError::Gap { start, end });
+			}
+		}
+	}
+	Ok(())
+}
+
+/// A layout of named fields within an integer of type `T`, defined at runtime.
+///
+/// The fields are validated when the schema is created: they must fit within
+/// `T`, must not overlap, and must have unique names.
+///
+/// See [`BitField`][crate::BitField] for fields that are known at compile time.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitSchema, SchemaField};
+///
+/// let fields = [
+///     SchemaField::new("ENABLE", 0..1),
+///     SchemaField::new("MODE", 1..4),
+///     SchemaField::new("DIV", 8..16),
+/// ];
+/// let schema = BitSchema::<u16>::new(&fields).unwrap();
+///
+/// assert_eq!(schema.get(0x2A0B, "MODE"), Some(5));
+/// assert_eq!(schema.get(0x2A0B, "DIV"), Some(0x2A));
+/// assert_eq!(schema.get(0x2A0B, "FOO"), None);
+///
+/// let mut reg = 0u16;
+/// schema.set(&mut reg, "DIV", 3).unwrap();
+/// assert_eq!(reg, 0x300);
+/// ```
+///
+/// With the `serde` feature, this implements `Serialize`, as its name and
+/// fields. Use `SchemaBuf` to deserialize a schema.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound = ""))]
+pub struct BitSchema<'a, T> {
+	name: &'a str,
+	fields: &'a [SchemaField<'a>],
+	#[cfg_attr(feature = "serde", serde(skip))]
+	_type: PhantomData<T>,
+}
+
+impl<'a, T> BitSchema<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	/// Create a schema from a list of fields, checking that they are valid.
+	pub fn new(fields: &'a [SchemaField<'a>]) -> Result<Self, SchemaError> {
+		for (i, a) in fields.iter().enumerate() {
+			if a.range.start > a.range.end || a.range.end > T::N_BITS {
+				return Err(SchemaError::OutOfRange { field: i });
+			}
+			for (j, b) in fields[..i].iter().enumerate() {
+				if a.name() == b.name() {
+					return Err(SchemaError::DuplicateName {
+						first: j,
+						second: i,
+					});
+				}
+				if a.range.start < b.range.end && b.range.start < a.range.end {
+					return Err(SchemaError::Overlap {
+						first: j,
+						second: i,
+					});
+				}
+			}
+		}
+		Ok(Self {
+			name: "",
+			fields,
+			_type: PhantomData,
+		})
+	}
+
+	/// Give the schema a name, which is used by [`display`][Self::display].
+	pub fn with_name(self, name: &'a str) -> Self {
+		Self { name, ..self }
+	}
+
+	/// The name of this schema, or `""` if it has none.
+	pub fn name(&self) -> &'a str {
+		self.name
+	}
+
+	/// The fields of this schema, in the order they were given.
+	pub fn fields(&self) -> core::slice::Iter<'a, SchemaField<'a>> {
+		self.fields.iter()
+	}
+
+	/// Look up a field by name.
+	pub fn field(&self, name: &str) -> Option<&'a SchemaField<'a>> {
+		self.fields.iter().find(|f| f.name() == name)
+	}
+
+	/// Get the value of the field with the given name.
+	///
+	/// Returns `None` if there is no such field.
+	pub fn get(&self, value: T, name: &str) -> Option<T::Bits> {
+		self.field(name).map(|f| value.bits(f.range.clone()))
+	}
+
+	/// Set the value of the field with the given name.
+	///
+	/// Panics when the bits of `bits` that don't fit in the field are not 0,
+	/// just like [`Bits::set_bits`].
+	pub fn set(&self, value: &mut T, name: &str, bits: T::Bits) -> Result<(), SchemaError> {
+		let field = self.field(name).ok_or(SchemaError::UnknownField)?;
+		value.set_bits(field.range.clone(), bits);
+		Ok(())
+	}
+
+	/// Iterate over the name and value of every field, in order.
+	pub fn values(&self, value: T) -> impl Iterator<Item = (&'a str, T::Bits)> + 'a
+	where
+		T: 'a,
+	{
+		self.fields
+			.iter()
+			.map(move |f| (f.name(), value.bits(f.range.clone())))
+	}
+
+	/// Check that all bits of `value` that are not part of any field are zero.
+	///
+	/// On success, returns `value`, so this can be used as a checked
+	/// constructor for values received from elsewhere. Otherwise, the error
+	/// contains the whole run of unused bits that contains the lowest bit that
+	/// is set.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitSchema, ReservedBitsSet, SchemaField};
+	///
+	/// let fields = [SchemaField::new("MODE", 0..4), SchemaField::new("DIV", 8..16)];
+	/// let schema = BitSchema::<u16>::new(&fields).unwrap();
+	///
+	/// assert_eq!(schema.check_reserved_zero(0x2A0B), Ok(0x2A0B));
+	/// assert_eq!(
+	///     schema.check_reserved_zero(0x2A4B),
+	///     Err(ReservedBitsSet { start: 4, end: 8 }),
+	/// );
+	/// ```
+	pub fn check_reserved_zero(&self, value: T) -> Result<T, ReservedBitsSet> {
+		let used = |i: u32| self.fields.iter().any(|f| f.range.contains(&i));
+		match (0..T::N_BITS).find(|&i| value.bit(i) && !used(i)) {
+			None => Ok(value),
+			Some(i) => {
+				let start = (0..i).rev().find(|&j| used(j)).map_or(0, |j| j + 1);
+				let end = (i..T::N_BITS).find(|&j| used(j)).unwrap_or(T::N_BITS);
+				Err(ReservedBitsSet { start, end })
+			}
+		}
+	}
+
+	/// Get an object that formats `value` as its named fields.
+	///
+	/// Single bit fields are shown in decimal, fields shorter than a byte in
+	/// binary, and other fields in hexadecimal, padded to the width of the
+	/// field. Both `{}` and `{:?}` give the same output.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitSchema, SchemaField};
+	///
+	/// let fields = [
+	///     SchemaField::new("ENABLE", 0..1),
+	///     SchemaField::new("MODE", 1..4),
+	///     SchemaField::new("DIV", 8..16),
+	/// ];
+	/// let schema = BitSchema::<u16>::new(&fields).unwrap().with_name("CTRL");
+	///
+	/// assert_eq!(
+	///     schema.display(0x2A0B).to_string(),
+	///     "CTRL { ENABLE: 1, MODE: 0b101, DIV: 0x2A }",
+	/// );
+	/// ```
+	pub fn display(&self, value: T) -> SchemaDisplay<'a, T> {
+		SchemaDisplay {
+			schema: *self,
+			value,
+		}
+	}
+}
+
+/// An owned list of named fields, from which a [`BitSchema`] can be created.
+///
+/// With the `serde` feature, this implements `Serialize` and `Deserialize`, in
+/// the same format as [`BitSchema`], so layouts can be loaded from
+/// configuration files. The fields are only checked when the schema is
+/// created.
+///
+/// This is only available with the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(all(feature = "alloc", feature = "serde"))] {
+/// use intbits::SchemaBuf;
+///
+/// let json = r#"{
+///     "name": "CTRL",
+///     "fields": [
+///         {"name": "MODE", "range": {"start": 0, "end": 4}},
+///         {"name": "DIV", "range": {"start": 8, "end": 16}}
+///     ]
+/// }"#;
+/// let buf: SchemaBuf = serde_json::from_reader(json.as_bytes()).unwrap();
+/// let schema = buf.schema::<u16>().unwrap();
+/// assert_eq!(schema.get(0x2A0B, "DIV"), Some(0x2A));
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaBuf {
+	/// The name of the schema, or `""` if it has none.
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub name: String,
+	/// The fields of the schema.
+	pub fields: Vec<SchemaField<'static>>,
+}
+
+#[cfg(feature = "alloc")]
+impl SchemaBuf {
+	/// Create a schema from these fields, checking that they are valid.
+	///
+	/// See [`BitSchema::new`].
+	pub fn schema<T>(&self) -> Result<BitSchema<'_, T>, SchemaError>
+	where
+		T: Bits,
+		u32: BitsIndex<T>,
+	{
+		BitSchema::new(&self.fields).map(|s| s.with_name(&self.name))
+	}
+}
+
+/// Several [`BitSchema`]s over the same bits, selected by a tag field.
+///
+/// This is like a C union of bit fields: the value of the bits in the tag
+/// range decides which schema applies to the rest of the bits. The schemas
+/// may include the tag range as one of their fields.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitSchema, SchemaField, SchemaOverlay};
+///
+/// let timer = [SchemaField::new("MODE", 0..1), SchemaField::new("PERIOD", 1..8)];
+/// let pwm = [
+///     SchemaField::new("MODE", 0..1),
+///     SchemaField::new("DUTY", 1..5),
+///     SchemaField::new("INVERT", 7..8),
+/// ];
+/// let variants = [
+///     (0, BitSchema::<u8>::new(&timer).unwrap().with_name("TIMER")),
+///     (1, BitSchema::<u8>::new(&pwm).unwrap().with_name("PWM")),
+/// ];
+/// let overlay = SchemaOverlay::new(0..1, &variants).unwrap();
+///
+/// assert_eq!(overlay.get(0x0A, "PERIOD"), Some(5));
+/// assert_eq!(overlay.get(0x0B, "PERIOD"), None);
+/// assert_eq!(overlay.get(0x0B, "DUTY"), Some(5));
+/// assert_eq!(
+///     overlay.display(0x8B).unwrap().to_string(),
+///     "PWM { MODE: 1, DUTY: 0b0101, INVERT: 1 }",
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct SchemaOverlay<'a, T: Bits> {
+	tag: Range<u32>,
+	variants: &'a [(T::Bits, BitSchema<'a, T>)],
+}
+
+impl<'a, T> SchemaOverlay<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	/// Create an overlay from the tag range and a list of tags with their
+	/// schema, checking that the tags are valid.
+	pub fn new(
+		tag: Range<u32>,
+		variants: &'a [(T::Bits, BitSchema<'a, T>)],
+	) -> Result<Self, OverlayError> {
+		if tag.start > tag.end || tag.end > T::N_BITS {
+			return Err(OverlayError::TagOutOfRange);
+		}
+		let width = tag.end - tag.start;
+		let zero = T::Bits::default();
+		for (i, &(a, _)) in variants.iter().enumerate() {
+			if width < <T::Bits as Bits>::N_BITS && a >> width != zero {
+				return Err(OverlayError::TagTooWide { variant: i });
+			}
+			if let Some(j) = variants[..i].iter().position(|&(b, _)| a == b) {
+				return Err(OverlayError::DuplicateTag {
+					first: j,
+					second: i,
+				});
+			}
+		}
+		Ok(Self { tag, variants })
+	}
+
+	/// The range of the tag field.
+	pub fn tag_range(&self) -> Range<u32> {
+		self.tag.clone()
+	}
+
+	/// The tags and their schema, in the order they were given.
+	pub fn variants(&self) -> core::slice::Iter<'a, (T::Bits, BitSchema<'a, T>)> {
+		self.variants.iter()
+	}
+
+	/// Get the value of the tag field.
+	pub fn tag(&self, value: T) -> T::Bits {
+		value.bits(self.tag.clone())
+	}
+
+	/// Get the schema selected by the tag of `value`.
+	///
+	/// Returns `None` if no variant has that tag.
+	pub fn schema(&self, value: T) -> Option<BitSchema<'a, T>> {
+		let tag = self.tag(value);
+		self.variants
+			.iter()
+			.find(|(t, _)| *t == tag)
+			.map(|(_, s)| *s)
+	}
+
+	/// Get the value of the field with the given name, in the schema selected
+	/// by the tag of `value`.
+	///
+	/// Returns `None` if there is no such variant or field.
+	pub fn get(&self, value: T, name: &str) -> Option<T::Bits> {
+		self.schema(value)?.get(value, name)
+	}
+
+	/// Set the value of the field with the given name, in the schema selected
+	/// by the tag of `value`.
+	///
+	/// To switch to another variant, set the tag first with
+	/// [`Bits::set_bits`] and [`tag_range`][Self::tag_range].
+	///
+	/// Panics when the bits of `bits` that don't fit in the field are not 0,
+	/// just like [`Bits::set_bits`].
+	pub fn set(&self, value: &mut T, name: &str, bits: T::Bits) -> Result<(), SchemaError> {
+		let schema = self.schema(*value).ok_or(SchemaError::UnknownField)?;
+		schema.set(value, name, bits)
+	}
+
+	/// Get an object that formats `value` using the schema selected by its
+	/// tag.
+	///
+	/// Returns `None` if no variant has that tag. See [`BitSchema::display`].
+	pub fn display(&self, value: T) -> Option<SchemaDisplay<'a, T>> {
+		self.schema(value).map(|s| s.display(value))
+	}
+}
+
+/// Formats a value using the fields of a [`BitSchema`].
+///
+/// Created by [`BitSchema::display`].
+#[derive(Clone, Copy)]
+pub struct SchemaDisplay<'a, T> {
+	schema: BitSchema<'a, T>,
+	value: T,
+}
+
+impl<'a, T> fmt::Display for SchemaDisplay<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if !self.schema.name.is_empty() {
+			write!(f, "{} ", self.schema.name)?;
+		}
+		f.write_str("{")?;
+		for (i, field) in self.schema.fields.iter().enumerate() {
+			let width = (field.range.end - field.range.start) as usize;
+			let bits = self.value.bits(field.range.clone());
+			f.write_str(if i == 0 { " " } else { ", " })?;
+			if width <= 1 {
+				write!(f, "{}: {}", field.name(), bits)?;
+			} else if width < 8 {
+				write!(f, "{}: {:#0w$b}", field.name(), bits, w = width + 2)?;
+			} else {
+				write!(
+					f,
+					"{}: {:#0w$X}",
+					field.name(),
+					bits,
+					w = width.div_ceil(4) + 2
+				)?;
+			}
+		}
+		f.write_str(if self.schema.fields.is_empty() {
+			"}"
+		} else {
+			" }"
+		})
+	}
+}
+
+impl<'a, T> fmt::Debug for SchemaDisplay<'a, T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+#[cfg(feature = "defmt")]
+impl<'a, T> defmt::Format for SchemaDisplay<'a, T>
+where
+	T: Bits,
+	T::Bits: defmt::Format,
+	u32: BitsIndex<T>,
+{
+	fn format(&self, f: defmt::Formatter) {
+		if !self.schema.name.is_empty() {
+			defmt::write!(f, "{=str} ", self.schema.name);
+		}
+		defmt::write!(f, "{{");
+		for (i, field) in self.schema.fields.iter().enumerate() {
+			let bits = self.value.bits(field.range.clone());
+			let sep = if i == 0 { " " } else { ", " };
+			defmt::write!(f, "{=str}{=str}: {:#x}", sep, field.name(), bits);
+		}
+		if !self.schema.fields.is_empty() {
+			defmt::write!(f, " ");
+		}
+		defmt::write!(f, "}}");
+	}
+}
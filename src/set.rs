@@ -0,0 +1,282 @@
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+/// An integer used as a set of small numbers.
+///
+/// Number `i` is in the set when bit `i` of the integer is set. The numbers
+/// that can be stored are `0` up to the number of bits of the integer.
+///
+/// The `|`, `&`, `-` and `^` operators give the union, intersection,
+/// difference and symmetric difference of two sets.
+///
+/// # Example
+///
+/// ```
+/// use intbits::IntSet;
+///
+/// let mut a = IntSet::<u64>::new();
+/// a.insert(3);
+/// a.insert(10);
+/// a.insert(42);
+/// assert!(a.contains(10));
+/// assert_eq!(a.len(), 3);
+///
+/// let b: IntSet<u64> = [10, 11].iter().copied().collect();
+/// assert_eq!((a & b).iter().collect::<Vec<_>>(), [10]);
+/// assert_eq!((a - b).iter().collect::<Vec<_>>(), [3, 42]);
+/// assert_eq!(format!("{:?}", a | b), "{3, 10, 11, 42}");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct IntSet<T>(pub T);
+
+/// An iterator over the numbers in an [`IntSet`], in increasing order.
+///
+/// Created by [`IntSet::iter`].
+#[derive(Clone, Debug)]
+pub struct IntSetIter<T>(T);
+
+macro_rules! int_set {
+	($($t:ident),*) => {$(
+		impl IntSet<$t> {
+			/// The empty set.
+			pub const EMPTY: Self = IntSet(0);
+
+			/// The set containing all numbers that fit.
+			pub const FULL: Self = IntSet(!0);
+
+			/// Create an empty set.
+			#[inline]
+			pub const fn new() -> Self {
+				IntSet(0)
+			}
+
+			/// Create a set from the bits of an integer.
+			#[inline]
+			pub const fn from_bits(bits: $t) -> Self {
+				IntSet(bits)
+			}
+
+			/// Get the bits of the integer.
+			#[inline]
+			pub const fn bits(self) -> $t {
+				self.0
+			}
+
+			/// Add a number to the set.
+			///
+			/// Returns whether it was newly added.
+			///
+			/// Panics if the number doesn't fit.
+			#[inline]
+			pub fn insert(&mut self, i: u32) -> bool {
+				let bit = Self::bit(i);
+				let new = self.0 & bit == 0;
+				self.0 |= bit;
+				new
+			}
+
+			/// Remove a number from the set.
+			///
+			/// Returns whether it was in the set.
+			///
+			/// Panics if the number doesn't fit.
+			#[inline]
+			pub fn remove(&mut self, i: u32) -> bool {
+				let bit = Self::bit(i);
+				let present = self.0 & bit != 0;
+				self.0 &= !bit;
+				present
+			}
+
+			/// Check whether a number is in the set.
+			///
+			/// Numbers that don't fit are never in the set.
+			#[inline]
+			pub const fn contains(self, i: u32) -> bool {
+				i < $t::BITS && self.0 >> i & 1 != 0
+			}
+
+			/// The number of numbers in the set.
+			#[inline]
+			pub const fn len(self) -> u32 {
+				self.0.count_ones()
+			}
+
+			/// Check whether the set is empty.
+			#[inline]
+			pub const fn is_empty(self) -> bool {
+				self.0 == 0
+			}
+
+			/// The smallest number in the set.
+			#[inline]
+			pub const fn first(self) -> Option<u32> {
+				if self.0 == 0 {
+					None
+				} else {
+					Some(self.0.trailing_zeros())
+				}
+			}
+
+			/// The largest number in the set.
+			#[inline]
+			pub const fn last(self) -> Option<u32> {
+				if self.0 == 0 {
+					None
+				} else {
+					Some($t::BITS - 1 - self.0.leading_zeros())
+				}
+			}
+
+			/// The numbers in either set.
+			#[inline]
+			pub const fn union(self, other: Self) -> Self {
+				IntSet(self.0 | other.0)
+			}
+
+			/// The numbers in both sets.
+			#[inline]
+			pub const fn intersection(self, other: Self) -> Self {
+				IntSet(self.0 & other.0)
+			}
+
+			/// The numbers in `self` but not in `other`.
+			#[inline]
+			pub const fn difference(self, other: Self) -> Self {
+				IntSet(self.0 & !other.0)
+			}
+
+			/// The numbers in exactly one of the sets.
+			#[inline]
+			pub const fn symmetric_difference(self, other: Self) -> Self {
+				IntSet(self.0 ^ other.0)
+			}
+
+			/// Check whether all numbers of `self` are in `other`.
+			#[inline]
+			pub const fn is_subset(self, other: Self) -> bool {
+				self.0 & !other.0 == 0
+			}
+
+			/// Check whether the sets have no numbers in common.
+			#[inline]
+			pub const fn is_disjoint(self, other: Self) -> bool {
+				self.0 & other.0 == 0
+			}
+
+			/// Iterate over the numbers in the set, in increasing order.
+			#[inline]
+			pub fn iter(self) -> IntSetIter<$t> {
+				IntSetIter(self.0)
+			}
+
+			#[inline]
+			fn bit(i: u32) -> $t {
+				check!(i < $t::BITS, invalid_bit_index);
+				1 << i
+			}
+		}
+
+		impl Iterator for IntSetIter<$t> {
+			type Item = u32;
+
+			#[inline]
+			fn next(&mut self) -> Option<u32> {
+				if self.0 == 0 {
+					None
+				} else {
+					let i = self.0.trailing_zeros();
+					self.0 &= self.0 - 1;
+					Some(i)
+				}
+			}
+
+			#[inline]
+			fn size_hint(&self) -> (usize, Option<usize>) {
+				let n = self.0.count_ones() as usize;
+				(n, Some(n))
+			}
+		}
+
+		impl DoubleEndedIterator for IntSetIter<$t> {
+			#[inline]
+			fn next_back(&mut self) -> Option<u32> {
+				if self.0 == 0 {
+					None
+				} else {
+					let i = $t::BITS - 1 - self.0.leading_zeros();
+					self.0 &= !(1 << i);
+					Some(i)
+				}
+			}
+		}
+
+		impl ExactSizeIterator for IntSetIter<$t> {}
+
+		impl IntoIterator for IntSet<$t> {
+			type Item = u32;
+			type IntoIter = IntSetIter<$t>;
+
+			#[inline]
+			fn into_iter(self) -> IntSetIter<$t> {
+				self.iter()
+			}
+		}
+
+		impl FromIterator<u32> for IntSet<$t> {
+			fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+				let mut set = Self::new();
+				set.extend(iter);
+				set
+			}
+		}
+
+		impl Extend<u32> for IntSet<$t> {
+			fn extend<I: IntoIterator<Item = u32>>(&mut self, iter: I) {
+				for i in iter {
+					self.insert(i);
+				}
+			}
+		}
+
+		impl fmt::Debug for IntSet<$t> {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.debug_set().entries(self.iter()).finish()
+			}
+		}
+
+		/// Formatted as the underlying integer in binary, rather than as a
+		/// list of elements.
+		#[cfg(feature = "defmt")]
+		impl defmt::Format for IntSet<$t> {
+			fn format(&self, f: defmt::Formatter) {
+				defmt::write!(f, "IntSet({=?:#b})", self.0);
+			}
+		}
+
+		int_set!(@op $t, BitOr bitor BitOrAssign bitor_assign union);
+		int_set!(@op $t, BitAnd bitand BitAndAssign bitand_assign intersection);
+		int_set!(@op $t, Sub sub SubAssign sub_assign difference);
+		int_set!(@op $t, BitXor bitxor BitXorAssign bitxor_assign symmetric_difference);
+	)*};
+	(@op $t:ident, $op:ident $f:ident $op_assign:ident $f_assign:ident $method:ident) => {
+		impl $op for IntSet<$t> {
+			type Output = Self;
+
+			#[inline]
+			fn $f(self, other: Self) -> Self {
+				self.$method(other)
+			}
+		}
+
+		impl $op_assign for IntSet<$t> {
+			#[inline]
+			fn $f_assign(&mut self, other: Self) {
+				*self = self.$method(other);
+			}
+		}
+	};
+}
+
+int_set!(u8, u16, u32, u64, u128, usize);
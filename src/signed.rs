@@ -0,0 +1,66 @@
+// Helpers for interpreting fields as signed and fixed-point numbers.
+//
+// Fields are passed as `u128`, together with `ones`: the field extracted from
+// a value with all bits set, which gives the width of the field.
+
+use core::convert::TryFrom;
+
+/// Sign extend a two's complement field.
+pub(crate) fn sign_extend(bits: u128, ones: u128) -> i128 {
+	if ones == 0 {
+		return 0;
+	}
+	let shift = ones.leading_zeros();
+	((bits << shift) as i128) >> shift
+}
+
+/// The sign bit of a field, or 0 for an empty field.
+fn sign_bit(ones: u128) -> u128 {
+	check!(ones <= u64::MAX as u128, bit_range_too_wide);
+	((ones >> 1) + 1) & ones
+}
+
+/// Convert a one's complement field. Negative zero becomes zero.
+pub(crate) fn ones_complement(bits: u128, ones: u128) -> i64 {
+	if bits & sign_bit(ones) != 0 {
+		-((!bits & ones) as i64)
+	} else {
+		bits as i64
+	}
+}
+
+/// Convert a sign-magnitude field. Negative zero becomes zero.
+pub(crate) fn sign_magnitude(bits: u128, ones: u128) -> i64 {
+	let sign = sign_bit(ones);
+	if bits & sign != 0 {
+		-((bits & !sign) as i64)
+	} else {
+		bits as i64
+	}
+}
+
+/// The value of `2^-frac_bits`.
+fn q_scale(frac_bits: u32) -> f32 {
+	check!(frac_bits < 128, invalid_fractional_bits);
+	1.0 / (1u128 << frac_bits) as f32
+}
+
+pub(crate) fn q_to_f32(v: i128, frac_bits: u32) -> f32 {
+	v as f32 * q_scale(frac_bits)
+}
+
+pub(crate) fn uq_to_f32(v: u128, frac_bits: u32) -> f32 {
+	v as f32 * q_scale(frac_bits)
+}
+
+/// Calculate `v * scale / 2^frac_bits`, rounding towards negative infinity.
+pub(crate) fn q_scaled(v: i128, frac_bits: u32, scale: i64) -> i64 {
+	check!(frac_bits < 128, invalid_fractional_bits);
+	match v
+		.checked_mul(scale as i128)
+		.map(|r| i64::try_from(r >> frac_bits))
+	{
+		Some(Ok(r)) => r,
+		_ => crate::panic::fixed_point_out_of_range(),
+	}
+}
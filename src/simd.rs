@@ -0,0 +1,138 @@
+use super::range::bounds;
+use core::ops::RangeBounds;
+use core::simd::cmp::SimdPartialEq;
+use core::simd::num::{SimdInt, SimdUint};
+use core::simd::{Mask, Simd};
+
+/// Lane-wise access to bits of SIMD vectors of integers.
+///
+/// Every method does the same as the corresponding method of
+/// [`Bits`][crate::Bits], but for all lanes at once. Single bits are returned
+/// as a [`Mask`].
+///
+/// This is only available with the `portable_simd` feature, which requires a
+/// nightly compiler.
+///
+/// # Example
+///
+/// ```
+/// #![feature(portable_simd)]
+/// use core::simd::Simd;
+/// use intbits::SimdBits;
+///
+/// let records = Simd::from_array([0x12u32, 0x34, 0x56, 0x78]);
+/// assert_eq!(records.bits(4..8).to_array(), [1, 3, 5, 7]);
+/// assert_eq!(records.bit(1).to_array(), [true, false, true, false]);
+/// ```
+pub trait SimdBits {
+	/// The vector of (unsigned) integers used to represent bits of this type.
+	type Bits;
+
+	/// The mask type with one boolean per lane.
+	type Mask;
+
+	/// The number of bits of each lane.
+	const N_BITS: u32;
+
+	/// Get a specific bit of every lane.
+	///
+	/// Panics if the index is out of range.
+	fn bit(self, i: u32) -> Self::Mask;
+
+	/// Get a range of bits of every lane.
+	///
+	/// Panics when the range bounds are out of range.
+	fn bits<R: RangeBounds<u32>>(self, range: R) -> Self::Bits;
+
+	/// Set a specific bit of every lane.
+	///
+	/// Panics if the index is out of range.
+	fn set_bit(&mut self, i: u32, bit: bool);
+
+	/// Set a range of bits of every lane.
+	///
+	/// Panics when the range bounds are out of range or when the irrelevant
+	/// bits of the second argument are not 0 in any lane.
+	fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: Self::Bits);
+
+	/// Get a new vector with one bit of every lane set to a specific value.
+	///
+	/// Panics if the index is out of range.
+	fn with_bit(self, i: u32, bit: bool) -> Self
+	where
+		Self: Sized;
+
+	/// Get a new vector with a range of bits of every lane set to specific
+	/// values.
+	///
+	/// Panics when the range bounds are out of range or when the irrelevant
+	/// bits of the second argument are not 0 in any lane.
+	fn with_bits<R: RangeBounds<u32>>(self, range: R, bits: Self::Bits) -> Self
+	where
+		Self: Sized;
+}
+
+macro_rules! simd_bits {
+	($($t:ident $ut:ident $m:ident),*) => {$(
+		impl<const N: usize> SimdBits for Simd<$t, N> {
+			type Bits = Simd<$ut, N>;
+			type Mask = Mask<$m, N>;
+			const N_BITS: u32 = $t::BITS;
+
+			#[inline]
+			fn bit(self, i: u32) -> Mask<$m, N> {
+				check!(i < $t::BITS, invalid_bit_index);
+				(self.cast::<$ut>() >> Simd::splat(i as $ut) & Simd::splat(1)).simd_ne(Simd::splat(0))
+			}
+
+			#[inline]
+			fn bits<R: RangeBounds<u32>>(self, range: R) -> Simd<$ut, N> {
+				let (start, end) = bounds(&range, $t::BITS);
+				if start == end {
+					return Simd::splat(0);
+				}
+				let mask = !0 >> ($t::BITS - (end - start));
+				self.cast::<$ut>() >> Simd::splat(start as $ut) & Simd::splat(mask)
+			}
+
+			#[inline]
+			fn set_bit(&mut self, i: u32, bit: bool) {
+				check!(i < $t::BITS, invalid_bit_index);
+				let v = self.cast::<$ut>() & Simd::splat(!(1 << i)) | Simd::splat((bit as $ut) << i);
+				*self = v.cast();
+			}
+
+			#[inline]
+			fn set_bits<R: RangeBounds<u32>>(&mut self, range: R, bits: Simd<$ut, N>) {
+				let (start, end) = bounds(&range, $t::BITS);
+				let mask: $ut = if start == end { 0 } else { !0 >> ($t::BITS - (end - start)) };
+				if (bits & Simd::splat(!mask)).simd_ne(Simd::splat(0)).any() {
+					crate::panic::bits_outside_range();
+				}
+				if start == end {
+					return;
+				}
+				let shift = Simd::splat(start as $ut);
+				let v = self.cast::<$ut>() & !(Simd::splat(mask) << shift) | bits << shift;
+				*self = v.cast();
+			}
+
+			#[inline]
+			fn with_bit(mut self, i: u32, bit: bool) -> Self {
+				self.set_bit(i, bit);
+				self
+			}
+
+			#[inline]
+			fn with_bits<R: RangeBounds<u32>>(mut self, range: R, bits: Simd<$ut, N>) -> Self {
+				self.set_bits(range, bits);
+				self
+			}
+		}
+	)*};
+}
+
+simd_bits!(
+	i8 u8 i8, u8 u8 i8, i16 u16 i16, u16 u16 i16, i32 u32 i32, u32 u32 i32,
+	i64 u64 i64, u64 u64 i64, isize usize isize, usize usize isize
+);
@@ -0,0 +1,537 @@
+use super::range::bounds_usize;
+use core::convert::TryInto;
+use core::ops::{Range, RangeBounds};
+
+/// The order of bits within each byte of a byte slice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitOrder {
+	/// Bit 0 is the least significant bit of the first byte.
+	///
+	/// Multi-bit values are little endian: the first bit is the least
+	/// significant bit of the value.
+	LsbFirst,
+	/// Bit 0 is the most significant bit of the first byte.
+	///
+	/// Multi-bit values are big endian: the first bit is the most significant
+	/// bit of the value.
+	MsbFirst,
+}
+
+/// Access to individual bits of byte slices.
+///
+/// Bit indices run across the whole slice, and ranges can start and end at
+/// any bit, regardless of byte boundaries. Results are returned as a
+/// [`u128`], so a range can be at most 128 bits wide.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, SliceBits};
+///
+/// let buf = [0x12, 0x34, 0x56];
+/// assert_eq!(buf[..].bits(4..16, BitOrder::LsbFirst), 0x341);
+/// assert_eq!(buf[..].bits(4..16, BitOrder::MsbFirst), 0x234);
+///
+/// let mut buf = [0u8; 3];
+/// buf.set_bits(6..10, 0b1001, BitOrder::MsbFirst);
+/// assert_eq!(buf, [0b10, 0b0100_0000, 0]);
+/// ```
+pub trait SliceBits {
+	/// Get a specific bit.
+	///
+	/// Panics if the index is out of range.
+	fn bit(&self, i: usize, order: BitOrder) -> bool;
+
+	/// Get a range of bits.
+	///
+	/// The bits are returned in the least significant bits of the return
+	/// value. The other bits, if any, will be 0.
+	///
+	/// Panics when the range bounds are out of range, or when the range is
+	/// wider than 128 bits.
+	fn bits<R: RangeBounds<usize>>(&self, range: R, order: BitOrder) -> u128;
+
+	/// Set a specific bit.
+	///
+	/// Panics if the index is out of range.
+	fn set_bit(&mut self, i: usize, bit: bool, order: BitOrder);
+
+	/// Set a range of bits.
+	///
+	/// The bits should be given in the least significant bits of the second
+	/// argument. The other bits should be 0.
+	///
+	/// Panics when the range bounds are out of range, when the range is wider
+	/// than 128 bits, or when the irrelevant bits of the second argument are
+	/// not 0.
+	fn set_bits<R: RangeBounds<usize>>(&mut self, range: R, bits: u128, order: BitOrder);
+}
+
+/// The shift of bit `i` within its byte.
+#[inline]
+fn shift(i: usize, n: usize, order: BitOrder) -> usize {
+	match order {
+		BitOrder::LsbFirst => i % 8,
+		BitOrder::MsbFirst => 8 - i % 8 - n,
+	}
+}
+
+impl SliceBits for [u8] {
+	#[inline]
+	fn bit(&self, i: usize, order: BitOrder) -> bool {
+		check!(i < self.len() * 8, invalid_bit_index);
+		self[i / 8] >> shift(i, 1, order) & 1 != 0
+	}
+
+	fn bits<R: RangeBounds<usize>>(&self, range: R, order: BitOrder) -> u128 {
+		let (start, end) = bounds_usize(&range, self.len() * 8);
+		check!(end - start <= 128, bit_range_too_wide);
+		let mut result = 0u128;
+		let mut i = start;
+		while i < end {
+			let n = (8 - i % 8).min(end - i);
+			let bits = (self[i / 8] >> shift(i, n, order)) as u128 & (0xFF >> (8 - n));
+			result = match order {
+				BitOrder::LsbFirst => result | bits << (i - start),
+				BitOrder::MsbFirst => result << n | bits,
+			};
+			i += n;
+		}
+		result
+	}
+
+	#[inline]
+	fn set_bit(&mut self, i: usize, bit: bool, order: BitOrder) {
+		check!(i < self.len() * 8, invalid_bit_index);
+		let shift = shift(i, 1, order);
+		let byte = &mut self[i / 8];
+		*byte = *byte & !(1 << shift) | (bit as u8) << shift;
+	}
+
+	fn set_bits<R: RangeBounds<usize>>(&mut self, range: R, bits: u128, order: BitOrder) {
+		let (start, end) = bounds_usize(&range, self.len() * 8);
+		check!(end - start <= 128, bit_range_too_wide);
+		if bits.checked_shr((end - start) as u32).unwrap_or(0) != 0 {
+			crate::panic::bits_outside_range();
+		}
+		let mut i = start;
+		while i < end {
+			let n = (8 - i % 8).min(end - i);
+			let value = match order {
+				BitOrder::LsbFirst => bits >> (i - start),
+				BitOrder::MsbFirst => bits >> (end - i - n),
+			} as u8;
+			let shift = shift(i, n, order);
+			let mask = 0xFF >> (8 - n) << shift;
+			let byte = &mut self[i / 8];
+			*byte = *byte & !mask | value << shift & mask;
+			i += n;
+		}
+	}
+}
+
+/// Copy `len` bits from `src`, starting at bit `src_offset`, to `dst`,
+/// starting at bit `dst_offset`.
+///
+/// When both offsets are at the same position within a byte, the bulk of the
+/// bits are copied as whole bytes. Otherwise, the bits are copied in 64-bit
+/// chunks.
+///
+/// Panics when either range of bits is out of range.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{copy_bits_slice, BitOrder};
+///
+/// let src = [0xAB, 0xCD];
+/// let mut dst = [0u8; 3];
+/// copy_bits_slice(&src, 4, &mut dst, 8, 8, BitOrder::LsbFirst);
+/// assert_eq!(dst, [0, 0xDA, 0]);
+/// ```
+pub fn copy_bits_slice(
+	src: &[u8],
+	src_offset: usize,
+	dst: &mut [u8],
+	dst_offset: usize,
+	len: usize,
+	order: BitOrder,
+) {
+	let src_end = src_offset.checked_add(len);
+	let dst_end = dst_offset.checked_add(len);
+	match (src_end, dst_end) {
+		(Some(s), Some(d)) if s <= src.len() * 8 && d <= dst.len() * 8 => {}
+		_ => crate::panic::invalid_bit_range(),
+	}
+	let mut done = 0;
+	if src_offset % 8 == dst_offset % 8 {
+		// Same alignment: copy the head bits, then whole bytes.
+		let head = ((8 - src_offset % 8) % 8).min(len);
+		let bits = src.bits(src_offset..src_offset + head, order);
+		dst.set_bits(dst_offset..dst_offset + head, bits, order);
+		done = head;
+		let bytes = (len - done) / 8;
+		let s = (src_offset + done) / 8;
+		let d = (dst_offset + done) / 8;
+		dst[d..d + bytes].copy_from_slice(&src[s..s + bytes]);
+		done += bytes * 8;
+	}
+	while done < len {
+		let n = (len - done).min(64);
+		let bits = src.bits(src_offset + done..src_offset + done + n, order);
+		dst.set_bits(dst_offset + done..dst_offset + done + n, bits, order);
+		done += n;
+	}
+}
+
+/// Move every bit `k` positions towards the end of the buffer.
+///
+/// `lsb` tells whether bit 0 of a byte is its least significant bit.
+fn shift_towards_end(buf: &mut [u8], k: usize, lsb: bool) {
+	let bytes = k / 8;
+	let r = (k % 8) as u32;
+	let get = |buf: &[u8], j: usize| if j < bytes { 0 } else { buf[j - bytes] };
+	for j in (0..buf.len()).rev() {
+		let (cur, prev) = (get(buf, j), if j == 0 { 0 } else { get(buf, j - 1) });
+		buf[j] = match (lsb, r) {
+			(_, 0) => cur,
+			(true, _) => cur << r | prev >> (8 - r),
+			(false, _) => cur >> r | prev << (8 - r),
+		};
+	}
+}
+
+/// Move every bit `k` positions towards the start of the buffer.
+///
+/// `lsb` tells whether bit 0 of a byte is its least significant bit.
+fn shift_towards_start(buf: &mut [u8], k: usize, lsb: bool) {
+	let bytes = k / 8;
+	let r = (k % 8) as u32;
+	let get = |buf: &[u8], j: usize| match j.checked_add(bytes) {
+		Some(j) if j < buf.len() => buf[j],
+		_ => 0,
+	};
+	for j in 0..buf.len() {
+		let (cur, next) = (get(buf, j), get(buf, j + 1));
+		buf[j] = match (lsb, r) {
+			(_, 0) => cur,
+			(true, _) => cur >> r | next << (8 - r),
+			(false, _) => cur << r | next >> (8 - r),
+		};
+	}
+}
+
+/// Shift an entire buffer `k` bits to the left, as if it were one large
+/// integer.
+///
+/// With [`BitOrder::LsbFirst`], the buffer is treated as a little endian
+/// integer, so bits move towards the end of the buffer. With
+/// [`BitOrder::MsbFirst`], the buffer is treated as a big endian integer, so
+/// bits move towards the start of the buffer.
+///
+/// Bits shifted out are lost, and the vacated bits are set to 0.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{shift_bits_left, BitOrder};
+///
+/// let mut buf = [0x81, 0x42];
+/// shift_bits_left(&mut buf, 4, BitOrder::LsbFirst);
+/// assert_eq!(buf, [0x10, 0x28]);
+///
+/// let mut buf = [0x81, 0x42];
+/// shift_bits_left(&mut buf, 4, BitOrder::MsbFirst);
+/// assert_eq!(buf, [0x14, 0x20]);
+/// ```
+pub fn shift_bits_left(buf: &mut [u8], k: usize, order: BitOrder) {
+	match order {
+		BitOrder::LsbFirst => shift_towards_end(buf, k, true),
+		BitOrder::MsbFirst => shift_towards_start(buf, k, false),
+	}
+}
+
+/// Shift an entire buffer `k` bits to the right, as if it were one large
+/// integer.
+///
+/// This is the opposite of [`shift_bits_left`].
+///
+/// # Example
+///
+/// ```
+/// use intbits::{shift_bits_right, BitOrder};
+///
+/// let mut buf = [0x81, 0x42];
+/// shift_bits_right(&mut buf, 4, BitOrder::LsbFirst);
+/// assert_eq!(buf, [0x28, 0x04]);
+///
+/// let mut buf = [0x81, 0x42];
+/// shift_bits_right(&mut buf, 4, BitOrder::MsbFirst);
+/// assert_eq!(buf, [0x08, 0x14]);
+/// ```
+pub fn shift_bits_right(buf: &mut [u8], k: usize, order: BitOrder) {
+	match order {
+		BitOrder::LsbFirst => shift_towards_start(buf, k, true),
+		BitOrder::MsbFirst => shift_towards_end(buf, k, false),
+	}
+}
+
+/// Find the first occurrence of a bit pattern in a buffer, at any bit offset.
+///
+/// The pattern consists of the lowest `pattern_len` bits of `pattern`, which
+/// are compared the same way as [`SliceBits::bits`] would return them. The
+/// bit offset of the first match is returned.
+///
+/// Panics when `pattern_len` is more than 64, or when the other bits of
+/// `pattern` are not 0.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{find_bit_pattern, BitOrder};
+///
+/// let stream = [0x00, 0x3A, 0x58];
+/// assert_eq!(find_bit_pattern(&stream, 0b1110_1001_01, 10, BitOrder::MsbFirst), Some(10));
+/// assert_eq!(find_bit_pattern(&stream, 0b1111, 4, BitOrder::MsbFirst), None);
+/// ```
+pub fn find_bit_pattern(
+	haystack: &[u8],
+	pattern: u64,
+	pattern_len: u32,
+	order: BitOrder,
+) -> Option<u64> {
+	check!(pattern_len <= 64, bit_range_too_wide);
+	if pattern.checked_shr(pattern_len).unwrap_or(0) != 0 {
+		crate::panic::bits_outside_range();
+	}
+	if pattern_len == 0 {
+		return Some(0);
+	}
+	let mask = !0u64 >> (64 - pattern_len);
+	let mut window = 0u64;
+	let mut n = 0u64;
+	for &byte in haystack {
+		for j in 0..8 {
+			n += 1;
+			window = match order {
+				BitOrder::LsbFirst => window >> 1 | ((byte >> j & 1) as u64) << (pattern_len - 1),
+				BitOrder::MsbFirst => (window << 1 | (byte >> (7 - j) & 1) as u64) & mask,
+			};
+			if n >= pattern_len as u64 && window == pattern {
+				return Some(n - pattern_len as u64);
+			}
+		}
+	}
+	None
+}
+
+/// Iterate over the runs of equal bits in a buffer.
+///
+/// Yields the value and length of every run of equal bits, in the given
+/// [`BitOrder`], across byte boundaries. The lengths add up to the number of
+/// bits in the buffer.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{iter_runs, BitOrder};
+///
+/// let line = [0xFF, 0xF0, 0x00, 0x01];
+/// let runs: Vec<_> = iter_runs(&line, BitOrder::MsbFirst).collect();
+/// assert_eq!(runs, [(true, 12), (false, 19), (true, 1)]);
+/// ```
+pub fn iter_runs(buf: &[u8], order: BitOrder) -> Runs<'_> {
+	Runs { buf, pos: 0, order }
+}
+
+/// An iterator over the runs of equal bits in a buffer.
+///
+/// Created by [`iter_runs`].
+#[derive(Clone, Debug)]
+pub struct Runs<'a> {
+	buf: &'a [u8],
+	pos: usize,
+	order: BitOrder,
+}
+
+impl Iterator for Runs<'_> {
+	type Item = (bool, usize);
+
+	#[inline]
+	fn next(&mut self) -> Option<(bool, usize)> {
+		let start = self.pos;
+		let mut bit = None;
+		while let Some(&byte) = self.buf.get(self.pos / 8) {
+			// Put the next bit in the lowest bit, and invert the byte such
+			// that the run consists of zeros.
+			let byte = match self.order {
+				BitOrder::LsbFirst => byte,
+				BitOrder::MsbFirst => byte.reverse_bits(),
+			} >> (self.pos % 8);
+			let bit = *bit.get_or_insert(byte & 1 != 0);
+			let byte = if bit { !byte } else { byte };
+			let left = 8 - self.pos % 8;
+			let n = (byte.trailing_zeros() as usize).min(left);
+			self.pos += n;
+			if n < left {
+				break;
+			}
+		}
+		bit.map(|bit| (bit, self.pos - start))
+	}
+}
+
+impl core::iter::FusedIterator for Runs<'_> {}
+
+/// Reverse the order of the bits within each byte of a buffer.
+///
+/// This converts between [`BitOrder::LsbFirst`] and [`BitOrder::MsbFirst`]
+/// data. The buffer is processed eight bytes at a time.
+///
+/// # Example
+///
+/// ```
+/// use intbits::reverse_bit_order;
+///
+/// let mut buf = [0x01, 0x80, 0x0F, 0x35];
+/// reverse_bit_order(&mut buf);
+/// assert_eq!(buf, [0x80, 0x01, 0xF0, 0xAC]);
+/// ```
+pub fn reverse_bit_order(buf: &mut [u8]) {
+	let mut chunks = buf.chunks_exact_mut(8);
+	for chunk in &mut chunks {
+		let word = u64::from_ne_bytes(chunk.try_into().unwrap());
+		chunk.copy_from_slice(&word.reverse_bits().swap_bytes().to_ne_bytes());
+	}
+	for byte in chunks.into_remainder() {
+		*byte = byte.reverse_bits();
+	}
+}
+
+/// Copy a buffer while reversing the order of the bits within each byte.
+///
+/// See [`reverse_bit_order`].
+///
+/// Panics when the buffers are not of the same length.
+///
+/// # Example
+///
+/// ```
+/// use intbits::reverse_bit_order_copy;
+///
+/// let mut dst = [0; 2];
+/// reverse_bit_order_copy(&[0x01, 0x35], &mut dst);
+/// assert_eq!(dst, [0x80, 0xAC]);
+/// ```
+pub fn reverse_bit_order_copy(src: &[u8], dst: &mut [u8]) {
+	check!(src.len() == dst.len(), buffer_length_mismatch);
+	dst.copy_from_slice(src);
+	reverse_bit_order(dst);
+}
+
+/// Pack a sequence of `width` bit integers tightly into a byte buffer.
+///
+/// The values are written one after the other, like with
+/// [`BitWriter::write_bits`][crate::BitWriter::write_bits]. The unused bits
+/// of the last byte are set to 0. Returns the number of bytes used.
+///
+/// Panics if `width` is more than 32, if a value does not fit in `width`
+/// bits, or if `out` is too small.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{pack_bits, unpack_bits, BitOrder};
+///
+/// let mut buf = [0; 2];
+/// assert_eq!(pack_bits(&[1, 2, 3, 4, 5], 3, &mut buf, BitOrder::LsbFirst), 2);
+/// assert_eq!(buf, [0b11_010_001, 0b0_101_100_0]);
+///
+/// let mut values = [0; 5];
+/// unpack_bits(&buf, 3, &mut values, BitOrder::LsbFirst);
+/// assert_eq!(values, [1, 2, 3, 4, 5]);
+/// ```
+pub fn pack_bits(values: &[u32], width: u32, out: &mut [u8], order: BitOrder) -> usize {
+	check!(width <= 32, bit_range_too_wide);
+	let n_bytes = (values.len() * width as usize).div_ceil(8);
+	check!(n_bytes <= out.len(), buffer_too_small);
+	let mut w = crate::BitWriter::new(&mut out[..n_bytes], order);
+	for &v in values {
+		w.write_bits(v as u64, width).unwrap();
+	}
+	w.finish();
+	n_bytes
+}
+
+/// Unpack a sequence of `width` bit integers from a byte buffer, filling all
+/// of `out`.
+///
+/// This is the counterpart of [`pack_bits`].
+///
+/// Panics if `width` is more than 32, or if `input` is too small.
+pub fn unpack_bits(input: &[u8], width: u32, out: &mut [u32], order: BitOrder) {
+	check!(width <= 32, bit_range_too_wide);
+	check!(
+		out.len() * width as usize <= input.len() * 8,
+		buffer_too_small
+	);
+	let mut r = crate::BitReader::new(input, order);
+	for v in out {
+		*v = r.read_bits(width).unwrap() as u32;
+	}
+}
+
+/// Read a big endian field, numbered the way RFC header diagrams number them.
+///
+/// The field starts at bit `bit` of byte `byte`, where bit 0 is the most
+/// significant bit, and is `width` bits wide. The offset may be larger than 7,
+/// so fields can be given relative to the start of a 32-bit word.
+///
+/// Panics if `width` is more than 64, or if the field is out of range.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{read_be_field, write_be_field};
+///
+/// // An IPv4 header: version 4, IHL 5, ..., flags 0b010, fragment offset 0.
+/// let mut header = [0x45, 0x00, 0x00, 0x54, 0x12, 0x34, 0x40, 0x00];
+/// assert_eq!(read_be_field(&header, 0, 0, 4), 4);
+/// assert_eq!(read_be_field(&header, 0, 4, 4), 5);
+/// assert_eq!(read_be_field(&header, 2, 0, 16), 84);
+/// assert_eq!(read_be_field(&header, 4, 16, 3), 0b010);
+///
+/// write_be_field(&mut header, 4, 19, 13, 0x1ABC);
+/// assert_eq!(header[6..8], [0x5A, 0xBC]);
+/// ```
+pub fn read_be_field(buf: &[u8], byte: usize, bit: u32, width: u32) -> u64 {
+	buf.bits(be_field_range(byte, bit, width), BitOrder::MsbFirst) as u64
+}
+
+/// Write a big endian field, numbered the way RFC header diagrams number them.
+///
+/// See [`read_be_field`].
+///
+/// Panics if `width` is more than 64, if the field is out of range, or if
+/// `value` does not fit in `width` bits.
+pub fn write_be_field(buf: &mut [u8], byte: usize, bit: u32, width: u32, value: u64) {
+	let range = be_field_range(byte, bit, width);
+	buf.set_bits(range, value as u128, BitOrder::MsbFirst);
+}
+
+/// The bits of a field given as for [`read_be_field`].
+///
+/// Panics if `width` is more than 64, or if the end of the field doesn't fit
+/// in a `usize`.
+fn be_field_range(byte: usize, bit: u32, width: u32) -> Range<usize> {
+	check!(width <= 64, bit_range_too_wide);
+	let start = byte
+		.checked_mul(8)
+		.and_then(|b| b.checked_add(bit as usize));
+	match start.and_then(|start| Some(start..start.checked_add(width as usize)?)) {
+		Some(range) => range,
+		None => crate::panic::invalid_bit_range(),
+	}
+}
@@ -0,0 +1,153 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// Get the bytes that contain a range of bits.
+///
+/// Bit `i` is in byte `i / 8`, whatever the [`BitOrder`][crate::BitOrder].
+/// Returns the range of bytes, the number of bits in the first byte before
+/// the range (`head_bits`), and the number of bits in the last byte after the
+/// range (`tail_bits`). Both are 0 if the range starts and ends on byte
+/// boundaries. An empty range of bits gives an empty range of bytes.
+///
+/// Panics if the range is inverted.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{bit_range_to_bytes, bytes_to_bit_range};
+///
+/// assert_eq!(bit_range_to_bytes(12..40), (1..5, 4, 0));
+/// assert_eq!(bit_range_to_bytes(16..19), (2..3, 0, 5));
+/// assert_eq!(bytes_to_bit_range(1..5, 4, 0), 12..40);
+/// ```
+pub fn bit_range_to_bytes(bits: Range<usize>) -> (Range<usize>, u32, u32) {
+	if bits.start >= bits.end {
+		return (bits.start / 8..bits.start / 8, 0, 0);
+	}
+	let end = bits.end.div_ceil(8);
+	let head_bits = (bits.start % 8) as u32;
+	let tail_bits = (end * 8 - bits.end) as u32;
+	(bits.start / 8..end, head_bits, tail_bits)
+}
+
+/// Get the range of bits in a range of bytes, leaving out `head_bits` at the
+/// start and `tail_bits` at the end.
+///
+/// This is the inverse of [`bit_range_to_bytes`].
+///
+/// Panics if `head_bits` or `tail_bits` is not below 8, or if they don't fit
+/// in the range of bytes.
+///
+/// # Example
+///
+/// ```
+/// use intbits::bytes_to_bit_range;
+///
+/// assert_eq!(bytes_to_bit_range(2..4, 0, 0), 16..32);
+/// assert_eq!(bytes_to_bit_range(2..4, 3, 1), 19..31);
+/// ```
+pub fn bytes_to_bit_range(bytes: Range<usize>, head_bits: u32, tail_bits: u32) -> Range<usize> {
+	check!(head_bits < 8 && tail_bits < 8, invalid_bit_range);
+	check!(bytes.start <= bytes.end, invalid_bit_range);
+	let start = bytes.start * 8 + head_bits as usize;
+	let end = bytes.end * 8 - tail_bits as usize;
+	check!(start <= end, invalid_bit_range);
+	start..end
+}
+
+/// Get the range of bytes that exactly covers a range of bits.
+///
+/// Returns `None` if the range doesn't start and end on byte boundaries. An
+/// inverted range is treated as the empty range at its start.
+///
+/// # Example
+///
+/// ```
+/// use intbits::aligned_byte_range;
+///
+/// assert_eq!(aligned_byte_range(16..48), Some(2..6));
+/// assert_eq!(aligned_byte_range(16..47), None);
+/// ```
+pub fn aligned_byte_range(bits: Range<usize>) -> Option<Range<usize>> {
+	let bits = bits.start..bits.end.max(bits.start);
+	if bits.start.is_multiple_of(8) && bits.end.is_multiple_of(8) {
+		Some(bits.start / 8..bits.end / 8)
+	} else {
+		None
+	}
+}
+
+/// A part of a range of bits, as given by [`AlignedSegments`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Segment {
+	/// Some of the bits of one unit: the index of the unit, and the range of
+	/// bits within that unit.
+	Partial(usize, Range<u32>),
+	/// A run of whole units, by index.
+	Full(Range<usize>),
+}
+
+/// An iterator splitting a range of bits into aligned segments.
+///
+/// Created by [`aligned_segments`].
+#[derive(Clone, Debug)]
+pub struct AlignedSegments {
+	bits: Range<usize>,
+	unit_bits: u32,
+}
+
+/// Split a range of bits into parts of units of `unit_bits` bits, such as bytes
+/// or words.
+///
+/// This gives a [`Segment::Partial`] for a leading partial unit, a
+/// [`Segment::Full`] for all whole units, and a [`Segment::Partial`] for a
+/// trailing partial unit. Parts that are not needed are left out, and an empty
+/// or inverted range gives nothing. Bit `i` is in unit `i / unit_bits`.
+///
+/// Panics if `unit_bits` is 0.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{aligned_segments, Segment};
+///
+/// let mut s = aligned_segments(5..30, 8);
+/// assert_eq!(s.next(), Some(Segment::Partial(0, 5..8)));
+/// assert_eq!(s.next(), Some(Segment::Full(1..3)));
+/// assert_eq!(s.next(), Some(Segment::Partial(3, 0..6)));
+/// assert_eq!(s.next(), None);
+///
+/// let mut s = aligned_segments(33..35, 32);
+/// assert_eq!(s.next(), Some(Segment::Partial(1, 1..3)));
+/// assert_eq!(s.next(), None);
+/// ```
+pub fn aligned_segments(bits: Range<usize>, unit_bits: u32) -> AlignedSegments {
+	check!(unit_bits > 0, invalid_bit_width);
+	let bits = bits.start..bits.end.max(bits.start);
+	AlignedSegments { bits, unit_bits }
+}
+
+impl Iterator for AlignedSegments {
+	type Item = Segment;
+
+	#[inline]
+	fn next(&mut self) -> Option<Segment> {
+		let Range { start, end } = self.bits;
+		if start == end {
+			return None;
+		}
+		let unit_bits = self.unit_bits as usize;
+		let (unit, offset) = (start / unit_bits, start % unit_bits);
+		if offset != 0 || end - start < unit_bits {
+			let len = (unit_bits - offset).min(end - start);
+			self.bits.start += len;
+			Some(Segment::Partial(unit, offset as u32..(offset + len) as u32))
+		} else {
+			let n = (end - start) / unit_bits;
+			self.bits.start += n * unit_bits;
+			Some(Segment::Full(unit..unit + n))
+		}
+	}
+}
+
+impl FusedIterator for AlignedSegments {}
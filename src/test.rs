@@ -1,7 +1,12 @@
 use super::Bits;
 use core::ops::Bound;
 
+extern crate std;
+use std::format;
+
 #[test]
+#[allow(clippy::reversed_empty_ranges, clippy::bool_assert_comparison)]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
 fn test_get() {
 	assert_eq!(2u32.bit(0), false);
 	assert_eq!(2u32.bit(1), true);
@@ -41,6 +46,7 @@ fn test_get() {
 }
 
 #[test]
+#[cfg(feature = "impl-128")]
 fn test_set() {
 	assert_eq!(0xFFu8.with_bits(4..8, 2), 0x2F);
 	assert_eq!(0xFFu8.with_bits(4.., 2), 0x2F);
@@ -61,6 +67,44 @@ fn test_set() {
 	);
 }
 
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_move_bits() {
+	let mut v = 0xF0A5u16;
+	v.move_bits(0..8, 4);
+	assert_eq!(v, 0xFA50);
+	v.move_bits(4..12, 0);
+	assert_eq!(v, 0xF0A5);
+	v.move_bits(..4, 12);
+	assert_eq!(v, 0x50A0);
+	v.move_bits(4..=7, 4);
+	assert_eq!(v, 0x50A0);
+	v.move_bits(8..8, 16);
+	assert_eq!(v, 0x50A0);
+	let mut v = -1i32;
+	v.move_bits(16.., 0);
+	assert_eq!(v, 0xFFFF);
+	let mut v = crate::u12::new(0xABC);
+	v.move_bits(4.., 0);
+	assert_eq!(v.get(), 0x0AB);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_move_bits_out_of_range() {
+	0u8.move_bits(0..4, 5);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_with_bits_from() {
+	assert_eq!(0x1234u16.with_bits_from(0x0A50, 4..8), 0x1254);
+	assert_eq!(0x00u8.with_bits_from(0xFF, 2..=5), 0x3C);
+	assert_eq!(0xFFu8.with_bits_from(0x00, ..4), 0xF0);
+	assert_eq!(0xABu8.with_bits_from(0xCD, 8..), 0xAB);
+	assert_eq!(0x12i32.with_bits_from(-1, 28..), 0xF000_0012u32 as i32);
+}
+
 #[test]
 #[should_panic(expected = "invalid bit index")]
 fn test_get_panic_1() {
@@ -93,6 +137,7 @@ fn test_get_panic_5() {
 
 #[test]
 #[should_panic(expected = "invalid bit range")]
+#[cfg(feature = "impl-128")]
 fn test_get_panic_6() {
 	123u32.bits(0x10000000000000000000000000000000u128..);
 }
@@ -105,6 +150,7 @@ fn test_get_panic_7() {
 
 #[test]
 #[should_panic(expected = "invalid bit range")]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
 fn test_get_panic_8() {
 	123u32.bits((
 		Bound::Included(-0x10000000000000000000000000000000i128),
@@ -114,6 +160,7 @@ fn test_get_panic_8() {
 
 #[test]
 #[should_panic(expected = "invalid bit range")]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
 fn test_get_panic_9() {
 	123u128.bits(-128i8..);
 }
@@ -135,3 +182,2897 @@ fn test_get_panic_11() {
 fn test_set_panic() {
 	123u32.with_bits(4..8, 0x10);
 }
+
+#[test]
+fn test_derive() {
+	use crate::{FromBits, IntoBits};
+	#[cfg(not(feature = "derive"))]
+	use intbits_derive::{FromBits, IntoBits};
+
+	#[derive(FromBits, IntoBits, Debug, PartialEq)]
+	#[bits(u32)]
+	struct Reg {
+		#[bits(0..=3)]
+		a: u8,
+		#[bits(4)]
+		b: bool,
+		#[bits(8..24)]
+		c: u16,
+		#[bits(24..)]
+		d: u8,
+	}
+
+	let reg = Reg {
+		a: 0xA,
+		b: true,
+		c: 0x1234,
+		d: 0xFF,
+	};
+	assert_eq!(Reg::from_bits(0xFF12341A), reg);
+	assert_eq!(reg.into_bits(), 0xFF12341A);
+	assert_eq!(Reg::from_bits(0).into_bits(), 0);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+#[should_panic(expected = "bits outside range")]
+fn test_derive_panic() {
+	use crate::IntoBits;
+	#[cfg(not(feature = "derive"))]
+	use intbits_derive::IntoBits;
+
+	#[derive(IntoBits)]
+	#[bits(u16)]
+	struct Reg {
+		#[bits(0..4)]
+		a: u8,
+		#[bits(4..12)]
+		b: i8,
+	}
+
+	Reg { a: 0, b: -1 }.into_bits();
+}
+
+#[test]
+fn test_derive_bits_index() {
+	#[cfg(feature = "derive")]
+	use crate::BitsIndex;
+	#[cfg(not(feature = "derive"))]
+	use intbits_derive::BitsIndex;
+
+	#[derive(BitsIndex)]
+	enum Flag {
+		Carry = 0,
+		Zero = 1,
+		Overflow = 6,
+		Negative = 7,
+	}
+
+	let mut status = 0u8;
+	status.set_bit(Flag::Carry, true);
+	status.set_bit(Flag::Negative, true);
+	assert_eq!(status, 0x81);
+	assert!(status.bit(Flag::Carry));
+	assert!(!status.bit(Flag::Zero));
+	status.set_bits(Flag::Zero..Flag::Overflow, 0b1010);
+	assert_eq!(status, 0x95);
+	assert_eq!(status.bits(Flag::Overflow..), 0b10);
+	assert_eq!(0xFFu16.bits(..=Flag::Negative), 0xFF);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_bitfield() {
+	use crate::BitField;
+
+	type A = BitField<u8, 0, 8>;
+	type B = BitField<i32, 28, 4>;
+	type C = BitField<u128, 127, 1>;
+	type D = BitField<u16, 16, 0>;
+
+	assert_eq!(A::MASK, 0xFF);
+	assert_eq!(A::get(0x5A), 0x5A);
+	assert_eq!(B::MASK, 0xF000_0000);
+	assert_eq!(B::get(-1), 0xF);
+	assert_eq!(B::with(0, 0x8), i32::MIN);
+	assert_eq!(C::MASK, 1 << 127);
+	assert_eq!(C::with(!0, 0), !0 >> 1);
+	assert_eq!(D::MASK, 0);
+	assert_eq!(D::get(0xFFFF), 0);
+	assert_eq!(D::with(0x1234, 0), 0x1234);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_bitfield_panic() {
+	crate::BitField::<u32, 4, 4>::with(0, 0x10);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_schema() {
+	use crate::{BitSchema, SchemaError, SchemaField};
+
+	let fields = [
+		SchemaField::new("A", 0..4),
+		SchemaField::new("B", 4..5),
+		SchemaField::new("C", 8..32),
+	];
+	let schema = BitSchema::<i32>::new(&fields).unwrap();
+	assert_eq!(schema.get(-1, "C"), Some(0xFFFFFF));
+	assert_eq!(schema.field("B"), Some(&fields[1]));
+	assert_eq!(fields[2].name(), "C");
+	let mut v = 0;
+	schema.set(&mut v, "B", 1).unwrap();
+	assert_eq!(v, 0x10);
+	assert_eq!(schema.set(&mut v, "D", 1), Err(SchemaError::UnknownField));
+	assert!(schema.values(0x1F).eq([("A", 0xF), ("B", 1), ("C", 0)]));
+
+	let bad = [SchemaField::new("A", 0..4), SchemaField::new("B", 3..8)];
+	assert_eq!(
+		BitSchema::<u8>::new(&bad).unwrap_err(),
+		SchemaError::Overlap {
+			first: 0,
+			second: 1
+		}
+	);
+	let bad = [SchemaField::new("A", 0..4), SchemaField::new("A", 4..8)];
+	assert_eq!(
+		BitSchema::<u8>::new(&bad).unwrap_err(),
+		SchemaError::DuplicateName {
+			first: 0,
+			second: 1
+		}
+	);
+	let bad = [SchemaField::new("A", 4..9)];
+	assert_eq!(
+		BitSchema::<u8>::new(&bad).unwrap_err(),
+		SchemaError::OutOfRange { field: 0 }
+	);
+}
+
+#[test]
+fn test_reserved_zero() {
+	use crate::ReservedBitsSet;
+
+	assert_eq!(0x30FFu16.assert_reserved_zero(&[8..12, 14..16]), Ok(()));
+	assert_eq!(
+		0x31FFu16.assert_reserved_zero(&[8..12, 14..16]),
+		Err(ReservedBitsSet { start: 8, end: 12 })
+	);
+	assert_eq!(
+		0x8000u16.assert_reserved_zero(&[8..12, 14..16]),
+		Err(ReservedBitsSet { start: 14, end: 16 })
+	);
+	assert_eq!(0xFFFFu16.assert_reserved_zero(&[]), Ok(()));
+	assert_eq!(0xFFFFu16.assert_reserved_zero(&[4..4, 16..16]), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_reserved_zero_panic() {
+	let _ = 0u16.assert_reserved_zero(&[0..4, 12..17]);
+}
+
+#[test]
+fn test_schema_reserved_zero() {
+	use crate::{BitSchema, ReservedBitsSet, SchemaField};
+
+	let fields = [SchemaField::new("A", 0..4), SchemaField::new("B", 8..12)];
+	let schema = BitSchema::<u16>::new(&fields).unwrap();
+	assert_eq!(schema.check_reserved_zero(0x0F0F), Ok(0x0F0F));
+	assert_eq!(
+		schema.check_reserved_zero(0x0F1F),
+		Err(ReservedBitsSet { start: 4, end: 8 })
+	);
+	assert_eq!(
+		schema.check_reserved_zero(0x8F0F),
+		Err(ReservedBitsSet { start: 12, end: 16 })
+	);
+
+	let full = [SchemaField::new("A", 0..16)];
+	let schema = BitSchema::<u16>::new(&full).unwrap();
+	assert_eq!(schema.check_reserved_zero(0xFFFF), Ok(0xFFFF));
+
+	let schema = BitSchema::<u16>::new(&[]).unwrap();
+	assert_eq!(schema.check_reserved_zero(0), Ok(0));
+	assert_eq!(
+		schema.check_reserved_zero(0x0100),
+		Err(ReservedBitsSet { start: 0, end: 16 })
+	);
+}
+
+#[test]
+fn test_schema_overlay() {
+	use crate::{BitSchema, OverlayError, SchemaError, SchemaField, SchemaOverlay};
+
+	let read = [SchemaField::new("ADDR", 0..12)];
+	let write = [
+		SchemaField::new("ADDR", 0..8),
+		SchemaField::new("DATA", 8..14),
+	];
+	let variants = [
+		(0b01, BitSchema::<u16>::new(&read).unwrap()),
+		(0b10, BitSchema::<u16>::new(&write).unwrap()),
+	];
+	let overlay = SchemaOverlay::new(14..16, &variants).unwrap();
+	assert_eq!(overlay.tag(0x8ABC), 0b10);
+	assert_eq!(overlay.get(0x4ABC, "ADDR"), Some(0xABC));
+	assert_eq!(overlay.get(0x8ABC, "ADDR"), Some(0xBC));
+	assert_eq!(overlay.get(0x8ABC, "DATA"), Some(0x0A));
+	assert_eq!(overlay.get(0x4ABC, "DATA"), None);
+	assert_eq!(overlay.get(0x0ABC, "ADDR"), None);
+	assert!(overlay.display(0xCABC).is_none());
+
+	let mut v = 0x8000;
+	overlay.set(&mut v, "DATA", 0x3F).unwrap();
+	assert_eq!(v, 0xBF00);
+	assert_eq!(
+		overlay.set(&mut v, "FOO", 1),
+		Err(SchemaError::UnknownField)
+	);
+	let mut v = 0;
+	assert_eq!(
+		overlay.set(&mut v, "ADDR", 1),
+		Err(SchemaError::UnknownField)
+	);
+
+	assert_eq!(
+		SchemaOverlay::new(14..17, &variants).unwrap_err(),
+		OverlayError::TagOutOfRange
+	);
+	assert_eq!(
+		SchemaOverlay::new(15..16, &variants).unwrap_err(),
+		OverlayError::TagTooWide { variant: 1 }
+	);
+	let same = [variants[0], variants[1], variants[0]];
+	assert_eq!(
+		SchemaOverlay::new(14..16, &same).unwrap_err(),
+		OverlayError::DuplicateTag {
+			first: 0,
+			second: 2
+		}
+	);
+}
+
+#[test]
+fn test_schema_display() {
+	use crate::{BitSchema, SchemaField};
+
+	let fields = [
+		SchemaField::new("A", 0..1),
+		SchemaField::new("B", 1..3),
+		SchemaField::new("C", 4..13),
+	];
+	let schema = BitSchema::<u16>::new(&fields).unwrap();
+	assert_eq!(
+		format!("{:?}", schema.display(0x0024)),
+		"{ A: 0, B: 0b10, C: 0x002 }"
+	);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_builder() {
+	use crate::BuildError;
+
+	assert_eq!(u8::bits_builder().build(), Ok(0));
+	assert_eq!(
+		i16::bits_builder()
+			.field(12.., 0xF)
+			.field(0..=3, 1)
+			.bit(4, false)
+			.build(),
+		Ok(-0x0FFF)
+	);
+	assert_eq!(
+		u32::bits_builder().field(0..8, 0x100).build(),
+		Err(BuildError::ValueTooWide)
+	);
+	assert_eq!(
+		u32::bits_builder().bit(3, false).bit(3, false).build(),
+		Err(BuildError::Overlap)
+	);
+	assert_eq!(
+		u32::bits_builder().field(4..4, 0).field(0..8, 1).build(),
+		Ok(1)
+	);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_bits_as() {
+	use crate::InvalidValue;
+	use core::convert::TryFrom;
+
+	#[derive(Debug, PartialEq)]
+	enum Parity {
+		None = 0,
+		Even = 2,
+		Odd = 3,
+	}
+
+	impl TryFrom<u32> for Parity {
+		type Error = ();
+		fn try_from(v: u32) -> Result<Self, ()> {
+			match v {
+				0 => Ok(Parity::None),
+				2 => Ok(Parity::Even),
+				3 => Ok(Parity::Odd),
+				_ => Err(()),
+			}
+		}
+	}
+
+	impl From<Parity> for u32 {
+		fn from(p: Parity) -> u32 {
+			p as u32
+		}
+	}
+
+	assert_eq!(0xC0i32.bits_as(6..8), Ok(Parity::Odd));
+	assert_eq!(0x40i32.bits_as::<Parity, _, _>(6..8), Err(InvalidValue(1)));
+	let mut v = -1i32;
+	v.set_bits_from(30.., Parity::None);
+	assert_eq!(v, 0x3FFF_FFFF);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_nonzero() {
+	use core::num::{NonZeroI8, NonZeroU32};
+
+	let a = NonZeroU32::new(0x1234).unwrap();
+	assert!(a.bit(2));
+	assert_eq!(a.bits(4..12), 0x23);
+	assert_eq!(a.with_bits(0..8, 0).get(), 0x1200);
+	assert_eq!(NonZeroI8::new(-1).unwrap().bits(4..), 0xF);
+	assert_eq!(NonZeroI8::new(1).unwrap().with_bit(7, true).get(), -127);
+}
+
+#[test]
+#[should_panic(expected = "result is zero")]
+fn test_nonzero_panic() {
+	core::num::NonZeroU8::new(0x10).unwrap().with_bit(4, false);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_wrapping() {
+	use core::num::{Saturating, Wrapping};
+
+	let mut a = Wrapping(0xF0u8);
+	a.set_bits(0..4, 0xA);
+	assert_eq!(a, Wrapping(0xFA));
+	assert_eq!((a + Wrapping(0x10)).bits(4..), 0);
+	let b = Saturating(-1i16);
+	assert_eq!(b.with_bit(15, false), Saturating(i16::MAX));
+	assert_eq!(b.bits(8..), 0xFF);
+	assert_eq!(<Wrapping<u64> as Bits>::N_BITS, 64);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_cell() {
+	use crate::CellBits;
+	use core::cell::Cell;
+
+	let c = Cell::new(-1i64);
+	c.set_bits(32.., 0);
+	assert_eq!(c.get(), 0xFFFF_FFFF);
+	c.toggle_bit(63);
+	c.toggle_bit(0);
+	assert_eq!(c.get(), i64::MIN + 0xFFFF_FFFE);
+	assert!(c.bit(63));
+	assert_eq!(c.bits(..4), 0xE);
+}
+
+#[test]
+fn test_ref_bits() {
+	use crate::{BitsIndex, RefBits};
+
+	fn high_byte<T: RefBits>(x: T) -> <T::Value as Bits>::Bits
+	where
+		u32: BitsIndex<T::Value>,
+	{
+		let n = <T::Value as Bits>::N_BITS;
+		x.bits(n - 8..)
+	}
+
+	let words = [0x1234u16, 0xABCD];
+	assert_eq!(
+		words.iter().map(high_byte).collect::<std::vec::Vec<_>>(),
+		[0x12, 0xAB]
+	);
+	assert_eq!(high_byte(&u32::MAX), 0xFF);
+	let r = &words[1];
+	assert!(r.bit(0));
+	assert_eq!(r.bits(4..8), 0xC);
+	// The methods of `Bits` are still used for values.
+	assert_eq!(words[1].bits(4..8), 0xC);
+}
+
+#[test]
+fn test_array() {
+	use crate::ArrayBits;
+
+	let a = [0x12u8, 0x34, 0x56];
+	assert_eq!(<[u8; 3]>::N_BITS, 24);
+	assert_eq!(a.bits(..), 0x563412);
+	assert_eq!(a.bits(4..20), 0x6341);
+	assert_eq!(a.bits(8..8), 0);
+	assert!(a.bit(4));
+	assert_eq!(a.with_bits(4..=19, 0xABCD), [0xD2, 0xBC, 0x5A]);
+	assert_eq!(a.with_bit(23, true), [0x12, 0x34, 0xD6]);
+
+	let b = [!0u64; 3];
+	assert_eq!(b.bits(32..160), !0);
+	assert_eq!(b.with_bits(32..160, 0), [0xFFFF_FFFF, 0, 0xFFFF_FFFF << 32]);
+	assert_eq!([0u128; 2].with_bits(100..200, 1 << 99)[1], 1 << 71);
+}
+
+#[test]
+#[should_panic(expected = "bit range too wide")]
+fn test_array_panic() {
+	use crate::ArrayBits;
+	[0u64; 3].bits(0..129);
+}
+
+#[test]
+fn test_slice() {
+	use crate::{BitOrder::*, SliceBits};
+
+	let buf: &[u8] = &[0x12, 0x34, 0x56, 0x78];
+	assert_eq!(buf.bits(.., LsbFirst), 0x78563412);
+	assert_eq!(buf.bits(.., MsbFirst), 0x12345678);
+	assert_eq!(buf.bits(3..29, LsbFirst), 0x78563412 >> 3 & 0x3FF_FFFF);
+	assert_eq!(buf.bits(3..29, MsbFirst), 0x12345678 >> 3 & 0x3FF_FFFF);
+	assert_eq!(buf.bits(9..9, MsbFirst), 0);
+	assert!(buf.bit(1, LsbFirst));
+	assert!(buf.bit(3, MsbFirst));
+
+	let mut buf = [0u8; 20];
+	buf.set_bits(3..131, !0 >> 1, LsbFirst);
+	assert_eq!(buf.bits(3..131, LsbFirst), !0 >> 1);
+	assert_eq!(buf.bits(2..4, LsbFirst), 0b10);
+	assert_eq!(buf[0], 0xF8);
+	assert_eq!(buf[16], 0x03);
+	let mut buf = [0u8; 4];
+	buf.set_bits(3..29, 0x12345678 >> 3 & 0x3FF_FFFF, MsbFirst);
+	buf.set_bit(31, true, MsbFirst);
+	assert_eq!(buf, [0x12, 0x34, 0x56, 0x79]);
+}
+
+#[test]
+fn test_copy_bits_slice() {
+	use crate::{copy_bits_slice, BitOrder::*, SliceBits};
+
+	let src: [u8; 32] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+	for &order in &[LsbFirst, MsbFirst] {
+		for &(s, d, n) in &[(0, 0, 256), (3, 11, 200), (5, 13, 0), (7, 1, 9), (1, 2, 3)] {
+			let mut dst = [0x5Au8; 32];
+			let before = dst;
+			copy_bits_slice(&src, s, &mut dst, d, n, order);
+			for i in 0..256 {
+				let expected = if i >= d && i < d + n {
+					src.bit(i - d + s, order)
+				} else {
+					before.bit(i, order)
+				};
+				assert_eq!(dst.bit(i, order), expected);
+			}
+		}
+	}
+}
+
+#[test]
+fn test_shift_bits() {
+	use crate::{shift_bits_left, shift_bits_right, BitOrder::*};
+
+	let v = 0x0123_4567_89AB_CDEFu64;
+	for k in 0..70 {
+		let mut buf = v.to_le_bytes();
+		shift_bits_left(&mut buf, k, LsbFirst);
+		assert_eq!(
+			u64::from_le_bytes(buf),
+			v.checked_shl(k as u32).unwrap_or(0)
+		);
+		let mut buf = v.to_le_bytes();
+		shift_bits_right(&mut buf, k, LsbFirst);
+		assert_eq!(
+			u64::from_le_bytes(buf),
+			v.checked_shr(k as u32).unwrap_or(0)
+		);
+		let mut buf = v.to_be_bytes();
+		shift_bits_left(&mut buf, k, MsbFirst);
+		assert_eq!(
+			u64::from_be_bytes(buf),
+			v.checked_shl(k as u32).unwrap_or(0)
+		);
+		let mut buf = v.to_be_bytes();
+		shift_bits_right(&mut buf, k, MsbFirst);
+		assert_eq!(
+			u64::from_be_bytes(buf),
+			v.checked_shr(k as u32).unwrap_or(0)
+		);
+	}
+}
+
+#[test]
+fn test_find_bit_pattern() {
+	use crate::{find_bit_pattern, BitOrder::*, SliceBits};
+
+	let buf: [u8; 16] = core::array::from_fn(|i| (i * 73 + 5) as u8);
+	for &order in &[LsbFirst, MsbFirst] {
+		for &(offset, len) in &[(0, 1), (13, 7), (50, 64), (64, 64), (120, 8), (3, 33)] {
+			let pattern = buf.bits(offset..offset + len, order) as u64;
+			let found = find_bit_pattern(&buf, pattern, len as u32, order).unwrap() as usize;
+			assert!(found <= offset);
+			assert_eq!(buf.bits(found..found + len, order) as u64, pattern);
+		}
+	}
+	assert_eq!(find_bit_pattern(&[0xFF; 4], 0, 1, LsbFirst), None);
+	assert_eq!(find_bit_pattern(&[0x80], 1, 1, LsbFirst), Some(7));
+	assert_eq!(find_bit_pattern(&[], 0, 0, MsbFirst), Some(0));
+}
+
+#[test]
+fn test_reverse_bit_order() {
+	use crate::{reverse_bit_order, BitOrder::*, SliceBits};
+
+	let orig: [u8; 21] = core::array::from_fn(|i| (i * 97 + 3) as u8);
+	let mut buf = orig;
+	reverse_bit_order(&mut buf);
+	for i in 0..21 * 8 {
+		assert_eq!(buf.bit(i, LsbFirst), orig.bit(i, MsbFirst));
+	}
+}
+
+#[test]
+#[should_panic(expected = "buffers must be of the same length")]
+fn test_reverse_bit_order_copy_panic() {
+	crate::reverse_bit_order_copy(&[0; 2], &mut [0; 3]);
+}
+
+#[test]
+fn test_bit_reader() {
+	use crate::{BitOrder::*, BitReader, OutOfBits};
+
+	let data = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0, 0x11];
+	let mut r = BitReader::new(&data, LsbFirst);
+	assert_eq!(r.read_bits(4), Ok(0x2));
+	assert_eq!(r.read_bits(64), Ok(0x1F0DEBC9A7856341));
+	assert_eq!(r.read_bits(0), Ok(0));
+	assert_eq!(r.remaining_bits(), 4);
+	assert_eq!(r.read_bits(5), Err(OutOfBits));
+	assert_eq!(r.position(), 68);
+	assert_eq!(r.skip(4), Ok(()));
+	assert_eq!(r.read_bit(), Err(OutOfBits));
+
+	let mut r = BitReader::new(&data, MsbFirst);
+	assert_eq!(r.read_bit(), Ok(false));
+	assert_eq!(r.peek_bits(11), Ok(0x123));
+	r.align_to_byte();
+	assert!(r.is_aligned());
+	assert_eq!(r.read_bits(16), Ok(0x3456));
+	r.align_to_byte();
+	assert_eq!(r.position(), 24);
+}
+
+#[test]
+fn test_bit_writer() {
+	use crate::{BitOrder::*, BitReader, BitWriter, OutOfBits};
+
+	for &order in &[LsbFirst, MsbFirst] {
+		let mut buf = [0xAA; 16];
+		let mut w = BitWriter::new(&mut buf, order);
+		for n in 0..=15 {
+			w.write_bits(((1 << n) - 1) & 0x5555, n).unwrap();
+		}
+		w.write_bit(true).unwrap();
+		assert_eq!(w.position(), 121);
+		assert_eq!(w.write_bits(0, 8), Err(OutOfBits));
+		w.align_to_byte().unwrap();
+		assert_eq!(w.remaining_bits(), 0);
+		assert_eq!(w.finish(), 128);
+
+		let mut r = BitReader::new(&buf, order);
+		for n in 0..=15 {
+			assert_eq!(r.read_bits(n), Ok(((1 << n) - 1) & 0x5555));
+		}
+		assert_eq!(r.read_bit(), Ok(true));
+		assert_eq!(r.read_bits(7), Ok(0));
+	}
+
+	let mut buf = [0xFF; 2];
+	let mut w = BitWriter::new(&mut buf, LsbFirst);
+	w.write_bits(0x3, 3).unwrap();
+	assert_eq!(w.flush(), 3);
+	assert_eq!(buf, [0x03, 0xFF]);
+}
+
+#[test]
+fn test_bit_cursor() {
+	use crate::{BitCursor, BitOrder::*, OutOfBits};
+
+	let mut buf = [0u8; 8];
+	let mut c = BitCursor::new(&mut buf[..], LsbFirst);
+	c.write_bits_at(60, 0xF, 4).unwrap();
+	assert_eq!(c.write_bits_at(61, 0xF, 4), Err(OutOfBits));
+	assert_eq!(c.write_bits_at(usize::MAX, 0, 1), Err(OutOfBits));
+	c.seek_bits(4).unwrap();
+	c.write_bits(0x123, 12).unwrap();
+	assert_eq!(c.position_bits(), 16);
+	assert_eq!(c.seek_bits(65), Err(OutOfBits));
+	c.seek_bits(64).unwrap();
+	assert_eq!(c.remaining_bits(), 0);
+	assert_eq!(c.read_bits(1), Err(OutOfBits));
+	assert_eq!(buf, [0x30, 0x12, 0, 0, 0, 0, 0, 0xF0]);
+
+	let mut c = BitCursor::new(&buf, MsbFirst);
+	assert_eq!(c.read_bits(12), Ok(0x301));
+	assert_eq!(c.read_bits_at(56, 8), Ok(0xF0));
+	assert_eq!(c.position_bits(), 12);
+}
+
+#[test]
+fn test_packed_seq() {
+	use crate::{pack_bits, BitOrder, PackedSeq};
+	use std::vec::Vec;
+
+	fn check<const W: u32>(order: BitOrder) {
+		let symbols: Vec<u32> = (0..37u32)
+			.map(|i| i.wrapping_mul(0x9E37_79B9) >> (32 - W))
+			.collect();
+		let mut expected = [0u8; 160];
+		pack_bits(&symbols, W, &mut expected, order);
+		let mut seq = PackedSeq::<_, W>::with_len([0u8; 160], symbols.len(), order);
+		assert_eq!(seq.len(), 37);
+		for (i, &s) in symbols.iter().enumerate().rev() {
+			seq.set(i, s);
+		}
+		assert_eq!(seq.get_ref(), &expected);
+		assert!((0..37).map(|i| seq.get(i)).eq(symbols.iter().copied()));
+		assert!(seq.iter().eq(symbols.iter().copied()));
+		assert!(seq.iter().rev().eq(symbols.iter().rev().copied()));
+		assert_eq!(seq.iter().nth(36), Some(symbols[36]));
+		assert_eq!(seq.iter().len(), 37);
+	}
+
+	for &order in &[BitOrder::LsbFirst, BitOrder::MsbFirst] {
+		check::<1>(order);
+		check::<2>(order);
+		check::<3>(order);
+		check::<4>(order);
+		check::<8>(order);
+		check::<12>(order);
+		check::<32>(order);
+	}
+
+	let seq = PackedSeq::<_, 3>::new(&[0xFF; 2][..], BitOrder::LsbFirst);
+	assert_eq!(seq.len(), 5);
+	assert!(seq.iter().all(|s| s == 7));
+}
+
+#[test]
+#[should_panic(expected = "invalid symbol index")]
+fn test_packed_seq_out_of_range() {
+	let seq = crate::PackedSeq::<_, 2>::with_len([0u8; 2], 5, crate::BitOrder::MsbFirst);
+	seq.get(5);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_packed_seq_symbol_too_wide() {
+	let mut seq = crate::PackedSeq::<_, 2>::new([0u8; 2], crate::BitOrder::MsbFirst);
+	seq.set(0, 4);
+}
+
+#[test]
+fn test_exp_golomb() {
+	use crate::{BitOrder::*, BitReader, BitWriter, DecodeError};
+
+	let unsigned = [
+		0,
+		1,
+		2,
+		3,
+		7,
+		8,
+		1000,
+		u32::MAX as u64,
+		u64::MAX - 1,
+		u64::MAX,
+	];
+	let signed = [
+		0,
+		1,
+		-1,
+		2,
+		-2,
+		1000,
+		-1000,
+		i64::MAX,
+		i64::MIN + 1,
+		i64::MIN,
+	];
+	for &order in &[LsbFirst, MsbFirst] {
+		let mut buf = [0; 128];
+		let mut w = BitWriter::new(&mut buf, order);
+		for &v in &unsigned {
+			w.write_ue(v).unwrap();
+		}
+		for &v in &signed {
+			w.write_se(v).unwrap();
+		}
+		w.finish();
+		let mut r = BitReader::new(&buf, order);
+		for &v in &unsigned {
+			assert_eq!(r.read_ue(), Ok(v));
+		}
+		for &v in &signed {
+			assert_eq!(r.read_se(), Ok(v));
+		}
+	}
+
+	let mut r = BitReader::new(&[0, 0, 0, 0, 0, 0, 0, 0, 0x7F], MsbFirst);
+	assert_eq!(r.read_ue(), Err(DecodeError::InvalidCode));
+	assert_eq!(r.position(), 0);
+	let mut r = BitReader::new(&[0x01], MsbFirst);
+	assert_eq!(r.read_ue(), Err(DecodeError::OutOfBits));
+	assert_eq!(r.position(), 0);
+
+	let mut buf = [0; 1];
+	let mut w = BitWriter::new(&mut buf, MsbFirst);
+	assert!(w.write_ue(15).is_err());
+	assert_eq!(w.position(), 0);
+}
+
+#[test]
+fn test_rice() {
+	use crate::{BitOrder::*, BitReader, BitWriter, DecodeError};
+
+	for &order in &[LsbFirst, MsbFirst] {
+		let mut buf = [0; 64];
+		let mut w = BitWriter::new(&mut buf, order);
+		w.write_unary(0).unwrap();
+		w.write_unary(200).unwrap();
+		for k in 0..=8 {
+			w.write_rice(k, 37).unwrap();
+		}
+		w.write_rice(64, u64::MAX).unwrap();
+		assert!(w.write_unary(400).is_err());
+		w.finish();
+		let mut r = BitReader::new(&buf, order);
+		assert_eq!(r.read_unary(), Ok(0));
+		assert_eq!(r.read_unary(), Ok(200));
+		for k in 0..=8 {
+			assert_eq!(r.read_rice(k), Ok(37));
+		}
+		assert_eq!(r.read_rice(64), Ok(u64::MAX));
+		let pos = r.position();
+		assert_eq!(r.read_unary(), Err(DecodeError::OutOfBits));
+		assert_eq!(r.position(), pos);
+	}
+}
+
+#[test]
+fn test_leb128() {
+	use crate::{BitOrder::*, BitReader, BitWriter, DecodeError};
+
+	let unsigned = [0, 1, 127, 128, 624485, u32::MAX as u64, u64::MAX];
+	let signed = [0, 1, -1, 63, 64, -64, -65, -123456, i64::MAX, i64::MIN];
+	for &order in &[LsbFirst, MsbFirst] {
+		let mut buf = [0; 64];
+		let mut w = BitWriter::new(&mut buf, order);
+		w.write_bits(5, 3).unwrap();
+		for &v in &unsigned {
+			w.write_uleb128(v).unwrap();
+		}
+		for &v in &signed {
+			w.write_sleb128(v).unwrap();
+		}
+		w.finish();
+		let mut r = BitReader::new(&buf, order);
+		r.skip(3).unwrap();
+		for &v in &unsigned {
+			assert_eq!(r.read_uleb128(), Ok(v));
+		}
+		for &v in &signed {
+			assert_eq!(r.read_sleb128(), Ok(v));
+		}
+	}
+
+	let mut r = BitReader::new(&[0xFF; 9][..], LsbFirst);
+	assert_eq!(r.read_uleb128(), Err(DecodeError::OutOfBits));
+	let mut r = BitReader::new(
+		&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02][..],
+		LsbFirst,
+	);
+	assert_eq!(r.read_uleb128(), Err(DecodeError::InvalidCode));
+	assert_eq!(r.position(), 0);
+	let mut buf = [0; 2];
+	let mut w = BitWriter::new(&mut buf, LsbFirst);
+	assert!(w.write_sleb128(-10000).is_err());
+	assert_eq!(w.position(), 0);
+}
+
+#[test]
+#[cfg(all(feature = "heapless", feature = "arrayvec"))]
+fn test_bit_sink() {
+	use crate::{BitOrder::*, BitWriter, OutOfBits};
+
+	let mut w = BitWriter::new(heapless::Vec::<u8, 2>::new(), LsbFirst);
+	assert_eq!(w.remaining_bits(), 16);
+	w.write_bits(0x3, 2).unwrap();
+	assert_eq!(w.get_ref().len(), 1);
+	w.write_bits(0xFF, 8).unwrap();
+	assert_eq!(w.get_ref().len(), 2);
+	assert_eq!(w.write_bits(0, 7), Err(OutOfBits));
+	assert_eq!(w.into_inner(), [0xFF, 0x03]);
+
+	let mut v = arrayvec::ArrayVec::<u8, 4>::new();
+	let mut w = BitWriter::new(&mut v, MsbFirst);
+	w.write_ue(3).unwrap();
+	w.write_bits(0xABC, 12).unwrap();
+	assert_eq!(w.finish(), 17);
+	assert_eq!(&v[..], [0x25, 0x5E, 0x00]);
+}
+
+#[test]
+#[cfg(feature = "embedded-io-async")]
+fn test_async_bit_reader() {
+	use crate::{AsyncBitReader, BitOrder::*, ReadError};
+	use core::future::Future;
+	use core::task::{Context, Poll, Waker};
+	use embedded_io_async::{ErrorKind, ErrorType, Read};
+
+	fn block_on<F: Future>(f: F) -> F::Output {
+		let mut f = core::pin::pin!(f);
+		let mut cx = Context::from_waker(Waker::noop());
+		loop {
+			if let Poll::Ready(r) = f.as_mut().poll(&mut cx) {
+				return r;
+			}
+		}
+	}
+
+	// Returns at most three bytes per read, and an error at the end if `fail`.
+	struct Chunks<'a> {
+		data: &'a [u8],
+		fail: bool,
+	}
+
+	impl ErrorType for Chunks<'_> {
+		type Error = ErrorKind;
+	}
+
+	impl Read for Chunks<'_> {
+		async fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+			if self.data.is_empty() && self.fail {
+				return Err(ErrorKind::Other);
+			}
+			let n = buf.len().min(self.data.len()).min(3);
+			buf[..n].copy_from_slice(&self.data[..n]);
+			self.data = &self.data[n..];
+			Ok(n)
+		}
+	}
+
+	let data: [u8; 24] = core::array::from_fn(|i| (i as u8).wrapping_mul(37) ^ 0x5A);
+	for &order in &[LsbFirst, MsbFirst] {
+		let source = Chunks {
+			data: &data,
+			fail: false,
+		};
+		let mut r = AsyncBitReader::<_, 9>::new(source, order);
+		let mut expected = crate::BitReader::new(&data, order);
+		block_on(async {
+			for &n in &[3, 64, 1, 17, 0, 33, 64] {
+				assert_eq!(r.peek_bits(n).await, Ok(expected.peek_bits(n).unwrap()));
+				assert_eq!(r.read_bits(n).await, Ok(expected.read_bits(n).unwrap()));
+				assert_eq!(r.position(), expected.position());
+			}
+			r.align_to_byte().await.unwrap();
+			assert!(r.is_aligned());
+			r.skip(5).await.unwrap();
+			assert_eq!(r.position(), 189);
+			expected.align_to_byte();
+			expected.skip(5).unwrap();
+			assert_eq!(r.read_bits(3).await, Ok(expected.read_bits(3).unwrap()));
+			assert_eq!(r.read_bits(1).await, Err(ReadError::OutOfBits));
+		});
+	}
+
+	let source = Chunks {
+		data: &data[..2],
+		fail: true,
+	};
+	let mut r = AsyncBitReader::<_>::new(source, LsbFirst);
+	block_on(async {
+		assert_eq!(
+			r.read_bits(16).await,
+			Ok(u16::from_le_bytes([data[0], data[1]]) as u64)
+		);
+		assert_eq!(r.read_bit().await, Err(ReadError::Io(ErrorKind::Other)));
+	});
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_bit_stream() {
+	use crate::{BitOrder::*, BitReader, BitWriter, IoBitReader, IoBitWriter};
+	use std::io::ErrorKind;
+	use std::vec::Vec;
+
+	for &order in &[LsbFirst, MsbFirst] {
+		let mut w = IoBitWriter::<_, 9>::new(Vec::new(), order);
+		for i in 0..20u64 {
+			w.write_bits(i * 0x0123_4567_89AB % (1 << (i + 40)), (i + 40) as u32)
+				.unwrap();
+			w.write_bit(i % 3 == 0).unwrap();
+		}
+		assert_eq!(w.position(), 20 * 41 + 190);
+		let data = w.finish().unwrap();
+		assert_eq!(data.len(), 127);
+
+		let mut r = IoBitReader::<_, 9>::new(&data[..], order);
+		let mut expected = BitReader::new(&data, order);
+		for i in 0..20u64 {
+			let n = (i + 40) as u32;
+			assert_eq!(r.peek_bits(n).unwrap(), expected.peek_bits(n).unwrap());
+			assert_eq!(r.read_bits(n).unwrap(), i * 0x0123_4567_89AB % (1 << n));
+			expected.skip(n as usize + 1).unwrap();
+			assert_eq!(r.read_bit().unwrap(), i % 3 == 0);
+		}
+		assert_eq!(r.position(), 1010);
+		r.align_to_byte().unwrap();
+		assert_eq!(r.read_bit().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+	}
+
+	let mut w = BitWriter::into_write(Vec::new(), MsbFirst);
+	w.write_bits(0x5, 3).unwrap();
+	let data = w.finish().unwrap();
+	assert_eq!(
+		BitReader::from_read(&data[..], MsbFirst)
+			.read_bits(8)
+			.unwrap(),
+		0xA0
+	);
+}
+
+#[test]
+fn test_error() {
+	use crate::enc8b10b::CodeError;
+	use crate::{BuildError, DecodeError, Error, InvalidValue, OutOfBits, ParseBinError};
+	use crate::{PartitionError, ReadError, SchemaError};
+
+	fn source(e: &dyn core::error::Error) -> Option<&dyn core::error::Error> {
+		e.source()
+	}
+
+	assert_eq!(Error::from(OutOfBits), Error::OutOfBits);
+	assert_eq!(Error::from(DecodeError::InvalidCode), Error::InvalidCode);
+	assert_eq!(Error::from(CodeError::WrongDisparity), Error::InvalidCode);
+	assert_eq!(Error::from(BuildError::ValueTooWide), Error::ValueTooWide);
+	assert_eq!(
+		Error::from(SchemaError::OutOfRange { field: 1 }),
+		Error::InvalidRange
+	);
+	assert_eq!(
+		Error::from(PartitionError::Gap { start: 0, end: 1 }),
+		Error::Gap
+	);
+	assert_eq!(Error::from(ParseBinError::Empty), Error::InvalidDigit);
+	assert_eq!(Error::from(InvalidValue(3u8)), Error::InvalidValue);
+	assert!(source(&Error::Overlap).is_none());
+	let e = ReadError::Io(ParseBinError::Empty);
+	assert_eq!(format!("{}", source(&e).unwrap()), "no binary digits");
+	assert_eq!(format!("{}", Error::ValueTooWide), "bits outside range");
+}
+
+#[test]
+fn test_iter_runs() {
+	use crate::{iter_runs, BitOrder::*, SliceBits};
+
+	assert_eq!(iter_runs(&[], LsbFirst).next(), None);
+	let mut runs = iter_runs(&[0x00, 0x00], LsbFirst);
+	assert_eq!(runs.next(), Some((false, 16)));
+	assert_eq!(runs.next(), None);
+	assert_eq!(runs.next(), None);
+
+	let data: [u8; 40] = core::array::from_fn(|i| match i % 7 {
+		0 => 0xFF,
+		1 | 2 => 0,
+		_ => (i as u8).wrapping_mul(0x9D) & 0xF3,
+	});
+	for &order in &[LsbFirst, MsbFirst] {
+		let mut pos = 0;
+		let mut prev = None;
+		for (bit, n) in iter_runs(&data, order) {
+			assert!(n > 0);
+			assert_ne!(prev, Some(bit));
+			for i in pos..pos + n {
+				assert_eq!(data.bits(i..i + 1, order) != 0, bit);
+			}
+			pos += n;
+			prev = Some(bit);
+		}
+		assert_eq!(pos, 320);
+	}
+}
+
+#[test]
+fn test_rank_select() {
+	use crate::{rank, select, BitOrder::*, RankIndex, SliceBits};
+
+	let mut data: [u8; 200] = core::array::from_fn(|i| (i as u8).wrapping_mul(0x9D) & 0x5B);
+	data[64..128].fill(0);
+	for &order in &[LsbFirst, MsbFirst] {
+		let mut storage = [0; 5];
+		let index = RankIndex::new(&data, order, &mut storage);
+		let mut ones = 0;
+		for i in 0..=1600 {
+			assert_eq!(rank(&data, i, order), ones);
+			assert_eq!(index.rank(i), ones);
+			if i < 1600 && data.bits(i..i + 1, order) != 0 {
+				assert_eq!(select(&data, ones, order), Some(i));
+				assert_eq!(index.select(ones), Some(i));
+				ones += 1;
+			}
+		}
+		assert_eq!(index.count_ones(), ones);
+		assert_eq!(select(&data, ones, order), None);
+		assert_eq!(index.select(ones), None);
+	}
+
+	let mut storage = [];
+	let index = RankIndex::new(&[], LsbFirst, &mut storage);
+	assert_eq!((index.rank(0), index.select(0)), (0, None));
+}
+
+#[test]
+fn test_pack_bits() {
+	use crate::{pack_bits, unpack_bits, BitOrder::*};
+
+	let values: [u32; 37] = core::array::from_fn(|i| (i as u32).wrapping_mul(2654435761) >> 9);
+	for &order in &[LsbFirst, MsbFirst] {
+		for &width in &[23, 24, 25, 32] {
+			let mut buf = [0xFF; 160];
+			let n = pack_bits(&values, width, &mut buf, order);
+			assert_eq!(n, (37 * width as usize).div_ceil(8));
+			let mut out = [0; 37];
+			unpack_bits(&buf[..n], width, &mut out, order);
+			assert_eq!(out, values);
+		}
+	}
+}
+
+#[test]
+fn test_raw10_raw12() {
+	use crate::{pack_raw10, pack_raw12, unpack_raw10, unpack_raw12};
+
+	let pixels: [u16; 12] = core::array::from_fn(|i| (i as u16 * 331 + 7) & 0x3FF);
+	let mut packed = [0; 15];
+	assert_eq!(pack_raw10(&pixels, &mut packed), 15);
+	let mut out = [0; 13];
+	assert_eq!(unpack_raw10(&packed, &mut out), 12);
+	assert_eq!(out[..12], pixels);
+	assert_eq!(unpack_raw10(&packed[..14], &mut out), 8);
+
+	let pixels: [u16; 12] = core::array::from_fn(|i| (i as u16 * 1331 + 7) & 0xFFF);
+	let mut packed = [0; 18];
+	assert_eq!(pack_raw12(&pixels, &mut packed), 18);
+	let mut out = [0; 12];
+	assert_eq!(unpack_raw12(&packed, &mut out), 12);
+	assert_eq!(out, pixels);
+}
+
+#[test]
+fn test_can_signals() {
+	use crate::can::{extract_signal, insert_signal, ByteOrder::*, Signedness::*};
+
+	let frame = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
+	assert_eq!(
+		extract_signal(&frame, 0, 64, Intel, Unsigned),
+		0xF0DEBC9A78563412u64 as i64
+	);
+	assert_eq!(
+		extract_signal(&frame, 7, 64, Motorola, Unsigned),
+		0x123456789ABCDEF0
+	);
+	assert_eq!(extract_signal(&frame, 12, 8, Intel, Unsigned), 0x63);
+	assert_eq!(extract_signal(&frame, 12, 8, Intel, Signed), 0x63);
+	assert_eq!(extract_signal(&frame, 60, 4, Intel, Signed), -1);
+	// Starts at bit 3 of byte 1, continues into byte 2.
+	assert_eq!(extract_signal(&frame, 11, 8, Motorola, Unsigned), 0x45);
+	assert_eq!(extract_signal(&frame, 55, 4, Motorola, Signed), -3);
+
+	let mut f = [0u8; 8];
+	insert_signal(&mut f, 11, 8, Motorola, Unsigned, 0xFF);
+	assert_eq!(f[1..3], [0x0F, 0xF0]);
+	insert_signal(&mut f, 40, 9, Intel, Signed, -256);
+	assert_eq!(f[5..7], [0x00, 0x01]);
+	assert_eq!(extract_signal(&f, 40, 9, Intel, Signed), -256);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_can_signal_panic() {
+	crate::can::insert_signal(
+		&mut [0; 8],
+		0,
+		4,
+		crate::can::ByteOrder::Intel,
+		crate::can::Signedness::Signed,
+		8,
+	);
+}
+
+#[test]
+fn test_be_field() {
+	use crate::{read_be_field, write_be_field};
+
+	// TCP header: ports 443 -> 51000, data offset 5, flags SYN|ACK.
+	let mut tcp = [0u8; 20];
+	write_be_field(&mut tcp, 0, 0, 16, 443);
+	write_be_field(&mut tcp, 0, 16, 16, 51000);
+	write_be_field(&mut tcp, 12, 0, 4, 5);
+	write_be_field(&mut tcp, 12, 11, 1, 1);
+	write_be_field(&mut tcp, 12, 14, 1, 1);
+	assert_eq!(tcp[..4], [0x01, 0xBB, 0xC7, 0x38]);
+	assert_eq!(tcp[12..14], [0x50, 0x12]);
+	assert_eq!(read_be_field(&tcp, 12, 0, 4), 5);
+	assert_eq!(read_be_field(&tcp, 12, 10, 6), 0x12);
+	assert_eq!(read_be_field(&tcp, 0, 0, 64), 0x01BB_C738_0000_0000);
+	assert_eq!(read_be_field(&tcp, 19, 8, 0), 0);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_be_field_panic() {
+	crate::read_be_field(&[0; 4], usize::MAX / 4, 0, 8);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_bits_be() {
+	use core::num::{NonZeroU16, Wrapping};
+
+	// A big endian 16-bit register with a 4-bit field in the top nibble.
+	let wire = [0xA1, 0x23];
+	let mut reg = u16::from_le_bytes(wire);
+	assert_eq!(reg.bits_be(12..16), 0xA);
+	assert_eq!(reg.bits_be(0..12), 0x123);
+	reg.set_bits_be(12..16, 0x5);
+	assert_eq!(reg.to_le_bytes(), [0x51, 0x23]);
+
+	assert_eq!(0x1234i16.bits_be(..8), 0x12);
+	assert_eq!(0x12u8.bits_be(4..), 1);
+	assert_eq!(Wrapping(0xAABBu16).bits_be(0..8), 0xAA);
+	let mut nz = NonZeroU16::new(0x0100).unwrap();
+	nz.set_bits_be(8..16, 0x02);
+	assert_eq!(nz.get(), 0x0102);
+}
+
+// A `Bits` implementation like one outside of this crate, with only the
+// required methods.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct Minimal(u16);
+
+impl Bits for Minimal {
+	type Bits = u16;
+	const N_BITS: u32 = 16;
+	fn bit<I: crate::BitsIndex<Self>>(self, i: I) -> bool {
+		I::bit(self, i)
+	}
+	fn bits<I: crate::BitsIndex<Self>, R: core::ops::RangeBounds<I>>(self, range: R) -> u16 {
+		I::bits(self, range)
+	}
+	fn set_bit<I: crate::BitsIndex<Self>>(&mut self, i: I, bit: bool) {
+		I::set_bit(self, i, bit)
+	}
+	fn set_bits<I: crate::BitsIndex<Self>, R: core::ops::RangeBounds<I>>(
+		&mut self,
+		range: R,
+		bits: u16,
+	) {
+		I::set_bits(self, range, bits)
+	}
+	fn with_bit<I: crate::BitsIndex<Self>>(mut self, i: I, bit: bool) -> Self {
+		I::set_bit(&mut self, i, bit);
+		self
+	}
+	fn with_bits<I: crate::BitsIndex<Self>, R: core::ops::RangeBounds<I>>(
+		mut self,
+		range: R,
+		bits: u16,
+	) -> Self {
+		I::set_bits(&mut self, range, bits);
+		self
+	}
+}
+
+impl crate::BitsIndex<Minimal> for u32 {
+	fn bit(v: Minimal, i: u32) -> bool {
+		v.0.bit(i)
+	}
+	fn bits<R: core::ops::RangeBounds<u32>>(v: Minimal, range: R) -> u16 {
+		v.0.bits(range)
+	}
+	fn set_bit(v: &mut Minimal, i: u32, bit: bool) {
+		v.0.set_bit(i, bit)
+	}
+	fn set_bits<R: core::ops::RangeBounds<u32>>(v: &mut Minimal, range: R, bits: u16) {
+		v.0.set_bits(range, bits)
+	}
+}
+
+#[test]
+fn test_provided_methods() {
+	let mut reg = Minimal(u16::from_le_bytes([0xA1, 0x23]));
+	assert_eq!(reg.bits_be(12..16), 0xA);
+	assert_eq!(reg.bits_be(0..12), 0x123);
+	reg.set_bits_be(12..16, 0x5);
+	assert_eq!(reg.0.to_le_bytes(), [0x51, 0x23]);
+	assert_eq!(Minimal(0xFFF0).bits_q(4.., 8), -0.00390625);
+	assert_eq!(Minimal(0xC000).bits_uq(8..16, 8), 0.75);
+	assert_eq!(Minimal(0x00EC).bits_q_scaled(0..8, 4, 1000), -1250);
+	assert_eq!(Minimal(0x0C88).bits_sign_magnitude(3..12), -145);
+	assert_eq!(Minimal(0x0C88).bits_ones_complement(3..12), -110);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_bits_q() {
+	use core::num::{NonZeroU16, Wrapping};
+
+	// A 12-bit left-aligned accelerometer sample, Q4.8 in g.
+	let sample = -128i16;
+	assert_eq!(sample.bits_q(4.., 8), -0.03125);
+	assert_eq!(sample.bits_q_scaled(4.., 8, 1000), -32);
+	assert_eq!(0x7FFFu16.bits_q(0..16, 15), 32767.0 / 32768.0);
+	assert_eq!(0x8000u16.bits_q(0..16, 15), -1.0);
+	assert_eq!(0x8000u16.bits_uq(0..16, 15), 1.0);
+	assert_eq!(0xFFu8.bits_q(0..0, 0), 0.0);
+	assert_eq!(u128::MAX.bits_q(0..128, 0), -1.0);
+	assert_eq!(0x60u8.bits_q(5..7, 0), -1.0);
+	assert_eq!(Wrapping(0x30u8).bits_q(4..6, 1), -0.5);
+	assert_eq!(
+		NonZeroU16::new(0x0300).unwrap().bits_q_scaled(8..10, 1, 10),
+		-5
+	);
+}
+
+#[test]
+#[should_panic(expected = "fixed-point value out of range")]
+fn test_bits_q_scaled_panic() {
+	0x4000_0000_0000_0000u64.bits_q_scaled(0..64, 0, 2);
+}
+
+#[test]
+fn test_ones_complement_sign_magnitude() {
+	// A sensor reporting temperature as 9-bit sign-magnitude in bits 3..12.
+	assert_eq!(0x0C88u16.bits_sign_magnitude(3..12), -145);
+	assert_eq!(0x0800u16.bits_sign_magnitude(3..12), 0);
+	assert_eq!(0x0488u16.bits_sign_magnitude(3..12), 145);
+	assert_eq!(0x0C88u16.bits_ones_complement(3..12), -110);
+	assert_eq!(0x0FF8u16.bits_ones_complement(3..12), 0);
+	assert_eq!(u64::MAX.bits_sign_magnitude(0..64), -i64::MAX);
+	assert_eq!(
+		0u64.with_bit(63, true).bits_ones_complement(0..64),
+		-i64::MAX
+	);
+	assert_eq!(0xFFu8.bits_sign_magnitude(0..0), 0);
+	assert_eq!(0xFFu8.bits_ones_complement(7..8), 0);
+	assert_eq!(0x80u8.bits_sign_magnitude(7..8), 0);
+}
+
+#[test]
+#[should_panic(expected = "bit range too wide")]
+#[cfg(feature = "impl-128")]
+fn test_sign_magnitude_panic() {
+	0u128.bits_sign_magnitude(0..65);
+}
+
+#[test]
+#[allow(clippy::unusual_byte_groupings)]
+fn test_bitplanes() {
+	use crate::{merge_bitplanes, split_bitplanes};
+
+	// 4 bits per pixel, 16 pixels: 0, 1, 2, ..., 15.
+	let pixels = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+	let mut planes = [[0u8; 2]; 4];
+	let [p0, p1, p2, p3] = &mut planes;
+	assert_eq!(split_bitplanes(&pixels, &mut [p0, p1, p2, p3]), 2);
+	assert_eq!(
+		planes,
+		[[0x55, 0x55], [0x33, 0x33], [0x0F, 0x0F], [0x00, 0xFF]]
+	);
+
+	let mut out = [0u8; 9];
+	let [p0, p1, p2, p3] = &planes;
+	assert_eq!(merge_bitplanes(&[p0, p1, p2, p3], &mut out), 8);
+	assert_eq!(out[..8], pixels);
+	assert_eq!(out[8], 0);
+
+	// 3 bits per pixel, limited by the shortest plane.
+	let pixels = [0b000_001_01, 0b0_011_100_1, 0b01_110_111, 0xFF, 0xFF, 0xFF];
+	let (mut p0, mut p1, mut p2) = ([0; 2], [0; 1], [0; 2]);
+	assert_eq!(
+		split_bitplanes(&pixels, &mut [&mut p0, &mut p1, &mut p2]),
+		1
+	);
+	assert_eq!((p0, p1, p2), ([0x55, 0], [0x33], [0x0F, 0]));
+	let mut out = [0; 3];
+	assert_eq!(merge_bitplanes(&[&p0, &p1, &p2], &mut out), 3);
+	assert_eq!(out, pixels[..3]);
+
+	let mut p = [0; 1];
+	assert_eq!(split_bitplanes(&[0xA5], &mut [&mut p]), 1);
+	assert_eq!(p, [0xA5]);
+}
+
+#[test]
+fn test_int_set() {
+	use crate::IntSet;
+	use std::vec::Vec;
+
+	let mut s = IntSet::<u8>::new();
+	assert!(s.is_empty());
+	assert!(s.insert(7));
+	assert!(s.insert(0));
+	assert!(!s.insert(7));
+	assert_eq!(s.bits(), 0x81);
+	assert_eq!((s.first(), s.last()), (Some(0), Some(7)));
+	assert!(!s.contains(8));
+	assert!(s.remove(0));
+	assert!(!s.remove(0));
+	assert_eq!(s, IntSet(0x80));
+
+	let a = IntSet(0b1100u32);
+	let b = IntSet(0b1010u32);
+	assert_eq!(a | b, IntSet(0b1110));
+	assert_eq!(a & b, IntSet(0b1000));
+	assert_eq!(a - b, IntSet(0b0100));
+	assert_eq!(a ^ b, IntSet(0b0110));
+	assert!((a & b).is_subset(a));
+	assert!(!a.is_disjoint(b));
+	let mut c = a;
+	c -= a;
+	assert_eq!(c, IntSet::<u32>::EMPTY);
+
+	let full = IntSet::<u128>::FULL;
+	assert_eq!(full.len(), 128);
+	assert_eq!(full.iter().next_back(), Some(127));
+	assert_eq!(full.iter().sum::<u32>(), 127 * 128 / 2);
+	let v: Vec<u32> = IntSet(0x8000_0000_0000_0001u64).into_iter().collect();
+	assert_eq!(v, [0, 63]);
+	assert_eq!(format!("{:?}", IntSet(0u16)), "{}");
+}
+
+#[test]
+#[should_panic(expected = "invalid bit index")]
+fn test_int_set_panic() {
+	crate::IntSet::<u16>::new().insert(16);
+}
+
+#[test]
+fn test_fixed_bit_set() {
+	use crate::FixedBitSet;
+	use std::vec::Vec;
+
+	let mut a = FixedBitSet::<3>::new();
+	assert!(a.is_empty());
+	assert_eq!((a.first(), a.last()), (None, None));
+	for i in [0, 63, 64, 100, 191] {
+		assert!(a.insert(i));
+	}
+	assert!(!a.insert(100));
+	assert_eq!(a.len(), 5);
+	assert_eq!(a.iter().collect::<Vec<_>>(), [0, 63, 64, 100, 191]);
+	assert_eq!(a.iter().rev().collect::<Vec<_>>(), [191, 100, 64, 63, 0]);
+	let mut it = a.iter();
+	assert_eq!(it.next_back(), Some(191));
+	assert_eq!(it.next(), Some(0));
+	assert_eq!(it.next_back(), Some(100));
+	assert_eq!(it.next(), Some(63));
+	assert_eq!(it.next_back(), Some(64));
+	assert_eq!((it.next(), it.next_back()), (None, None));
+	assert_eq!(a.bits(60..68), 0x18);
+	assert!(!a.contains(192));
+
+	let b: FixedBitSet<3> = [63, 64, 65].iter().copied().collect();
+	assert_eq!((a & b).iter().collect::<Vec<_>>(), [63, 64]);
+	assert_eq!((a - b).len(), 3);
+	assert_eq!((a ^ b).iter().collect::<Vec<_>>(), [0, 65, 100, 191]);
+	assert_eq!((a | b).len(), 6);
+	assert!((a & b).is_subset(&b));
+	assert!(a.difference(&b).is_disjoint(&b));
+	assert!(a.remove(191));
+	assert_eq!(a.last(), Some(100));
+	assert_eq!(format!("{:?}", b), "{63, 64, 65}");
+	a.clear();
+	assert_eq!(a, FixedBitSet::default());
+
+	let one = FixedBitSet::<1>::from_words([0x8000_0000_0000_0001]);
+	assert_eq!(one.iter().rev().collect::<Vec<_>>(), [63, 0]);
+	assert_eq!(FixedBitSet::<0>::new().iter().next(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_dyn_bit_set() {
+	use crate::DynBitSet;
+	use std::vec::Vec;
+
+	let mut a = DynBitSet::new();
+	assert!(a.is_empty());
+	assert_eq!((a.n_bits(), a.first(), a.last()), (0, None, None));
+	for i in [0, 63, 64, 100, 191] {
+		assert!(a.insert(i));
+	}
+	assert!(!a.insert(100));
+	assert_eq!((a.n_bits(), a.len()), (192, 5));
+	assert_eq!(a.iter().collect::<Vec<_>>(), [0, 63, 64, 100, 191]);
+	assert_eq!(a.iter().rev().collect::<Vec<_>>(), [191, 100, 64, 63, 0]);
+	assert_eq!(a.bits(60..68), 0x18);
+	assert_eq!(a.bits(188..196), 0x8);
+	assert!(!a.contains(1000));
+	assert!(!a.remove(1000));
+	assert_eq!(a.n_bits(), 192);
+	a.set_bits(250..260, 0x3FF);
+	assert_eq!((a.n_bits(), a.len()), (320, 15));
+	a.set_bits(250..260, 0);
+
+	let b: DynBitSet = [63, 64, 65].iter().copied().collect();
+	assert_eq!(b.n_bits(), 128);
+	assert_eq!((a.clone() & &b).iter().collect::<Vec<_>>(), [63, 64]);
+	assert_eq!((b.clone() & &a).iter().collect::<Vec<_>>(), [63, 64]);
+	assert_eq!((a.clone() - &b).len(), 3);
+	assert_eq!((b.clone() - &a).iter().collect::<Vec<_>>(), [65]);
+	assert_eq!(
+		(b.clone() ^ &a).iter().collect::<Vec<_>>(),
+		[0, 65, 100, 191]
+	);
+	assert_eq!((b.clone() | &a).len(), 6);
+	assert!((a.clone() & &b).is_subset(&b));
+	assert!(!a.is_subset(&b));
+	assert!(a.clone().difference(&b).is_disjoint(&b));
+	assert_eq!(format!("{:?}", b), "{63, 64, 65}");
+
+	let mut c = b.clone().with_bit(500, true);
+	assert_ne!(c, b);
+	c.remove(500);
+	assert_eq!(c, b);
+	assert!(c.n_bits() > b.n_bits());
+	c.shrink_to_fit();
+	assert_eq!(c.words(), b.words());
+	c.clear();
+	assert_eq!(c, DynBitSet::default());
+}
+
+#[test]
+#[cfg(feature = "impl-128")]
+fn test_wide_int() {
+	use crate::WideInt;
+
+	type U256 = WideInt<4>;
+	assert_eq!(U256::N_BITS, 256);
+	let mut a = U256::from(0x1234_5678_9ABC_DEF0_1122_3344_5566_7788u128);
+	assert_eq!(a.bits(60..68), 0x01);
+	assert_eq!(a.bits(0..128), 0x1234_5678_9ABC_DEF0_1122_3344_5566_7788);
+	a.set_bits(250.., 0x3F);
+	a.set_bit(200u8, true);
+	assert_eq!(a.words()[3], 0xFC00_0000_0000_0100);
+	assert!(a.bit(255i32));
+	assert_eq!(a.bits(192..256), 0xFC00_0000_0000_0100);
+	assert_eq!(a.bits_be(248..256), 0x88);
+	assert_eq!(a.bits_q(250..256, 0), -1.0);
+	assert_eq!(a.with_bits(0..128, 0).count_ones(), 7);
+
+	let one = U256::from(1);
+	assert_eq!((one << 255).words(), &[0, 0, 0, 1 << 63]);
+	assert_eq!((one << 255) >> 190, one << 65);
+	assert_eq!(!U256::ZERO, U256::MAX);
+	assert_eq!(U256::MAX & (one << 100 | one), one << 100 | one);
+	assert_eq!(one ^ one, U256::ZERO);
+	assert!(one << 64 > U256::from(u64::MAX as u128));
+	assert_eq!(format!("{:?}", one << 68), "0x100000000000000000");
+	assert_eq!(format!("{:X}", U256::ZERO), "0");
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+#[cfg(feature = "impl-128")]
+fn test_wide_int_panic() {
+	crate::WideInt::<2>::ZERO.bits(-1..4);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_narrow() {
+	use crate::{u1, u24, u48, u7, U};
+	use core::convert::TryFrom;
+
+	assert_eq!(u7::MAX.get(), 0x7F);
+	assert_eq!(u48::MAX.get(), 0xFFFF_FFFF_FFFF);
+	assert_eq!(u1::N_BITS, 1);
+	assert_eq!(u7::try_from(0x80u8), Err(crate::InvalidValue(0x80)));
+	assert_eq!(u7::try_from(0x7Fu8), Ok(u7::MAX));
+
+	let mut x = u24::new(0x12_3456);
+	assert_eq!(x.bits(16..), 0x12);
+	assert_eq!(x.bits(..=7i8), 0x56);
+	assert_eq!(x.bits_be(0..8), 0x12);
+	x.set_bits(20..24, 0xF);
+	x.set_bit(0, true);
+	assert_eq!(u32::from(x), 0xF2_3457);
+	assert!(x.bit(23));
+	assert_eq!(x.bits_q(16..24, 0), -14.0);
+	assert_eq!(format!("{} {:?}", x, x), "15873111 15873111");
+	assert_eq!(U::<u64, 64>::MAX.get(), u64::MAX);
+	assert!(u7::new(3) < u7::new(4));
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_narrow_range_panic() {
+	crate::u7::new(1).bits(0..8);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit index")]
+fn test_narrow_index_panic() {
+	crate::u7::new(1).set_bit(7, true);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_narrow_new_panic() {
+	crate::u12::new(0x1000);
+}
+
+#[cfg(feature = "portable_simd")]
+#[test]
+fn test_simd_bits() {
+	use crate::SimdBits;
+	use core::simd::Simd;
+
+	let v = Simd::from_array([-1i16, 0x1234, 0, 0x7FFF]);
+	assert_eq!(v.bits(8..16).to_array(), [0xFF, 0x12, 0, 0x7F]);
+	assert_eq!(v.bits(16..).to_array(), [0; 4]);
+	assert_eq!(v.bit(15).to_array(), [true, false, false, false]);
+	let w = v
+		.with_bits(0..8, Simd::from_array([1, 2, 3, 4]))
+		.with_bit(15, true);
+	assert_eq!(
+		w.to_array(),
+		[
+			0xFF01u16 as i16,
+			0x9202u16 as i16,
+			0x8003u16 as i16,
+			0xFF04u16 as i16
+		]
+	);
+
+	let mut x = Simd::<u64, 2>::splat(0);
+	x.set_bits(60..64, Simd::from_array([0xA, 0x5]));
+	assert_eq!(x.to_array(), [0xA << 60, 0x5 << 60]);
+}
+
+#[cfg(feature = "portable_simd")]
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_simd_bits_panic() {
+	use crate::SimdBits;
+	core::simd::Simd::<u8, 4>::splat(0)
+		.set_bits(0..4, core::simd::Simd::from_array([0, 0, 0x10, 0]));
+}
+
+#[test]
+fn test_batch() {
+	use crate::batch::*;
+
+	let mut w = [0u64, u64::MAX, 0x1234_5678_9ABC_DEF0];
+	set_bits_all(&mut w, 60..64, 0x5);
+	assert_eq!(w, [0x5 << 60, 0x5FFF_FFFF_FFFF_FFFF, 0x5234_5678_9ABC_DEF0]);
+	set_bit_all(&mut w, 0, true);
+	assert_eq!(count_ones_all(&w, ..), 3 + 62 + 34);
+	assert_eq!(count_ones_all(&w, 64..), 0);
+	set_bits_all(&mut w, 64.., 0);
+
+	let mut out = [0; 4];
+	extract_bits_all(&w, 0..8, &mut out);
+	assert_eq!(out, [0x01, 0xFF, 0xF1, 0]);
+	extract_bits_all(&w, .., &mut out);
+	assert_eq!(out[..3], w);
+
+	insert_bits_all(&mut w, 56..64, &[1, 2, 3]);
+	assert_eq!(w.map(|w| w >> 56), [1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_batch_panic() {
+	crate::batch::insert_bits_all(&mut [0; 2], 0..4, &[1, 16]);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_generic_bits() {
+	use crate::{u12, BitsIndex, WideInt};
+	use core::num::{NonZeroU32, Wrapping};
+
+	// Reverse the order of the lowest `n` bits.
+	fn reverse_low<T: Bits>(x: T, n: u32) -> T
+	where
+		u32: BitsIndex<T>,
+	{
+		let bits = x.bits(0..n);
+		let mut rev = T::Bits::default();
+		for i in 0..n {
+			if bits >> i & T::Bits::from(1) != T::Bits::default() {
+				rev = rev | T::Bits::from(1) << (n - 1 - i);
+			}
+		}
+		x.with_bits(0..n, rev)
+	}
+
+	assert_eq!(reverse_low(0b0011u8, 4), 0b1100);
+	assert_eq!(reverse_low(-8i32, 4), -15);
+	assert_eq!(reverse_low(Wrapping(1u64), 64), Wrapping(1 << 63));
+	assert_eq!(reverse_low(NonZeroU32::new(0x10).unwrap(), 8).get(), 0x08);
+	assert_eq!(reverse_low(u12::new(0x801), 12), u12::new(0x801));
+	assert_eq!(
+		reverse_low(WideInt::<3>::from(1), 128),
+		WideInt::from(1 << 127)
+	);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_dyn_bits() {
+	use crate::{u12, DynBits, WideInt};
+	use core::num::NonZeroU64;
+	use std::boxed::Box;
+	use std::vec::Vec;
+
+	let mut regs: Vec<Box<dyn DynBits>> = std::vec![
+		Box::new(0x80u8),
+		Box::new(u12::new(0xABC)),
+		Box::new(NonZeroU64::new(1).unwrap()),
+		Box::new(WideInt::<4>::MAX),
+		Box::new(-1i128),
+	];
+	let n: Vec<u32> = regs.iter().map(|r| r.n_bits()).collect();
+	assert_eq!(n, [8, 12, 64, 256, 128]);
+	assert!(regs[0].bit(7));
+	assert_eq!(regs[1].bits(4..12), 0xAB);
+	regs[2].set_bits(32..64, 0xFFFF_FFFF);
+	assert_eq!(regs[2].bits(28..36), 0xF0);
+	assert_eq!(regs[3].bits(200..256), (1 << 56) - 1);
+	regs[4].set_bit(127, false);
+	assert_eq!(regs[4].bits(0..128), u128::MAX >> 1);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_dyn_bits_panic() {
+	crate::DynBits::set_bits(&mut 0u16, 0..16, 0x1_0000);
+}
+
+#[cfg(all(feature = "num-traits", feature = "impl-128", feature = "impl-signed"))]
+#[test]
+fn test_prim_int_bits() {
+	use crate::{u12, PrimIntBits, WideInt};
+	use num_traits::{Bounded, PrimInt, ToPrimitive};
+
+	fn field<T: PrimInt>(x: T) -> T {
+		PrimIntBits::bits(x, 4..12)
+	}
+
+	assert_eq!(field(0x1234u16), 0x23);
+	assert_eq!(field(-1i32), 0xFF);
+	assert!(PrimIntBits::bit(i8::MIN, 7));
+	assert_eq!(PrimIntBits::with_bits(-1i16, 12..16, 0), 0x0FFF);
+	assert_eq!(PrimIntBits::with_bit(0u128, 127, true), 1 << 127);
+	assert_eq!(PrimIntBits::bits(-1i64, 64..), 0);
+	assert_eq!(PrimIntBits::bits(-1i64, 0..64), -1);
+
+	assert_eq!(u12::max_value(), u12::new(0xFFF));
+	assert_eq!(u12::new(7).to_u8(), Some(7));
+	assert_eq!(WideInt::<4>::max_value().to_u64(), None);
+	assert_eq!(WideInt::<4>::from(1 << 64).to_u128(), Some(1 << 64));
+	assert_eq!(WideInt::<1>::MAX.to_u128(), Some(u64::MAX as u128));
+}
+
+#[cfg(feature = "funty")]
+#[test]
+fn test_integral_bits() {
+	use crate::IntegralBits;
+	use funty::Integral;
+
+	fn field<T: Integral>(x: T) -> T {
+		IntegralBits::bits(x, 4..12)
+	}
+
+	assert_eq!(field(0x1234u16), 0x23);
+	assert_eq!(field(-1i32), 0xFF);
+	assert!(IntegralBits::bit(i8::MIN, 7));
+	assert_eq!(IntegralBits::bits(i8::MIN, 0..7), 0);
+	assert_eq!(IntegralBits::bits(-1i8, 0..7), 0x7F);
+	assert_eq!(IntegralBits::with_bits(-1i16, 12..16, 0), 0x0FFF);
+	assert_eq!(IntegralBits::with_bit(0u128, 127, true), 1 << 127);
+	assert_eq!(IntegralBits::bits(-1i64, 64..), 0);
+	assert_eq!(IntegralBits::bits(-1i64, 0..64), -1);
+	assert_eq!(IntegralBits::with_bits(0i8, 1..8, 0x7F), -2);
+}
+
+#[cfg(all(feature = "bitvec", feature = "impl-128", feature = "impl-signed"))]
+#[test]
+fn test_bitslice() {
+	use crate::{load_bitslice, store_bitslice, u12, WideInt};
+	use bitvec::prelude::*;
+
+	let data = bitarr![u32, Msb0; 1; 40];
+	let x: u64 = load_bitslice(&data[..40]);
+	assert_eq!(x, (1 << 40) - 1);
+	let x: u128 = load_bitslice(BitSlice::<u8, Lsb0>::empty());
+	assert_eq!(x, 0);
+
+	let mut out = bitarr![u8, Msb0; 0; 24];
+	store_bitslice(u12::new(0xABC), &mut out[4..16]);
+	assert_eq!(out.as_raw_slice(), [0x03, 0xD5, 0]);
+	store_bitslice(-1i8, &mut out[16..]);
+	assert_eq!(out.as_raw_slice(), [0x03, 0xD5, 0xFF]);
+	let w: u16 = load_bitslice(&out[4..16]);
+	assert_eq!(w, 0xABC);
+	store_bitslice(WideInt::<3>::MAX >> 100, &mut bitarr![0; 100][..92]);
+}
+
+#[cfg(feature = "bitvec")]
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_bitslice_panic() {
+	use bitvec::prelude::*;
+	crate::store_bitslice(0x10u8, &mut bitarr![0; 8][..4]);
+}
+
+#[cfg(feature = "bitflags")]
+#[test]
+fn test_bits_as_flags() {
+	use crate::{InvalidValue, UnknownBits};
+
+	bitflags::bitflags! {
+		#[derive(Debug, PartialEq, Eq)]
+		struct Mode: u8 {
+			const READ = 0x1;
+			const WRITE = 0x2;
+			const EXEC = 0x8;
+		}
+	}
+
+	let reg = 0xB600u16;
+	assert_eq!(
+		reg.bits_as_flags::<Mode, _, _>(12..16, UnknownBits::Reject),
+		Ok(Mode::READ | Mode::WRITE | Mode::EXEC)
+	);
+	assert_eq!(
+		reg.bits_as_flags::<Mode, _, _>(8..12, UnknownBits::Reject),
+		Err(InvalidValue(6))
+	);
+	assert_eq!(
+		reg.bits_as_flags::<Mode, _, _>(8..12, UnknownBits::Truncate),
+		Ok(Mode::WRITE)
+	);
+	assert_eq!(
+		reg.bits_as_flags::<Mode, _, _>(8..12, UnknownBits::Retain)
+			.map(|m| m.bits()),
+		Ok(6)
+	);
+	assert_eq!(
+		0x1FFu16.bits_as_flags::<Mode, _, _>(0..9, UnknownBits::Retain),
+		Err(InvalidValue(0x1FF))
+	);
+	assert_eq!(
+		0x10Bu16.bits_as_flags::<Mode, _, _>(0..9, UnknownBits::Truncate),
+		Ok(Mode::all())
+	);
+
+	let mut r = 0u64;
+	r.set_bits_from_flags(60..64, Mode::EXEC | Mode::READ);
+	assert_eq!(r, 0x9 << 60);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_schema_serde() {
+	use crate::can::ByteOrder;
+	use crate::{BitField, BitOrder, BitSchema, FieldSpec, SchemaField};
+
+	let json = r#"[
+		{"name": "ENABLE", "range": {"start": 0, "end": 1}},
+		{"name": "MODE", "range": {"start": 1, "end": 4}}
+	]"#;
+	let fields: [SchemaField; 2] = serde_json::from_str(json).unwrap();
+	assert_eq!(fields[1], SchemaField::new("MODE", 1..4));
+	let schema = BitSchema::<u8>::new(&fields).unwrap();
+	assert_eq!(schema.get(0x0B, "MODE"), Some(5));
+	assert!(serde_json::to_string(&schema)
+		.unwrap()
+		.contains(r#""name":"MODE""#));
+	assert_eq!(
+		serde_json::to_string(&fields[0]).unwrap(),
+		r#"{"name":"ENABLE","range":{"start":0,"end":1}}"#
+	);
+	assert_eq!(
+		serde_json::to_string(&BitOrder::MsbFirst).unwrap(),
+		r#""MsbFirst""#
+	);
+	assert_eq!(
+		serde_json::from_str::<ByteOrder>(r#""Motorola""#).unwrap(),
+		ByteOrder::Motorola
+	);
+
+	let spec = FieldSpec::<u16>::new(4..12);
+	let json = serde_json::to_string(&spec).unwrap();
+	assert_eq!(json, r#"{"start":4,"end":12}"#);
+	assert_eq!(serde_json::from_str::<FieldSpec<u16>>(&json).unwrap(), spec);
+	assert!(serde_json::from_str::<FieldSpec<u8>>(&json).is_err());
+	let json = r#"{"start":4,"end":7}"#;
+	let field: BitField<u32, 4, 3> = serde_json::from_str(json).unwrap();
+	assert_eq!(serde_json::to_string(&field).unwrap(), json);
+	assert!(serde_json::from_str::<BitField<u32, 4, 4>>(json).is_err());
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[test]
+fn test_schema_buf_serde() {
+	use crate::{BitSchema, SchemaBuf, SchemaError, SchemaField};
+
+	// Escapes and readers need owned names.
+	let json = r#"{"fields": [{"name": "M\u004fDE", "range": {"start": 1, "end": 4}}]}"#;
+	let buf: SchemaBuf = serde_json::from_reader(json.as_bytes()).unwrap();
+	assert_eq!(buf.name, "");
+	assert_eq!(buf.fields, [SchemaField::new("MODE", 1..4)]);
+	assert_eq!(buf.fields[0], SchemaField::owned("MODE".into(), 1..4));
+	let schema = buf.schema::<u8>().unwrap();
+	assert_eq!(schema.get(0x0B, "MODE"), Some(5));
+
+	let fields = [SchemaField::new("A", 0..4), SchemaField::new("B", 4..8)];
+	let schema = BitSchema::<u8>::new(&fields).unwrap().with_name("R");
+	let json = serde_json::to_string(&schema).unwrap();
+	let buf: SchemaBuf = serde_json::from_str(&json).unwrap();
+	assert_eq!(buf.name, "R");
+	assert_eq!(buf.fields, fields);
+
+	let json = r#"{"fields": [{"name": "A", "range": {"start": 4, "end": 12}}]}"#;
+	let buf: SchemaBuf = serde_json::from_str(json).unwrap();
+	assert_eq!(
+		buf.schema::<u8>().unwrap_err(),
+		SchemaError::OutOfRange { field: 0 }
+	);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_bit_string() {
+	use crate::BitString;
+
+	assert_eq!(
+		serde_json::to_string(&BitString(5u8)).unwrap(),
+		r#""0b0000_0101""#
+	);
+	assert_eq!(
+		serde_json::to_string(&BitString(-2i16)).unwrap(),
+		r#""0b1111_1111_1111_1110""#
+	);
+	assert_eq!(
+		serde_json::from_str::<BitString<i8>>(r#""0b1000_0000""#).unwrap(),
+		BitString(-128)
+	);
+	assert_eq!(
+		serde_json::from_str::<BitString<u32>>(r#""101""#).unwrap(),
+		BitString(5)
+	);
+	assert_eq!(
+		serde_json::from_str::<BitString<u8>>("255").unwrap(),
+		BitString(255)
+	);
+	assert_eq!(
+		serde_json::from_str::<BitString<u128>>(r#""0b1""#).unwrap(),
+		BitString(1)
+	);
+	let max = serde_json::to_string(&BitString(u128::MAX)).unwrap();
+	assert_eq!(
+		serde_json::from_str::<BitString<u128>>(&max).unwrap(),
+		BitString(u128::MAX)
+	);
+	assert!(serde_json::from_str::<BitString<u8>>(r#""0b1_0000_0000""#).is_err());
+	assert!(serde_json::from_str::<BitString<u8>>(r#""0b""#).is_err());
+	assert!(serde_json::from_str::<BitString<u8>>(r#""0b12""#).is_err());
+	assert!(serde_json::from_str::<BitString<u8>>("256").is_err());
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+	use crate::{arbitrary_bit_index, arbitrary_bit_range, u12, FixedBitSet, IntSet};
+	use arbitrary::{Arbitrary, Unstructured};
+
+	let data = [0xFF; 64];
+	let mut u = Unstructured::new(&data);
+	assert_eq!(u12::arbitrary(&mut u).unwrap(), u12::MAX);
+	assert_eq!(IntSet::<u8>::arbitrary(&mut u).unwrap().len(), 8);
+	assert_eq!(FixedBitSet::<2>::arbitrary(&mut u).unwrap().len(), 128);
+
+	let data: std::vec::Vec<u8> = (0..=255).collect();
+	let mut u = Unstructured::new(&data);
+	while !u.is_empty() {
+		let range = arbitrary_bit_range(&mut u, 20).unwrap();
+		assert!(range.start <= range.end && range.end <= 20);
+		assert!(arbitrary_bit_index(&mut u, 20).unwrap() < 20);
+	}
+}
+
+#[test]
+#[cfg(feature = "impl-128")]
+fn test_bin() {
+	use crate::{u12, Bin, WideInt};
+	use core::num::NonZeroU8;
+
+	assert_eq!(format!("{}", Bin::new(0u8)), "0000_0000");
+	assert_eq!(format!("{:?}", Bin::new(1u16)), "0000_0000_0000_0001");
+	assert_eq!(
+		format!("{:#}", Bin::new(0x8000_0001u32).group(8, '\'')),
+		"0b10000000'00000000'00000000'00000001"
+	);
+	assert_eq!(
+		format!("{}", Bin::new(u12::new(0xA5C)).group(3, ' ')),
+		"101 001 011 100"
+	);
+	assert_eq!(
+		format!("{}", Bin::new(NonZeroU8::new(3).unwrap())),
+		"0000_0011"
+	);
+	assert_eq!(format!("{}", Bin::new(u64::MAX).group(32, ' ')).len(), 65);
+	assert_eq!(format!("{}", Bin::new(WideInt::<2>::MAX)).len(), 128 + 31);
+	assert_eq!(Bin::from(5u8).into_inner(), 5);
+}
+
+#[test]
+#[cfg(feature = "impl-128")]
+fn test_from_bin_str() {
+	use crate::{u12, Bin, FromBinStr, ParseBinError};
+
+	assert_eq!(u32::from_bin_str("101"), Ok(5));
+	assert_eq!(u8::from_bin_str("0b_1111_1111_"), Ok(255));
+	assert_eq!(u8::from_bin_str("0000_0000_0000_0001"), Ok(1));
+	assert_eq!(i16::from_bin_str("0b1000_0000_0000_0000"), Ok(i16::MIN));
+	assert_eq!(
+		u128::from_bin_str(&format!("{}", Bin::new(u128::MAX))),
+		Ok(u128::MAX)
+	);
+	assert_eq!(u12::from_bin_str("1111_0000_1010"), Ok(u12::new(0xF0A)));
+	assert_eq!(
+		u12::from_bin_str("1_1111_0000_1010"),
+		Err(ParseBinError::TooLong { position: 15 })
+	);
+	assert_eq!(u8::from_bin_str(""), Err(ParseBinError::Empty));
+	assert_eq!(u8::from_bin_str("0b__"), Err(ParseBinError::Empty));
+	assert_eq!(
+		u8::from_bin_str("0x1"),
+		Err(ParseBinError::InvalidDigit { position: 1 })
+	);
+	assert_eq!(
+		u8::from_bin_str("0b10é1"),
+		Err(ParseBinError::InvalidDigit { position: 4 })
+	);
+	assert_eq!(
+		format!("{}", ParseBinError::InvalidDigit { position: 4 }),
+		"invalid binary digit at position 4"
+	);
+
+	let mask: Bin<u16> = "0b1010_1100_0001_0000".parse().unwrap();
+	assert_eq!(mask.into_inner(), 0xAC10);
+	assert_eq!(
+		"2".parse::<Bin<u16>>().err(),
+		Some(ParseBinError::InvalidDigit { position: 0 })
+	);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_set_bits_debug() {
+	use crate::{u12, SetBits};
+
+	assert_eq!(format!("{:?}", SetBits(-1i8)), "{0, 1, 2, 3, 4, 5, 6, 7}");
+	assert_eq!(format!("{:?}", SetBits(1u128 << 127)), "{127}");
+	assert_eq!(format!("{:?}", SetBits(u12::new(0x801))), "{0, 11}");
+	assert_eq!(format!("{:#?}", SetBits(6u8)), "{\n    1,\n    2,\n}");
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_field_dump() {
+	use crate::{u7, FieldDump};
+
+	assert_eq!(
+		format!("{}", FieldDump::new(0xA5u8, 4..)),
+		"0xA5 = [1010] 0101 (bits 4..8 = 0b1010 = 10)"
+	);
+	assert_eq!(
+		format!("{:?}", FieldDump::new(-1i8, 0..=0)),
+		"0xFF = 1111 111[1] (bits 0..1 = 0b1 = 1)"
+	);
+	assert_eq!(
+		format!("{}", FieldDump::new(u7::new(0x5A), 3..3)),
+		"0x5A = 101 1010 (bits 3..3 = 0b0 = 0)"
+	);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_field_dump_panic() {
+	crate::FieldDump::new(0u8, 4..9);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_bits_mut() {
+	use crate::u12;
+	use core::num::Wrapping;
+
+	let mut x = 0xFFu8;
+	let mut f = x.bits_mut(0..4);
+	f &= 0b0101;
+	f += 0xE;
+	assert_eq!(f.get(), 0x3);
+	assert_eq!(x, 0xF3);
+
+	let mut x = 0u128;
+	let mut f = x.bits_mut(..);
+	f += u128::MAX;
+	f += 2;
+	assert_eq!(x, 1);
+
+	let mut x = -1i32;
+	x.bits_mut(28..).set(0x7);
+	assert_eq!(x, 0x7FFF_FFFF);
+
+	let mut x = u12::new(0);
+	x.bits_mut(8..12).set(0xA);
+	assert_eq!(x.get(), 0xA00);
+
+	let mut x = Wrapping(0u16);
+	let mut f = x.bits_mut(4..4);
+	f += 1;
+	f |= 0;
+	assert_eq!(x, Wrapping(0));
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_bits_mut_panic() {
+	let mut x = 0u8;
+	let mut f = x.bits_mut(0..4);
+	f |= 0x10;
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_bits_of() {
+	use crate::{u7, BitsOf};
+
+	let table = [BitsOf(0x81u8), BitsOf(0x7E)];
+	assert!(table[0][0] && table[0][7] && !table[1][0] && table[1][6]);
+	let r: &bool = &BitsOf(-1i64)[63];
+	assert!(*r);
+	assert!(BitsOf(u7::MAX)[6]);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit index")]
+fn test_bits_of_panic() {
+	let _ = crate::BitsOf(0u16)[16];
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_bit_ref() {
+	use crate::WideInt;
+
+	let mut x = 0i8;
+	let mut b = x.bit_mut(7);
+	assert!(!b.get());
+	b.toggle();
+	assert!(b.get());
+	assert!(b.replace(false));
+	b.toggle();
+	assert_eq!(x, i8::MIN);
+
+	let mut x = WideInt::<3>::ZERO;
+	x.bit_mut(190).set(true);
+	assert_eq!(x.words(), &[0, 0, 1 << 62]);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit index")]
+fn test_bit_ref_panic() {
+	0u32.bit_mut(32);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_bits_of_iter() {
+	use crate::{u7, BitsOf};
+
+	let mut it = BitsOf(0x81u8).into_iter();
+	assert_eq!(it.len(), 8);
+	assert_eq!(it.next(), Some(true));
+	assert_eq!(it.next_back(), Some(true));
+	assert_eq!(it.len(), 6);
+	assert!(it.all(|b| !b));
+
+	assert_eq!(BitsOf(u7::MAX).into_iter().filter(|&b| b).count(), 7);
+	assert_eq!(BitsOf(-1i128).into_iter().count(), 128);
+
+	let same = BitsOf(0xA5u8)
+		.into_iter()
+		.zip(BitsOf(0xA5u16))
+		.all(|(a, b)| a == b);
+	assert!(same);
+
+	let last = BitsOf(0x4000u16).enumerate_bits().rev().find(|&(_, b)| b);
+	assert_eq!(last, Some((14, true)));
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_index_types() {
+	let x = 0xABCD_1234u32;
+	assert_eq!(x.bits(4i8..12), x.bits(4u32..12));
+	assert_eq!(x.bits(4u128..=11), x.bits(4u32..12));
+	assert_eq!(x.bits(0isize..=-1), 0);
+	assert_eq!(x.bits((Bound::Excluded(-1i16), Bound::Unbounded)), x);
+	assert!(x.bit(2usize) && !x.bit(0i128));
+	assert_eq!(0u64.with_bits(60u8..64, 0xF), 0xF << 60);
+	assert_eq!(0i16.with_bit(15i32, true), i16::MIN);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_index_types_panic() {
+	0u8.bits(0u64..(1 << 40));
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_const_bits() {
+	use crate::ConstBits;
+
+	assert_eq!((-1i8).const_bits::<0, 8>(), 0xFF);
+	assert_eq!((-1i8).const_bits::<8, 0>(), 0);
+	assert_eq!(0xABCDu16.const_bits::<4, 8>(), 0xBC);
+	assert_eq!(0u128.with_const_bits::<120, 8>(0xFF), 0xFF << 120);
+	let mut x = 0usize;
+	x.set_const_bits::<1, 2>(3);
+	assert_eq!(x, 6);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_const_bits_panic() {
+	use crate::ConstBits;
+	0u8.with_const_bits::<4, 4>(0x10);
+}
+
+#[test]
+#[cfg(feature = "impl-128")]
+fn test_edge_ranges() {
+	for n in 0..=8u32 {
+		assert_eq!(0xFFu8.bits(n..n), 0);
+		assert_eq!(0xFFu8.bits(n..), (0xFFu16 >> n) as u8);
+		assert_eq!(0xFFu8.bits(..n), (0xFFu16 >> (8 - n)) as u8);
+		assert_eq!(0xA5u8.with_bits(n..n, 0), 0xA5);
+	}
+	assert_eq!(u128::MAX.bits(0..128), u128::MAX);
+	assert_eq!(u128::MAX.bits(127..128), 1);
+	assert_eq!(0u128.with_bits(1..128, u128::MAX >> 1), !1);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_bits_widened() {
+	use crate::u12;
+
+	assert_eq!((-1i8).bits_widened::<u128, _, _>(0..8), 0xFF);
+	assert_eq!(u12::MAX.bits_widened::<u32, _, _>(4..12), 0xFF);
+	assert_eq!(0xABCDu16.bits_widened::<u16, _, _>(8..), 0xAB);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_impl_bits() {
+	#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+	struct Reg(i16);
+
+	crate::impl_bits!(Reg => i16);
+
+	let mut r = Reg(0);
+	r.set_bits(4u8..8, 0xF);
+	r.set_bit(15usize, true);
+	assert_eq!(r, Reg(-0x7F10));
+	assert_eq!(r.bits(4i64..=7), 0xF);
+	assert_eq!(Reg(0x1234).bits_be(8..16), 0x34);
+	assert_eq!(Reg(0x0F00).bits_q(8..12, 0), -1.0);
+	assert_eq!(Reg(0x0F00).bits_uq(8..12, 0), 15.0);
+	assert_eq!(Reg(0x0B00).bits_sign_magnitude(8..12), -3);
+	assert_eq!(Reg(0x0B00).with_bits(8.., 1), Reg(0x0100));
+}
+
+#[test]
+#[cfg(feature = "svd2rust")]
+fn test_register_bits() {
+	use crate::RegisterBits;
+
+	struct Reg(u16);
+
+	impl RegisterBits for Reg {
+		type Raw = u16;
+
+		fn raw_bits(&self) -> u16 {
+			self.0
+		}
+
+		unsafe fn set_raw_bits(&mut self, raw: u16) {
+			self.0 = raw;
+		}
+	}
+
+	let mut r = Reg(0x8001);
+	assert!(r.read_bit(15));
+	assert_eq!(r.read_bits(0..4), 1);
+	unsafe { r.modify_bits(4..12, 0xAB).modify_bit(15, false) };
+	assert_eq!(r.0, 0x0AB1);
+}
+
+#[test]
+#[cfg(all(feature = "ethnum", feature = "impl-128"))]
+fn test_ethnum() {
+	use ethnum::{I256, U256};
+
+	let mut a = U256::ZERO;
+	a.set_bits(120..136, 0xABCD);
+	assert_eq!(a.into_words(), (0xAB, 0xCD << 120));
+	assert_eq!(a.bits(124..132), 0xBC);
+	assert!(a.bit(135u8));
+	assert!(!a.bit(255));
+	assert_eq!(a.with_bit(255, true).into_words().0, 0xAB | 1 << 127);
+	assert_eq!(U256::MAX.bits(64..192), !0);
+	assert_eq!(U256::MAX.with_bits(100..=200, 0).leading_ones(), 55);
+	assert_eq!(U256::from_words(1, 2).bits_be(120..136), 0x0001);
+
+	let mut b = I256::MINUS_ONE;
+	b.set_bits(100..200, 0);
+	assert!(b < I256::ZERO);
+	assert_eq!(b.bits(96..104), 0x0F);
+	assert_eq!(b.bits(200..), !0 >> 72);
+	assert_eq!(I256::new(-2).bits(0..128), !1);
+	assert_eq!(I256::MIN.with_bit(0, true), I256::MIN + 1);
+}
+
+#[test]
+#[cfg(all(feature = "fixed", feature = "impl-signed"))]
+fn test_fixed() {
+	use crate::FixedBits;
+	use fixed::types::{I8F8, U0F16, U16F0};
+
+	let mut x = I8F8::from_num(-1.5);
+	assert_eq!(x.to_bits(), -0x180);
+	assert_eq!(x.int_bits(), 0xFE);
+	assert_eq!(x.frac_bits(), 0x80);
+	assert!(x.bit(15));
+	x.set_frac_bits(0x40);
+	assert_eq!(x, -1.75);
+	x.set_bit(15, false);
+	assert_eq!(x, 126.25);
+	assert_eq!(x.bits_q(0..16, 8), 126.25);
+	assert_eq!(x.bits_be(0..8), 0x7E);
+
+	assert_eq!(U0F16::int_range(), 16..16);
+	assert_eq!(U0F16::MAX.int_bits(), 0);
+	assert_eq!(U16F0::frac_range(), 0..0);
+	assert_eq!(U16F0::from_num(7).with_bits(8.., 1), 0x107);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_lanes() {
+	use crate::{u12, WideInt};
+
+	let rgb565 = 0xF81Fu16;
+	assert_eq!(rgb565.to_lanes::<1, 16>()[..6], [1, 1, 1, 1, 1, 0]);
+	assert_eq!(u64::MAX.to_lanes::<64, 1>(), [u64::MAX]);
+	assert_eq!((-2i8).to_lanes::<2, 4>(), [0b10, 0b11, 0b11, 0b11]);
+	assert_eq!(i8::from_lanes::<2, 4>([0b10, 0b11, 0b11, 0b11]), -2);
+	assert_eq!(u12::new(0xABC).to_lanes::<4, 3>(), [0xC, 0xB, 0xA]);
+	assert_eq!(u12::from_lanes::<6, 2>([0x3F, 0]), u12::new(0x3F));
+
+	let wide = WideInt::<2>::from_words([1, 2]);
+	assert_eq!(wide.to_lanes::<32, 4>(), [1, 0, 2, 0]);
+	assert_eq!(WideInt::<2>::from_lanes::<64, 2>([1, 2]), wide);
+
+	let values: [u32; 5] = core::array::from_fn(|i| 0x9E37_79B9u32.wrapping_mul(i as u32 + 1));
+	for &v in &values {
+		assert_eq!(u32::from_lanes::<8, 4>(v.to_lanes::<8, 4>()), v);
+		assert_eq!(u32::from_lanes::<16, 2>(v.to_lanes::<16, 2>()), v);
+	}
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_lanes_too_wide() {
+	u16::from_lanes::<8, 2>([0x100, 0]);
+}
+
+#[test]
+fn test_color() {
+	use crate::color::{Rgb10a2, Rgb565, Rgba5551};
+
+	for v in 0..=255 {
+		let p = Rgb565::from_rgb8([v, v, v]);
+		assert_eq!(Rgb565::from_rgb8(p.to_rgb8()), p);
+		let [r, g, b] = p.to_rgb8();
+		assert!(r.abs_diff(v) <= 4 && g.abs_diff(v) <= 2 && b.abs_diff(v) <= 4);
+		let p = Rgb10a2::from_rgba8([v, 0, 0, v]);
+		assert_eq!(p.to_rgba8()[0], v);
+		assert_eq!(Rgba5551::from_rgba8([0, 0, 0, v]).a(), (v >= 128) as u16);
+	}
+	for v in 0..32 {
+		let mut p = Rgba5551::default();
+		p.set_g(v);
+		assert_eq!(Rgba5551::from_rgba8(p.to_rgba8()), p);
+	}
+	assert_eq!(Rgb565::from_rgb8([0xFF, 0, 0]), Rgb565(0xF800));
+	assert_eq!(Rgba5551::from_rgba8([0, 0, 0xFF, 0xFF]), Rgba5551(0x003F));
+	assert_eq!(Rgb10a2::from_rgba8([0xFF; 4]), Rgb10a2(!0));
+	assert_eq!(
+		Rgb10a2::from_rgba8([0, 0x80, 0, 0x55]),
+		Rgb10a2(1 << 30 | 514 << 10)
+	);
+	assert_eq!(Rgb10a2(0x4000_0000).to_rgba8(), [0, 0, 0, 0x55]);
+	let mut p = Rgb10a2(0);
+	p.set_b(0x3FF);
+	assert_eq!(p.b(), 0x3FF);
+	assert_eq!(p, Rgb10a2(0x3FF0_0000));
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_ct() {
+	use crate::ct::CtBits;
+
+	for i in 0..16 {
+		assert_eq!(0x8421u16.ct_bit(i), if 0x8421u16.bit(i) { !0 } else { 0 });
+	}
+	assert_eq!((-1i128).ct_bit(127), !0);
+	assert_eq!(i8::MIN.ct_bit(7), 0xFF);
+	assert_eq!(0u64.ct_eq_bits(.., 0), !0);
+	assert_eq!(0u64.ct_eq_bits(.., 1 << 63), 0);
+	assert_eq!(0u64.ct_eq_bits(..63, 1 << 63), !0);
+	assert_eq!(0xABu8.ct_eq_bits(8..8, 0), 0xFF);
+	assert_eq!(0xABu8.ct_eq_bits(4..8, 0xA0), 0xFF);
+	assert_eq!((-1i32).ct_eq_bits(0..1, 1), !0);
+	assert_eq!(i16::ct_select(!0, -5, 7), -5);
+	assert_eq!(i16::ct_select(0, -5, 7), 7);
+	assert_eq!(u8::ct_select(0xF0, 0xAB, 0xCD), 0xAD);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit index")]
+fn test_ct_bit_out_of_range() {
+	use crate::ct::CtBits;
+
+	0u32.ct_bit(32);
+}
+
+#[test]
+#[cfg(all(feature = "rand", feature = "impl-128", feature = "impl-signed"))]
+fn test_random() {
+	use crate::{random_with_pattern, ExactOnes};
+	use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+	let mut rng = SmallRng::seed_from_u64(7);
+	let (mut zeros, mut ones) = (0u16, 0u16);
+	for _ in 0..100 {
+		let v: i16 = random_with_pattern(&mut rng, 0x8001, 0x8000);
+		assert_eq!(v.bits(0..1), 0);
+		assert!(v < 0);
+		zeros |= !v as u16;
+		ones |= v as u16;
+	}
+	assert_eq!((zeros, ones), (0x7FFF, 0xFFFE));
+	assert_eq!(random_with_pattern::<u8, _>(&mut rng, !0, 0x5A), 0x5A);
+
+	let mut seen = 0u8;
+	for k in 0..=8 {
+		for _ in 0..50 {
+			let v: u8 = rng.sample(ExactOnes::new(k));
+			assert_eq!(v.count_ones(), k);
+			seen |= v;
+		}
+	}
+	assert_eq!(seen, 0xFF);
+	let v: u128 = rng.sample(ExactOnes::new(128));
+	assert_eq!(v, !0);
+	let v: i32 = rng.sample(ExactOnes::new(1));
+	assert_eq!(v.count_ones(), 1);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+#[should_panic(expected = "bits outside range")]
+fn test_random_with_pattern_outside_mask() {
+	let mut rng = <rand::rngs::SmallRng as rand::SeedableRng>::seed_from_u64(0);
+	crate::random_with_pattern::<u8, _>(&mut rng, 0x0F, 0x10);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_bits_multi() {
+	let mut a = -1i16;
+	a.set_bits_multi(&[(0..4, 0), (8..9, 0), (12..16, 0x7)]);
+	assert_eq!(a, 0x7EF0);
+	assert_eq!(
+		0u8.with_bits_multi([(0..0, 0), (1..1, 0), (0..8, 0xAB)]),
+		0xAB
+	);
+	assert_eq!(0xFFu8.with_bits_multi::<core::ops::Range<u32>, 0>([]), 0xFF);
+}
+
+#[test]
+#[should_panic(expected = "overlapping bit ranges")]
+fn test_bits_multi_overlap() {
+	0u32.with_bits_multi([(0..8, 1), (4..12, 1)]);
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_mask_predicates() {
+	assert!(0u32.contains_all_bits(0));
+	assert!(!0u32.contains_any_bits(0));
+	assert!(0u32.disjoint_bits(0));
+	assert!((-1i64).contains_all_bits(u64::MAX));
+	assert!(!0x8000u16.contains_all_bits(0xC000));
+	assert!(0x8000u16.contains_any_bits(0xC000));
+	assert!(!0x8000u16.disjoint_bits(0xC000));
+	assert!(0x8000u16.disjoint_bits(0x7FFF));
+}
+
+#[test]
+fn test_merge_bits() {
+	use crate::merge_bits;
+
+	assert_eq!(merge_bits(0u8, 0xFF, 0), 0);
+	assert_eq!(merge_bits(0u8, 0xFF, 0xFF), 0xFF);
+	assert_eq!(merge_bits(0xF0u8, 0x0F, 0x3C), 0xCC);
+	assert_eq!(merge_bits(i128::MIN, 1, 1), i128::MIN + 1);
+	for mask in 0..=255u8 {
+		assert_eq!(merge_bits(0x5Au8, 0xC3, mask), 0x5A & !mask | 0xC3 & mask);
+	}
+}
+
+#[test]
+fn test_byte_span() {
+	use crate::{aligned_byte_range, bit_range_to_bytes, bytes_to_bit_range};
+
+	assert_eq!(bit_range_to_bytes(0..0), (0..0, 0, 0));
+	assert_eq!(bit_range_to_bytes(13..13), (1..1, 0, 0));
+	assert_eq!(bit_range_to_bytes(0..8), (0..1, 0, 0));
+	assert_eq!(bit_range_to_bytes(3..5), (0..1, 3, 3));
+	assert_eq!(bit_range_to_bytes(7..9), (0..2, 7, 7));
+	for start in 0..40 {
+		for end in start + 1..48 {
+			let (bytes, head, tail) = bit_range_to_bytes(start..end);
+			assert_eq!(bytes_to_bit_range(bytes, head, tail), start..end);
+		}
+	}
+	assert_eq!(aligned_byte_range(8..8), Some(1..1));
+	assert_eq!(aligned_byte_range(1..8), None);
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_byte_span_invalid() {
+	crate::bytes_to_bit_range(1..2, 5, 4);
+}
+
+#[test]
+fn test_aligned_segments() {
+	use crate::{aligned_segments, Segment};
+	use std::vec::Vec;
+
+	let all = |r, n| aligned_segments(r, n).collect::<Vec<_>>();
+	assert_eq!(all(0..0, 8), []);
+	assert_eq!(all(0..64, 32), [Segment::Full(0..2)]);
+	assert_eq!(all(3..5, 8), [Segment::Partial(0, 3..5)]);
+	assert_eq!(all(3..8, 8), [Segment::Partial(0, 3..8)]);
+	assert_eq!(all(8..13, 8), [Segment::Partial(1, 0..5)]);
+	assert_eq!(
+		all(6..10, 8),
+		[Segment::Partial(0, 6..8), Segment::Partial(1, 0..2)]
+	);
+	assert_eq!(
+		all(0..10, 3),
+		[Segment::Full(0..3), Segment::Partial(3, 0..1)]
+	);
+	for start in 0..30 {
+		for end in start..40 {
+			let n: usize = aligned_segments(start..end, 8)
+				.map(|s| match s {
+					Segment::Partial(_, r) => r.len(),
+					Segment::Full(r) => r.len() * 8,
+				})
+				.sum();
+			assert_eq!(n, end - start);
+		}
+	}
+}
+
+#[test]
+#[allow(clippy::single_range_in_vec_init)]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_validate_partition() {
+	use crate::{validate_partition, PartitionError};
+
+	assert_eq!(validate_partition::<u8>(&[0..8]), Ok(()));
+	assert_eq!(validate_partition::<u8>(&[4..4, 4..8, 0..4, 0..0]), Ok(()));
+	assert_eq!(
+		validate_partition::<u8>(&[]),
+		Err(PartitionError::Gap { start: 0, end: 8 })
+	);
+	assert_eq!(
+		validate_partition::<u8>(&[1..8]),
+		Err(PartitionError::Gap { start: 0, end: 1 })
+	);
+	assert_eq!(
+		validate_partition::<u8>(&[0..2, 6..7, 4..6]),
+		Err(PartitionError::Gap { start: 2, end: 4 })
+	);
+	assert_eq!(
+		validate_partition::<u8>(&[0..7]),
+		Err(PartitionError::Gap { start: 7, end: 8 })
+	);
+	assert_eq!(
+		validate_partition::<i128>(&[0..64, 64..129]),
+		Err(PartitionError::OutOfRange { field: 1 })
+	);
+	assert_eq!(
+		validate_partition::<u8>(&[0..8, 3..3, 2..5]),
+		Err(PartitionError::Overlap {
+			first: 0,
+			second: 2
+		})
+	);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_bit_indices() {
+	use crate::{u12, WideInt};
+
+	assert_eq!(u12::MAX_BIT, 11);
+	assert_eq!(u12::BIT_INDICES, 0..12);
+	assert_eq!(WideInt::<3>::MAX_BIT, 191);
+	assert_eq!(core::num::Wrapping::<i8>::BIT_INDICES, 0..8);
+	assert_eq!(u128::BIT_INDICES.len(), u128::N_BITS as usize);
+	assert_eq!(i64::BIT_INDICES.last(), Some(i64::MAX_BIT));
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_bit_len() {
+	use crate::WideInt;
+
+	for i in 0..128 {
+		assert_eq!((1u128 << i).bit_len(), i + 1);
+		assert_eq!((!0u128 >> i).bit_len(), 128 - i);
+	}
+	for v in 0..=u16::MAX {
+		assert_eq!(v.bit_len(), 16 - v.leading_zeros());
+	}
+	assert_eq!(i128::MIN.bit_len(), 128);
+	assert_eq!(usize::MAX.bit_len(), usize::BITS);
+	assert_eq!(0xFFu8.bit_len_in(8..), 0);
+	assert_eq!((-1i64).bit_len_in(60..), 4);
+	assert_eq!(WideInt::<4>::from(1u128).bit_len(), 1);
+	assert_eq!(WideInt::<4>::from(1u128 << 127).bit_len(), 128);
+	assert_eq!(WideInt::<4>::ZERO.with_bit(200, true).bit_len(), 201);
+	assert_eq!(WideInt::<4>::MAX.bit_len(), 256);
+	assert_eq!(WideInt::<3>::ZERO.bit_len(), 0);
+}
+
+#[test]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_field_spec() {
+	use crate::FieldSpec;
+
+	let f = FieldSpec::<i8>::new(4..);
+	assert_eq!((f.mask(), f.shift(), f.len()), (0xF0, 4, 4));
+	assert_eq!(f.get(-0x10), 0xF);
+	assert_eq!(f.with(0x0F, 0x8), -0x71);
+	let empty = FieldSpec::<u64>::new(64..);
+	assert!(empty.is_empty());
+	assert_eq!(empty.get(u64::MAX), 0);
+	assert_eq!(empty.with(1, 0), 1);
+	let all = FieldSpec::<u128>::new(..);
+	assert_eq!(all.len(), 128);
+	let mut v = 5u128;
+	assert_eq!(all.replace(&mut v, u128::MAX), 5);
+	assert_eq!(v, u128::MAX);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_field_spec_too_wide() {
+	crate::FieldSpec::<u32>::new(0..4).with(0, 0x10);
+}
+
+#[test]
+fn test_bit_queue() {
+	use crate::{BitQueue, BitStack, OutOfBits};
+
+	let mut q = BitQueue::<u8>::new();
+	assert_eq!(q.push_bits(0xAB, 8), Ok(()));
+	assert_eq!(q.push_bits(0, 1), Err(OutOfBits));
+	assert_eq!(q.push_bits(0, 0), Ok(()));
+	assert_eq!(q.peek_bits(4), Ok(0xB));
+	assert_eq!(q.pop_bits(8), Ok(0xAB));
+	assert_eq!(q.pop_bits(0), Ok(0));
+	q.push_bits(1, 1).unwrap();
+	assert_eq!(q.pop_bits(2), Err(OutOfBits));
+	assert_eq!(q.len_bits(), 1);
+	q.clear();
+	assert!(q.is_empty());
+
+	let mut s = BitStack::<u128>::new();
+	s.push_bits(u128::MAX, 128).unwrap();
+	assert_eq!(s.push_bits(1, 1), Err(OutOfBits));
+	assert_eq!(s.pop_bits(120), Ok(u128::MAX >> 8));
+	s.push_bits(0, 3).unwrap();
+	assert_eq!(s.pop_bits(4), Ok(0b1000));
+	assert_eq!(s.capacity_bits(), 128);
+}
+
+#[test]
+#[should_panic(expected = "bits outside range")]
+fn test_bit_queue_too_wide() {
+	crate::BitQueue::<u16>::new().push_bits(4, 2).ok();
+}
+
+#[test]
+#[cfg(feature = "impl-signed")]
+fn test_field_key() {
+	use crate::FieldKey;
+	use std::collections::HashSet;
+
+	let sign = FieldKey::<i16, 15, 1>;
+	assert_eq!(sign(-1), sign(-2));
+	assert_ne!(sign(-1), sign(1));
+	assert!(sign(1) < sign(-1));
+	assert_eq!(sign(i16::MIN).key(), 1);
+	let set: HashSet<_> = (-5..5).map(sign).collect();
+	assert_eq!(set.len(), 2);
+	assert_eq!(FieldKey::<u8, 0, 0>(1), FieldKey(2));
+}
+
+#[test]
+fn test_bit_histogram() {
+	use crate::batch::bit_histogram;
+	use std::vec::Vec;
+
+	assert_eq!(bit_histogram(&[]), [0; 64]);
+	assert_eq!(bit_histogram(&[u64::MAX; 1000]), [1000; 64]);
+	let words: Vec<u64> = (0..2000u64)
+		.map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+		.collect();
+	let mut expected = [0; 64];
+	for w in &words {
+		for (i, e) in expected.iter_mut().enumerate() {
+			*e += (w >> i & 1) as u32;
+		}
+	}
+	assert_eq!(bit_histogram(&words), expected);
+}
+
+#[test]
+fn test_transpose() {
+	use crate::batch::{from_bit_sliced, to_bit_sliced, transpose_64};
+
+	let mut block = [0; 64];
+	for (i, w) in block.iter_mut().enumerate() {
+		*w = (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+	}
+	let original = block;
+	transpose_64(&mut block);
+	for (i, row) in original.iter().enumerate() {
+		for (j, column) in block.iter().enumerate() {
+			assert_eq!(column >> i & 1, row >> j & 1);
+		}
+	}
+	transpose_64(&mut block);
+	assert_eq!(block, original);
+
+	let mut sliced = [u64::MAX; 64];
+	to_bit_sliced(&original[..10], &mut sliced);
+	assert!(sliced.iter().all(|w| w >> 10 == 0));
+	let mut words = [0; 10];
+	from_bit_sliced(&sliced, &mut words);
+	assert_eq!(words, original[..10]);
+}
+
+#[test]
+fn test_prbs() {
+	use crate::{BitOrder, BitReader, BitWriter, PrbsChecker, PrbsGenerator, PrbsPattern};
+
+	for pattern in [PrbsPattern::Prbs7, PrbsPattern::Prbs15] {
+		let period = (1 << pattern.order()) - 1;
+		let mut prbs = PrbsGenerator::new(pattern);
+		let start = prbs.state();
+		for i in 1..=period {
+			prbs.next_bit();
+			assert_eq!(prbs.state() == start, i == period);
+		}
+	}
+
+	let mut buf = [0; 64];
+	let mut prbs = PrbsGenerator::with_seed(PrbsPattern::Prbs31, 12345);
+	prbs.write_to(&mut BitWriter::new(&mut buf, BitOrder::LsbFirst), 512)
+		.unwrap();
+	buf[40] ^= 0x11;
+	let mut checker = PrbsChecker::new(PrbsPattern::Prbs31);
+	checker.check_from(&mut BitReader::new(&buf, BitOrder::LsbFirst));
+	assert_eq!(
+		(checker.bits_checked(), checker.bit_errors()),
+		(512 - 31, 2)
+	);
+	checker.reset();
+	assert!(!checker.is_locked());
+	assert_eq!(
+		prbs.write_to(&mut BitWriter::new(&mut buf, BitOrder::LsbFirst), 513),
+		Err(crate::OutOfBits)
+	);
+}
+
+#[test]
+#[should_panic(expected = "invalid PRBS seed")]
+fn test_prbs_zero_seed() {
+	crate::PrbsGenerator::with_seed(crate::PrbsPattern::Prbs7, 0);
+}
+
+#[test]
+#[allow(clippy::unusual_byte_groupings)]
+fn test_8b10b() {
+	use crate::enc8b10b::{CodeError, Decoder, Encoder, Symbol};
+
+	// D.21.5 is the same for both disparities, and K.28.7 is neutral.
+	let mut e = Encoder::new();
+	assert_eq!(e.encode(Symbol::Data(0xB5)), 0b101010_1010);
+	assert_eq!(e.encode(Symbol::Control(0xFC)), 0b001111_1000);
+	// D.17.7 uses the alternate encoding with a negative running disparity.
+	assert_eq!(e.encode(Symbol::Data(0xF1)), 0b100011_0111);
+
+	let mut symbols = std::vec::Vec::new();
+	for b in 0..=255 {
+		symbols.push(Symbol::Data(b));
+		symbols.push(Symbol::Data(b));
+		if Symbol::Control(b).is_valid() {
+			symbols.push(Symbol::Control(b));
+		}
+	}
+	assert_eq!(symbols.len(), 512 + 12);
+	let mut e = Encoder::new();
+	let mut d = Decoder::new();
+	let (mut disparity, mut run, mut last) = (-1i32, 0, false);
+	for &s in &symbols {
+		let code = e.encode(s);
+		assert_eq!(d.decode(code), Ok(s));
+		assert_eq!(d.running_disparity(), e.running_disparity());
+		for i in (0..10).rev() {
+			let bit = code >> i & 1 != 0;
+			disparity += if bit { 1 } else { -1 };
+			run = if bit == last { run + 1 } else { 1 };
+			last = bit;
+			assert!(run <= 5);
+			assert!((-3..=3).contains(&disparity));
+		}
+	}
+
+	assert_eq!(Decoder::new().decode(0), Err(CodeError::InvalidCode));
+	assert_eq!(
+		Decoder::new().decode(0b111111_0000),
+		Err(CodeError::InvalidCode)
+	);
+}
+
+#[test]
+#[should_panic(expected = "invalid control symbol")]
+fn test_8b10b_invalid_control() {
+	crate::enc8b10b::Encoder::new().encode(crate::enc8b10b::Symbol::Control(0));
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_normalize() {
+	use crate::{normalize, normalize_range};
+	use core::ops::Bound::{Excluded, Included, Unbounded};
+
+	assert_eq!(normalize_range((Excluded(3), Included(5)), 8), 4..6);
+	assert_eq!(normalize_range((Excluded(7), Unbounded), 8), 8..8);
+	assert_eq!(normalize_range(6..2, 8), 6..6);
+	assert_eq!(normalize::<u128, _>(..), (u128::MAX, 0));
+	assert_eq!(
+		normalize::<isize, _>(usize::BITS - 1..),
+		(1 << (usize::BITS - 1), usize::BITS - 1)
+	);
+	for start in 0..=16 {
+		for end in start..=16 {
+			let (mask, shift) = normalize::<u16, _>(start..end);
+			assert_eq!(
+				0xA5C3u16.bits(start..end),
+				(0xA5C3 & mask).checked_shr(shift).unwrap_or(0)
+			);
+		}
+	}
+}
+
+#[test]
+#[should_panic(expected = "invalid bit range")]
+fn test_normalize_invalid() {
+	crate::normalize_range(0..9, 8);
+}
+
+#[test]
+fn test_to_range() {
+	use crate::ToRange;
+
+	let starts = [0u8, 8, 16];
+	let ends = [4u8, 12, 20];
+	let fields: std::vec::Vec<_> = starts
+		.iter()
+		.zip(&ends)
+		.map(|(&s, &e)| (s, e).to_range())
+		.collect();
+	assert_eq!(fields[1], 8..12);
+	assert_eq!(0x0F_F0_0Fu32.bits(fields[2].clone()), 0xF);
+	assert_eq!([3i64, 5].to_range(), 3..5);
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+fn test_inverted_ranges() {
+	use crate::{
+		aligned_byte_range, aligned_segments, bit_range_to_bytes, ArrayBits, BitOrder, SliceBits,
+		WideInt,
+	};
+
+	assert_eq!(0xFFu8.bits(6..2), 0);
+	assert_eq!(0xFFu8.bits(8..0), 0);
+	assert_eq!((-1i64).bits(40i8..=20), 0);
+	assert_eq!(0xA5u16.with_bits(9usize..3, 0), 0xA5);
+	assert_eq!(crate::u12::MAX.bits(10..4), 0);
+	assert_eq!(WideInt::<4>::MAX.bits(200..100), 0);
+	assert_eq!(
+		SliceBits::bits(&[0xFFu8; 4][..], 30..2, BitOrder::MsbFirst),
+		0
+	);
+	assert_eq!([u64::MAX; 2].bits(100..50), 0);
+	assert_eq!(bit_range_to_bytes(20..10), (2..2, 0, 0));
+	assert_eq!(aligned_byte_range(16..8), Some(2..2));
+	assert_eq!(aligned_segments(20..10, 8).count(), 0);
+}
@@ -0,0 +1,72 @@
+use super::range::u32_bounds;
+use super::{ArrayBits, Bits, BitsIndex};
+use core::convert::TryFrom;
+use core::ops::RangeBounds;
+use ethnum::{I256, U256};
+
+// Both types are stored as two 128-bit words, which are accessed through
+// `ArrayBits` as `[lo, hi]`, so ranges can cross the word boundary.
+
+#[inline]
+fn u256_words(v: U256) -> [u128; 2] {
+	let (hi, lo) = v.into_words();
+	[lo, hi]
+}
+
+#[inline]
+fn i256_words(v: I256) -> [u128; 2] {
+	let (hi, lo) = v.into_words();
+	[lo as u128, hi as u128]
+}
+
+forward_bits!([] U256, u128, 256, |v| v.swap_bytes(), U256::MAX);
+forward_bits!([] I256, u128, 256, |v| v.swap_bytes(), I256::MINUS_ONE);
+
+macro_rules! u256_index {
+	($t:ident, $words:ident, |$hi:ident, $lo:ident| $from:expr; $($i:ident),*) => {$(
+		impl BitsIndex<$t> for $i {
+			#[inline]
+			fn bit(v: $t, i: Self) -> bool {
+				$words(v).bit(u32::try_from(i).unwrap_or(u32::MAX))
+			}
+
+			#[inline]
+			fn bits<R>(v: $t, range: R) -> u128
+			where
+				R: RangeBounds<Self>,
+			{
+				$words(v).bits(u32_bounds(&range))
+			}
+
+			#[inline]
+			fn set_bit(v: &mut $t, i: Self, bit: bool) {
+				let [$lo, $hi] = $words(*v).with_bit(u32::try_from(i).unwrap_or(u32::MAX), bit);
+				*v = $from;
+			}
+
+			#[inline]
+			fn set_bits<R>(v: &mut $t, range: R, bits: u128)
+			where
+				R: RangeBounds<Self>,
+			{
+				let [$lo, $hi] = $words(*v).with_bits(u32_bounds(&range), bits);
+				*v = $from;
+			}
+		}
+	)*};
+}
+
+u256_index!(U256, u256_words, |hi, lo| U256::from_words(hi, lo); u8, u16, u32, u64, usize, i32);
+#[cfg(feature = "impl-signed")]
+u256_index!(U256, u256_words, |hi, lo| U256::from_words(hi, lo); i8, i16, i64, isize);
+#[cfg(feature = "impl-128")]
+u256_index!(U256, u256_words, |hi, lo| U256::from_words(hi, lo); u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+u256_index!(U256, u256_words, |hi, lo| U256::from_words(hi, lo); i128);
+u256_index!(I256, i256_words, |hi, lo| I256::from_words(hi as i128, lo as i128); u8, u16, u32, u64, usize, i32);
+#[cfg(feature = "impl-signed")]
+u256_index!(I256, i256_words, |hi, lo| I256::from_words(hi as i128, lo as i128); i8, i16, i64, isize);
+#[cfg(feature = "impl-128")]
+u256_index!(I256, i256_words, |hi, lo| I256::from_words(hi as i128, lo as i128); u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+u256_index!(I256, i256_words, |hi, lo| I256::from_words(hi as i128, lo as i128); i128);
@@ -0,0 +1,134 @@
+use super::{Bits, BitsIndex};
+use core::iter::{FusedIterator, Zip};
+use core::ops::{Index, Range};
+
+/// A read-only view of the bits of an integer, supporting indexing.
+///
+/// `view[i]` is the same as [`bit(i)`][Bits::bit], but gives a `&bool`.
+///
+/// Iterating over the view gives all bits, starting at the least significant
+/// bit.
+///
+/// # Example
+///
+/// ```
+/// use intbits::BitsOf;
+///
+/// let view = BitsOf(0b0100u8);
+/// assert!(view[2]);
+/// assert!(!view[3]);
+///
+/// let bits: Vec<bool> = BitsOf(0b0110u8).into_iter().take(4).collect();
+/// assert_eq!(bits, [false, true, true, false]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BitsOf<T>(pub T);
+
+impl<T> Index<u32> for BitsOf<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	type Output = bool;
+
+	/// Panics if the index is out of range.
+	#[inline]
+	fn index(&self, i: u32) -> &bool {
+		if self.0.bit(i) {
+			&true
+		} else {
+			&false
+		}
+	}
+}
+
+impl<T> BitsOf<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	/// Iterate over all bits together with their index.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::BitsOf;
+	///
+	/// let mut set = BitsOf(0x12u8).enumerate_bits().filter(|&(_, b)| b);
+	/// assert_eq!(set.next(), Some((1, true)));
+	/// assert_eq!(set.next(), Some((4, true)));
+	/// assert_eq!(set.next(), None);
+	/// ```
+	#[inline]
+	pub fn enumerate_bits(self) -> Zip<Range<u32>, BitsOfIter<T>> {
+		T::BIT_INDICES.zip(self)
+	}
+}
+
+impl<T> IntoIterator for BitsOf<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	type Item = bool;
+	type IntoIter = BitsOfIter<T>;
+
+	#[inline]
+	fn into_iter(self) -> BitsOfIter<T> {
+		BitsOfIter {
+			value: self.0,
+			range: T::BIT_INDICES,
+		}
+	}
+}
+
+/// An iterator over the bits of a [`BitsOf`], starting at the least
+/// significant bit.
+#[derive(Clone, Debug)]
+pub struct BitsOfIter<T> {
+	value: T,
+	range: Range<u32>,
+}
+
+impl<T> Iterator for BitsOfIter<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	type Item = bool;
+
+	#[inline]
+	fn next(&mut self) -> Option<bool> {
+		self.range.next().map(|i| self.value.bit(i))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.range.size_hint()
+	}
+}
+
+impl<T> DoubleEndedIterator for BitsOfIter<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<bool> {
+		self.range.next_back().map(|i| self.value.bit(i))
+	}
+}
+
+impl<T> ExactSizeIterator for BitsOfIter<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+}
+
+impl<T> FusedIterator for BitsOfIter<T>
+where
+	T: Bits,
+	u32: BitsIndex<T>,
+{
+}
@@ -0,0 +1,252 @@
+use super::range::u32_bounds;
+use super::{ArrayBits, Bits, BitsIndex};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{BitAnd, BitOr, BitXor, Not, RangeBounds, Shl, Shr};
+
+/// An unsigned integer of `64 * WORDS` bits.
+///
+/// This is meant for values wider than 128 bits, like wide hardware
+/// registers. It implements [`Bits`], with ranges that can cross word
+/// boundaries. Since [`bits`][Bits::bits] returns a [`u128`], a range can be at
+/// most 128 bits wide.
+///
+/// The words are stored least significant word first.
+///
+/// # Example
+///
+/// ```
+/// use intbits::{Bits, WideInt};
+///
+/// let mut reg = WideInt::<4>::ZERO;
+/// reg.set_bits(120..136, 0xABCD);
+/// assert_eq!(reg.words(), &[0, 0xCD << 56, 0xAB, 0]);
+/// assert_eq!(reg.bits(128..136), 0xAB);
+/// assert!(reg.bit(135));
+/// assert_eq!((reg >> 120).bits(0..16), 0xABCD);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WideInt<const WORDS: usize> {
+	words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> WideInt<WORDS> {
+	/// The value 0.
+	pub const ZERO: Self = Self { words: [0; WORDS] };
+
+	/// The largest value, with all bits set.
+	pub const MAX: Self = Self { words: [!0; WORDS] };
+
+	/// Create a value from its words, least significant word first.
+	#[inline]
+	pub const fn from_words(words: [u64; WORDS]) -> Self {
+		Self { words }
+	}
+
+	/// Get the words, least significant word first.
+	#[inline]
+	pub const fn words(&self) -> &[u64; WORDS] {
+		&self.words
+	}
+
+	/// Reverse the order of the bytes.
+	#[inline]
+	pub fn swap_bytes(self) -> Self {
+		let mut words = self.words;
+		words.reverse();
+		for w in &mut words {
+			*w = w.swap_bytes();
+		}
+		Self { words }
+	}
+
+	/// The number of bits that are set.
+	#[inline]
+	pub fn count_ones(&self) -> u32 {
+		self.words.iter().map(|w| w.count_ones()).sum()
+	}
+}
+
+impl<const WORDS: usize> Default for WideInt<WORDS> {
+	#[inline]
+	fn default() -> Self {
+		Self::ZERO
+	}
+}
+
+impl<const WORDS: usize> From<u128> for WideInt<WORDS> {
+	/// Convert a `u128`.
+	///
+	/// Panics if it doesn't fit.
+	#[inline]
+	fn from(v: u128) -> Self {
+		let mut words = [0; WORDS];
+		let n = WORDS.min(2);
+		for (i, w) in words[..n].iter_mut().enumerate() {
+			*w = (v >> (64 * i)) as u64;
+		}
+		check!(
+			v.checked_shr(64 * n as u32).unwrap_or(0) == 0,
+			bits_outside_range
+		);
+		Self { words }
+	}
+}
+
+impl<const WORDS: usize> Ord for WideInt<WORDS> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.words.iter().rev().cmp(other.words.iter().rev())
+	}
+}
+
+impl<const WORDS: usize> PartialOrd for WideInt<WORDS> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<const WORDS: usize> fmt::Debug for WideInt<WORDS> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{:#X}", self)
+	}
+}
+
+/// Formatted as its words in hexadecimal, least significant word first.
+#[cfg(feature = "defmt")]
+impl<const WORDS: usize> defmt::Format for WideInt<WORDS> {
+	fn format(&self, f: defmt::Formatter) {
+		defmt::write!(f, "WideInt({=[?]:#x})", &self.words[..]);
+	}
+}
+
+impl<const WORDS: usize> fmt::UpperHex for WideInt<WORDS> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if f.alternate() {
+			f.write_str("0x")?;
+		}
+		let mut words = self.words.iter().rev().skip_while(|&&w| w == 0);
+		match words.next() {
+			Some(w) => write!(f, "{:X}", w)?,
+			None => f.write_str("0")?,
+		}
+		for w in words {
+			write!(f, "{:016X}", w)?;
+		}
+		Ok(())
+	}
+}
+
+impl<const WORDS: usize> Not for WideInt<WORDS> {
+	type Output = Self;
+
+	#[inline]
+	fn not(mut self) -> Self {
+		for w in &mut self.words {
+			*w = !*w;
+		}
+		self
+	}
+}
+
+macro_rules! bitwise {
+	($($op:ident $f:ident $o:tt),*) => {$(
+		impl<const WORDS: usize> $op for WideInt<WORDS> {
+			type Output = Self;
+
+			#[inline]
+			fn $f(mut self, other: Self) -> Self {
+				for (a, b) in self.words.iter_mut().zip(&other.words) {
+					*a $o *b;
+				}
+				self
+			}
+		}
+	)*};
+}
+
+bitwise!(BitAnd bitand &=, BitOr bitor |=, BitXor bitxor ^=);
+
+impl<const WORDS: usize> Shl<u32> for WideInt<WORDS> {
+	type Output = Self;
+
+	/// Shift left.
+	///
+	/// Panics if the shift is not less than the number of bits.
+	#[inline]
+	fn shl(self, n: u32) -> Self {
+		check!(n < Self::N_BITS, shift_out_of_range);
+		let (words, bits) = ((n / 64) as usize, n % 64);
+		let mut r = Self::ZERO;
+		for i in words..WORDS {
+			r.words[i] = self.words[i - words] << bits;
+			if bits != 0 && i > words {
+				r.words[i] |= self.words[i - words - 1] >> (64 - bits);
+			}
+		}
+		r
+	}
+}
+
+impl<const WORDS: usize> Shr<u32> for WideInt<WORDS> {
+	type Output = Self;
+
+	/// Shift right.
+	///
+	/// Panics if the shift is not less than the number of bits.
+	#[inline]
+	fn shr(self, n: u32) -> Self {
+		check!(n < Self::N_BITS, shift_out_of_range);
+		let (words, bits) = ((n / 64) as usize, n % 64);
+		let mut r = Self::ZERO;
+		for i in 0..WORDS - words {
+			r.words[i] = self.words[i + words] >> bits;
+			if bits != 0 && i + words + 1 < WORDS {
+				r.words[i] |= self.words[i + words + 1] << (64 - bits);
+			}
+		}
+		r
+	}
+}
+
+forward_bits!([const WORDS: usize] WideInt<WORDS>, u128, WORDS as u32 * 64, |v| v.swap_bytes(), WideInt::MAX);
+
+macro_rules! wide_index {
+	($($i:ident),*) => {$(
+		impl<const WORDS: usize> BitsIndex<WideInt<WORDS>> for $i {
+			#[inline]
+			fn bit(v: WideInt<WORDS>, i: Self) -> bool {
+				v.words.bit(u32::try_from(i).unwrap_or(u32::MAX))
+			}
+
+			#[inline]
+			fn bits<R>(v: WideInt<WORDS>, range: R) -> <WideInt<WORDS> as Bits>::Bits
+			where
+				R: RangeBounds<Self>,
+			{
+				v.words.bits(u32_bounds(&range))
+			}
+
+			#[inline]
+			fn set_bit(v: &mut WideInt<WORDS>, i: Self, bit: bool) {
+				v.words.set_bit(u32::try_from(i).unwrap_or(u32::MAX), bit)
+			}
+
+			#[inline]
+			fn set_bits<R>(v: &mut WideInt<WORDS>, range: R, bits: <WideInt<WORDS> as Bits>::Bits)
+			where
+				R: RangeBounds<Self>,
+			{
+				v.words.set_bits(u32_bounds(&range), bits)
+			}
+		}
+	)*};
+}
+
+wide_index!(u8, u16, u32, u64, usize, i32);
+#[cfg(feature = "impl-signed")]
+wide_index!(i8, i16, i64, isize);
+#[cfg(feature = "impl-128")]
+wide_index!(u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+wide_index!(i128);
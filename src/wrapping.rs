@@ -0,0 +1,53 @@
+use super::{Bits, BitsIndex};
+use core::num::{Saturating, Wrapping};
+use core::ops::RangeBounds;
+
+macro_rules! wrapper {
+	($w:ident: $($t:ident $ut:ident),*) => {$(
+		forward_bits!([] $w<$t>, $ut, <$t as Bits>::N_BITS, |v| $w(v.0.swap_bytes()), $w(!0));
+
+		impl<I: BitsIndex<$t>> BitsIndex<$w<$t>> for I {
+			#[inline]
+			fn bit(v: $w<$t>, i: Self) -> bool {
+				<I as BitsIndex<$t>>::bit(v.0, i)
+			}
+
+			#[inline]
+			fn bits<R>(v: $w<$t>, range: R) -> $ut
+			where
+				R: RangeBounds<Self>,
+			{
+				<I as BitsIndex<$t>>::bits(v.0, range)
+			}
+
+			#[inline]
+			fn set_bit(v: &mut $w<$t>, i: Self, bit: bool) {
+				<I as BitsIndex<$t>>::set_bit(&mut v.0, i, bit)
+			}
+
+			#[inline]
+			fn set_bits<R>(v: &mut $w<$t>, range: R, bits: $ut)
+			where
+				R: RangeBounds<Self>,
+			{
+				<I as BitsIndex<$t>>::set_bits(&mut v.0, range, bits)
+			}
+		}
+	)*};
+}
+
+wrapper!(Wrapping: u8 u8, u16 u16, u32 u32, u64 u64, usize usize);
+#[cfg(feature = "impl-signed")]
+wrapper!(Wrapping: i8 u8, i16 u16, i32 u32, i64 u64, isize usize);
+#[cfg(feature = "impl-128")]
+wrapper!(Wrapping: u128 u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+wrapper!(Wrapping: i128 u128);
+
+wrapper!(Saturating: u8 u8, u16 u16, u32 u32, u64 u64, usize usize);
+#[cfg(feature = "impl-signed")]
+wrapper!(Saturating: i8 u8, i16 u16, i32 u32, i64 u64, isize usize);
+#[cfg(feature = "impl-128")]
+wrapper!(Saturating: u128 u128);
+#[cfg(all(feature = "impl-128", feature = "impl-signed"))]
+wrapper!(Saturating: i128 u128);
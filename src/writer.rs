@@ -0,0 +1,380 @@
+use super::{BitOrder, OutOfBits, SliceBits};
+
+/// Storage that a [`BitWriter`] writes into.
+///
+/// This is implemented for byte slices and arrays, which have a fixed size,
+/// and for mutable references to any sink. With the `heapless` and `arrayvec`
+/// features, it is also implemented for `heapless::Vec<u8, N>` and
+/// `arrayvec::ArrayVec<u8, N>`, which grow as bits are written, up to their
+/// capacity.
+pub trait BitSink {
+	/// The bytes that can currently be written to.
+	fn bytes_mut(&mut self) -> &mut [u8];
+
+	/// The maximum number of bytes.
+	fn capacity_bytes(&self) -> usize;
+
+	/// Make sure [`bytes_mut`][Self::bytes_mut] returns at least `len` bytes,
+	/// adding bytes with the value 0 as needed.
+	///
+	/// `len` is never more than [`capacity_bytes`][Self::capacity_bytes].
+	fn grow_to(&mut self, len: usize);
+}
+
+impl BitSink for [u8] {
+	#[inline]
+	fn bytes_mut(&mut self) -> &mut [u8] {
+		self
+	}
+
+	#[inline]
+	fn capacity_bytes(&self) -> usize {
+		self.len()
+	}
+
+	#[inline]
+	fn grow_to(&mut self, _: usize) {}
+}
+
+impl<const N: usize> BitSink for [u8; N] {
+	#[inline]
+	fn bytes_mut(&mut self) -> &mut [u8] {
+		self
+	}
+
+	#[inline]
+	fn capacity_bytes(&self) -> usize {
+		N
+	}
+
+	#[inline]
+	fn grow_to(&mut self, _: usize) {}
+}
+
+impl<S: BitSink + ?Sized> BitSink for &mut S {
+	#[inline]
+	fn bytes_mut(&mut self) -> &mut [u8] {
+		(**self).bytes_mut()
+	}
+
+	#[inline]
+	fn capacity_bytes(&self) -> usize {
+		(**self).capacity_bytes()
+	}
+
+	#[inline]
+	fn grow_to(&mut self, len: usize) {
+		(**self).grow_to(len)
+	}
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> BitSink for heapless::Vec<u8, N> {
+	#[inline]
+	fn bytes_mut(&mut self) -> &mut [u8] {
+		self
+	}
+
+	#[inline]
+	fn capacity_bytes(&self) -> usize {
+		N
+	}
+
+	#[inline]
+	fn grow_to(&mut self, len: usize) {
+		if len > self.len() {
+			let _ = self.resize(len, 0);
+		}
+	}
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const N: usize> BitSink for arrayvec::ArrayVec<u8, N> {
+	#[inline]
+	fn bytes_mut(&mut self) -> &mut [u8] {
+		self
+	}
+
+	#[inline]
+	fn capacity_bytes(&self) -> usize {
+		N
+	}
+
+	#[inline]
+	fn grow_to(&mut self, len: usize) {
+		while self.len() < len {
+			self.push(0);
+		}
+	}
+}
+
+/// Writes values of any number of bits sequentially into a [`BitSink`], like
+/// a byte slice.
+///
+/// This is the counterpart of [`BitReader`][crate::BitReader], and uses the
+/// same conventions for the [`BitOrder`].
+///
+/// # Example
+///
+/// ```
+/// use intbits::{BitOrder, BitWriter};
+///
+/// let mut buf = [0xFF; 3];
+/// let mut w = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+/// w.write_bits(0b101, 3).unwrap();
+/// w.write_bits(0b0010111, 7).unwrap();
+/// assert_eq!(w.finish(), 10);
+/// assert_eq!(buf, [0xA5, 0xC0, 0xFF]);
+/// ```
+///
+/// With a growable sink, only the bytes that are (partially) written are
+/// added:
+///
+/// ```
+/// # #[cfg(feature = "heapless")] {
+/// use intbits::{BitOrder, BitWriter};
+///
+/// let mut w = BitWriter::new(heapless::Vec::<u8, 4>::new(), BitOrder::MsbFirst);
+/// w.write_bits(0b101, 3).unwrap();
+/// assert_eq!(w.into_inner(), [0xA0]);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BitWriter<S> {
+	buf: S,
+	pos: usize,
+	order: BitOrder,
+}
+
+impl<S: BitSink> BitWriter<S> {
+	/// Create a writer that starts at the first bit of `buf`.
+	pub fn new(buf: S, order: BitOrder) -> Self {
+		Self { buf, pos: 0, order }
+	}
+
+	/// Get a reference to the underlying sink.
+	pub fn get_ref(&self) -> &S {
+		&self.buf
+	}
+
+	/// Flush and return the underlying sink.
+	pub fn into_inner(mut self) -> S {
+		self.flush();
+		self.buf
+	}
+
+	/// The bit order of this writer.
+	pub fn order(&self) -> BitOrder {
+		self.order
+	}
+
+	/// The number of bits written so far.
+	pub fn position(&self) -> usize {
+		self.pos
+	}
+
+	/// The number of bits that still fit in the buffer.
+	pub fn remaining_bits(&self) -> usize {
+		self.buf.capacity_bytes() * 8 - self.pos
+	}
+
+	/// Whether the writer is at the start of a byte.
+	pub fn is_aligned(&self) -> bool {
+		self.pos.is_multiple_of(8)
+	}
+
+	/// Write the lowest `n` bits of `value`.
+	///
+	/// Panics if `n` is more than 64, or when the other bits of `value` are
+	/// not 0.
+	#[inline]
+	pub fn write_bits(&mut self, value: u64, n: u32) -> Result<(), OutOfBits> {
+		check!(n <= 64, bit_range_too_wide);
+		if n as usize > self.remaining_bits() {
+			return Err(OutOfBits);
+		}
+		let end = self.pos + n as usize;
+		self.buf.grow_to(end.div_ceil(8));
+		self.buf
+			.bytes_mut()
+			.set_bits(self.pos..end, value as u128, self.order);
+		self.pos = end;
+		Ok(())
+	}
+
+	/// Write a single bit.
+	#[inline]
+	pub fn write_bit(&mut self, bit: bool) -> Result<(), OutOfBits> {
+		self.write_bits(bit as u64, 1)
+	}
+
+	/// Write zero bits up to the start of the next byte, unless already at the
+	/// start of a byte.
+	pub fn align_to_byte(&mut self) -> Result<(), OutOfBits> {
+		let n = (8 - self.pos % 8) % 8;
+		self.write_bits(0, n as u32)
+	}
+
+	/// Set the unwritten bits of the last partially written byte to 0,
+	/// without advancing the writer.
+	///
+	/// Returns the number of bits written so far.
+	pub fn flush(&mut self) -> usize {
+		let end = self.pos.div_ceil(8) * 8;
+		self.buf.bytes_mut().set_bits(self.pos..end, 0, self.order);
+		self.pos
+	}
+
+	/// Flush and return the total number of bits written.
+	///
+	/// The number of bytes used is this number divided by eight, rounded up.
+	pub fn finish(mut self) -> usize {
+		self.flush()
+	}
+
+	/// Write an Exp-Golomb code for `x - 1`, for `x` below 2^65.
+	fn write_exp_golomb(&mut self, x: u128) -> Result<(), OutOfBits> {
+		let zeros = 127 - x.leading_zeros();
+		if 2 * zeros as usize + 1 > self.remaining_bits() {
+			return Err(OutOfBits);
+		}
+		self.write_bits(0, zeros)?;
+		self.write_bit(true)?;
+		self.write_bits((x & !(1 << zeros)) as u64, zeros)
+	}
+
+	/// Write an unsigned Exp-Golomb code, `ue(v)` in H.264.
+	///
+	/// Nothing is written if the code doesn't fit in the buffer.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitWriter};
+	///
+	/// let mut buf = [0; 2];
+	/// let mut w = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+	/// for v in 0..4 {
+	///     w.write_ue(v).unwrap();
+	/// }
+	/// w.finish();
+	/// assert_eq!(buf, [0b1010_0110, 0b0100_0000]);
+	/// ```
+	pub fn write_ue(&mut self, value: u64) -> Result<(), OutOfBits> {
+		self.write_exp_golomb(value as u128 + 1)
+	}
+
+	/// Write a signed Exp-Golomb code, `se(v)` in H.264.
+	///
+	/// The values 0, 1, -1, 2, -2, ... are represented by the unsigned codes
+	/// 0, 1, 2, 3, 4, ...
+	///
+	/// Nothing is written if the code doesn't fit in the buffer.
+	pub fn write_se(&mut self, value: i64) -> Result<(), OutOfBits> {
+		let k = if value > 0 {
+			2 * value as u128 - 1
+		} else {
+			2 * value.unsigned_abs() as u128
+		};
+		self.write_exp_golomb(k + 1)
+	}
+
+	/// Write a unary code: `n` 0 bits followed by a 1 bit.
+	///
+	/// Nothing is written if the code doesn't fit in the buffer.
+	pub fn write_unary(&mut self, n: u64) -> Result<(), OutOfBits> {
+		if n >= self.remaining_bits() as u64 {
+			return Err(OutOfBits);
+		}
+		let mut left = n as usize;
+		while left > 0 {
+			let chunk = left.min(64);
+			self.write_bits(0, chunk as u32)?;
+			left -= chunk;
+		}
+		self.write_bit(true)
+	}
+
+	/// Write a Rice code with parameter `k`: the unary quotient `value >> k`
+	/// followed by the `k` bit remainder.
+	///
+	/// Nothing is written if the code doesn't fit in the buffer.
+	///
+	/// Panics if `k` is more than 64.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitWriter};
+	///
+	/// let mut buf = [0; 1];
+	/// let mut w = BitWriter::new(&mut buf, BitOrder::MsbFirst);
+	/// w.write_rice(2, 10).unwrap();
+	/// w.finish();
+	/// assert_eq!(buf, [0b0011_0000]);
+	/// ```
+	pub fn write_rice(&mut self, k: u32, value: u64) -> Result<(), OutOfBits> {
+		check!(k <= 64, bit_range_too_wide);
+		let q = value.checked_shr(k).unwrap_or(0);
+		if q as u128 + 1 + k as u128 > self.remaining_bits() as u128 {
+			return Err(OutOfBits);
+		}
+		self.write_unary(q)?;
+		self.write_bits(value & (!0u64).checked_shr(64 - k).unwrap_or(0), k)
+	}
+
+	/// Write an unsigned LEB128 varint, starting at any bit position.
+	///
+	/// Each group of 8 bits is written like
+	/// [`write_bits(.., 8)`][Self::write_bits], so on a byte aligned stream
+	/// this is the usual byte based encoding.
+	///
+	/// Nothing is written if the code doesn't fit in the buffer.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitWriter};
+	///
+	/// let mut buf = [0; 3];
+	/// BitWriter::new(&mut buf, BitOrder::LsbFirst).write_uleb128(624485).unwrap();
+	/// assert_eq!(buf, [0xE5, 0x8E, 0x26]);
+	/// ```
+	pub fn write_uleb128(&mut self, mut value: u64) -> Result<(), OutOfBits> {
+		let groups = (64 - value.leading_zeros()).div_ceil(7).max(1);
+		if groups as usize * 8 > self.remaining_bits() {
+			return Err(OutOfBits);
+		}
+		for _ in 1..groups {
+			self.write_bits(value & 0x7F | 0x80, 8)?;
+			value >>= 7;
+		}
+		self.write_bits(value, 8)
+	}
+
+	/// Write a signed LEB128 varint, starting at any bit position.
+	///
+	/// Nothing is written if the code doesn't fit in the buffer.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use intbits::{BitOrder, BitWriter};
+	///
+	/// let mut buf = [0; 3];
+	/// BitWriter::new(&mut buf, BitOrder::LsbFirst).write_sleb128(-123456).unwrap();
+	/// assert_eq!(buf, [0xC0, 0xBB, 0x78]);
+	/// ```
+	pub fn write_sleb128(&mut self, mut value: i64) -> Result<(), OutOfBits> {
+		let significant = 65 - (value ^ value >> 63).leading_zeros();
+		let groups = significant.div_ceil(7);
+		if groups as usize * 8 > self.remaining_bits() {
+			return Err(OutOfBits);
+		}
+		for _ in 1..groups {
+			self.write_bits(value as u64 & 0x7F | 0x80, 8)?;
+			value >>= 7;
+		}
+		self.write_bits(value as u64 & 0x7F, 8)
+	}
+}
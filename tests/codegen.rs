@@ -0,0 +1,75 @@
+//! Checks that accessing constant ranges of bits compiles to straight-line
+//! code, without branches or panics.
+//!
+//! This compiles `examples/codegen.rs` in release mode, so it is ignored by
+//! default. Run it with `cargo test --test codegen -- --ignored`.
+
+use std::process::Command;
+
+const FUNCTIONS: &[&str] = &[
+	"get_bit",
+	"get_bits",
+	"get_bits_inclusive",
+	"get_bits_signed",
+	"set_bit",
+	"set_bits",
+	"with_bits",
+	"const_bits",
+	"set_const_bits",
+];
+
+/// Whether an instruction is a branch or a call, on x86 or ARM.
+fn is_branch(op: &str) -> bool {
+	op.starts_with('j')
+		|| op.starts_with("call")
+		|| op.starts_with("b.")
+		|| ["b", "bl", "blr", "br", "cbz", "cbnz", "tbz", "tbnz"].contains(&op)
+}
+
+fn asm() -> String {
+	let out = std::env::temp_dir().join(format!("intbits-codegen-{}.s", std::process::id()));
+	let status = Command::new(env!("CARGO"))
+		.current_dir(env!("CARGO_MANIFEST_DIR"))
+		.args(["rustc", "--release", "--example", "codegen", "--"])
+		.arg(format!("--emit=asm={}", out.display()))
+		.args(["-C", "codegen-units=1"])
+		.status()
+		.unwrap();
+	assert!(status.success());
+	let asm = std::fs::read_to_string(&out).unwrap();
+	let _ = std::fs::remove_file(out);
+	asm
+}
+
+/// Get the instructions of a function, without directives and labels.
+fn body<'a>(asm: &'a str, name: &str) -> Vec<&'a str> {
+	let start = asm
+		.lines()
+		.position(|l| l == format!("{}:", name) || l == format!("_{}:", name))
+		.unwrap_or_else(|| panic!("function {} not found", name));
+	asm.lines()
+		.skip(start + 1)
+		.map(str::trim)
+		.take_while(|l| !l.starts_with(".Lfunc_end") && !l.starts_with(".cfi_endproc"))
+		.filter(|l| !l.is_empty() && !l.starts_with('.') && !l.ends_with(':'))
+		.collect()
+}
+
+#[test]
+#[ignore]
+fn constant_ranges_are_branchless() {
+	let asm = asm();
+	for name in FUNCTIONS {
+		let body = body(&asm, name);
+		for line in &body {
+			let op = line.split_whitespace().next().unwrap();
+			assert!(
+				!is_branch(op) && !line.contains("panic"),
+				"{} contains a branch or call: {:?}\n{}",
+				name,
+				line,
+				body.join("\n")
+			);
+		}
+	}
+}